@@ -0,0 +1,125 @@
+//! Read a table of exchange rates from a file, or from standard input when no file is given, and
+//! print a cycle of exchanges that makes a profit, starting with a stake of 1000.
+//!
+//! ```text
+//! arbitrage [FILE] [--stake AMOUNT]
+//! ```
+//!
+//! Exits with status 1 if the input cannot be read, and 2 if the arguments are invalid.
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    process::ExitCode,
+};
+
+use algorithms_rust::graphs::arbitrage::ExchangeRates;
+
+const USAGE: &str = "usage: arbitrage [FILE] [--stake AMOUNT]";
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    file: Option<String>,
+    stake: f64,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            file: None,
+            stake: 1000.0,
+        }
+    }
+}
+
+impl Options {
+    fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut options = Options::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--stake" => {
+                    let value = args.next().ok_or("--stake requires a value")?;
+                    options.stake = value
+                        .parse()
+                        .ok()
+                        .filter(|&stake: &f64| stake > 0.0)
+                        .ok_or_else(|| {
+                            format!("--stake expects a positive amount, got `{}`", value)
+                        })?;
+                }
+                _ if arg.starts_with("--") => return Err(format!("unknown option `{}`", arg)),
+                _ if options.file.is_none() => options.file = Some(arg),
+                _ => return Err(format!("unexpected argument `{}`", arg)),
+            }
+        }
+        Ok(options)
+    }
+}
+
+fn run(options: &Options) -> io::Result<()> {
+    let reader: Box<dyn BufRead> = match &options.file {
+        Some(file) => Box::new(BufReader::new(File::open(file)?)),
+        None => Box::new(io::stdin().lock()),
+    };
+    let rates = ExchangeRates::from_reader(reader)?;
+
+    match rates.arbitrage() {
+        Some(exchanges) => {
+            let mut stake = options.stake;
+            for exchange in exchanges {
+                print!("{:10.5} {} = ", stake, exchange.from);
+                stake *= exchange.rate;
+                println!("{:10.5} {}", stake, exchange.to);
+            }
+        }
+        None => println!("No arbitrage opportunity"),
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("arbitrage: {}", e);
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            let input = options.file.as_deref().unwrap_or("standard input");
+            eprintln!("arbitrage: {}: {}", input, e);
+            ExitCode::from(1)
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::Options;
+
+    fn parse(args: &str) -> Result<Options, String> {
+        Options::parse(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse(""), Ok(Options::default()));
+        assert_eq!(
+            parse("rates.txt --stake 10"),
+            Ok(Options {
+                file: Some("rates.txt".to_string()),
+                stake: 10.0,
+            })
+        );
+        assert!(parse("--stake").is_err());
+        assert!(parse("--stake -1").is_err());
+        assert!(parse("--stake x").is_err());
+        assert!(parse("a b").is_err());
+    }
+}
@@ -0,0 +1,200 @@
+//! Compress a file, or standard input when no file is given, to standard output, or expand it
+//! back with `--expand`.
+//!
+//! ```text
+//! compress [FILE] [--codec rle|huffman|lzw] [--expand]
+//! compress resources/tinyTale.txt --codec lzw | compress --codec lzw --expand
+//! ```
+//!
+//! The default codec is Huffman. Exits with status 1 if the input cannot be read or expanded or
+//! the output cannot be written, and 2 if the arguments are invalid.
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    process::ExitCode,
+};
+
+use algorithms_rust::compression::Codec;
+
+const USAGE: &str = "usage: compress [FILE] [--codec rle|huffman|lzw] [--expand]";
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    file: Option<String>,
+    codec: Codec,
+    expand: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            file: None,
+            codec: Codec::Huffman,
+            expand: false,
+        }
+    }
+}
+
+impl Options {
+    fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut options = Options::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--codec" => {
+                    let value = args.next().ok_or("--codec requires a value")?;
+                    options.codec = value.parse()?;
+                }
+                "--expand" => options.expand = true,
+                _ if arg.starts_with("--") => return Err(format!("unknown option `{}`", arg)),
+                _ if options.file.is_none() => options.file = Some(arg),
+                _ => return Err(format!("unexpected argument `{}`", arg)),
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Writer that remembers its errors, to tell them apart from the errors of the input
+struct Output<W: Write> {
+    writer: W,
+    failed: bool,
+}
+
+impl<W: Write> Output<W> {
+    fn new(writer: W) -> Self {
+        Self {
+            writer,
+            failed: false,
+        }
+    }
+}
+
+impl<W: Write> Write for Output<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let result = self.writer.write(buf);
+        self.failed |= result.is_err();
+        result
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let result = self.writer.flush();
+        self.failed |= result.is_err();
+        result
+    }
+}
+
+/// An error and where it happened
+#[derive(Debug)]
+enum Error {
+    Input(io::Error),
+    Output(io::Error),
+}
+
+fn run(options: &Options) -> Result<(), Error> {
+    let reader: Box<dyn BufRead> = match &options.file {
+        Some(file) => Box::new(BufReader::new(File::open(file).map_err(Error::Input)?)),
+        None => Box::new(io::stdin().lock()),
+    };
+    // The codecs write a byte at a time, and standard output is line buffered
+    let mut output = Output::new(BufWriter::new(io::stdout().lock()));
+    let result = if options.expand {
+        options.codec.expand(reader, &mut output)
+    } else {
+        options.codec.compress(reader, &mut output)
+    };
+    let result = result.and_then(|()| output.flush());
+    match result {
+        Err(e) if output.failed => Err(Error::Output(e)),
+        result => result.map_err(Error::Input),
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("compress: {}", e);
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(Error::Input(e)) => {
+            let input = options.file.as_deref().unwrap_or("standard input");
+            eprintln!("compress: {}: {}", input, e);
+            ExitCode::from(1)
+        }
+        Err(Error::Output(e)) => {
+            eprintln!("compress: standard output: {}", e);
+            ExitCode::from(1)
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use std::io::{self, Write};
+
+    use algorithms_rust::compression::Codec;
+
+    use super::{Options, Output};
+
+    fn parse(args: &str) -> Result<Options, String> {
+        Options::parse(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse(""), Ok(Options::default()));
+        assert_eq!(
+            parse("--expand tinyTale.txt --codec lzw"),
+            Ok(Options {
+                file: Some("tinyTale.txt".to_string()),
+                codec: Codec::Lzw,
+                expand: true,
+            })
+        );
+        assert!(parse("--codec").is_err());
+        assert!(parse("--codec zip").is_err());
+        assert!(parse("--level 9").is_err());
+        assert!(parse("a b").is_err());
+    }
+
+    /// Writer that fails on every write
+    struct Closed;
+
+    impl Write for Closed {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_output_errors() {
+        let mut output = Output::new(Vec::new());
+        Codec::Lzw
+            .compress(&b"abracadabra"[..], &mut output)
+            .unwrap();
+        assert!(!output.failed);
+
+        let mut output = Output::new(Closed);
+        assert!(Codec::Lzw
+            .compress(&b"abracadabra"[..], &mut output)
+            .is_err());
+        assert!(output.failed);
+
+        // an input that is not in the format of the codec is not an output error
+        let mut output = Output::new(Vec::new());
+        assert!(Codec::Huffman.expand(&b"x"[..], &mut output).is_err());
+        assert!(!output.failed);
+    }
+}
@@ -0,0 +1,124 @@
+//! Read a symbol graph from a file and then names from standard input, one per line, and print
+//! a shortest chain of connections from the source to each name.
+//!
+//! ```text
+//! degrees-of-separation FILE DELIMITER SOURCE
+//! degrees-of-separation movies.txt "/" "Bacon, Kevin"
+//! ```
+//!
+//! Exits with status 1 if the input cannot be read, and 2 if the arguments are invalid or the
+//! source is not in the graph.
+use std::{
+    io::{self, BufRead},
+    process::ExitCode,
+};
+
+use algorithms_rust::graphs::symbolgraph::{DegreesOfSeparation, SymbolGraph};
+
+const USAGE: &str = "usage: degrees-of-separation FILE DELIMITER SOURCE";
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    file: String,
+    delimiter: String,
+    source: String,
+}
+
+impl Options {
+    fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut args = args.into_iter();
+        let mut next = |what: &str| args.next().ok_or(format!("missing {}", what));
+        let options = Options {
+            file: next("FILE")?,
+            delimiter: next("DELIMITER")?,
+            source: next("SOURCE")?,
+        };
+        if let Some(arg) = args.next() {
+            return Err(format!("unexpected argument `{}`", arg));
+        }
+        if options.delimiter.is_empty() {
+            return Err("the delimiter cannot be empty".to_string());
+        }
+        Ok(options)
+    }
+}
+
+fn run(sg: &SymbolGraph, separation: &DegreesOfSeparation) -> io::Result<()> {
+    for name in io::stdin().lock().lines() {
+        let name = name?;
+        if !sg.contains(&name) {
+            println!("Not in database");
+            continue;
+        }
+        match separation.path_to(&name) {
+            Some(path) => {
+                for name in path {
+                    println!("   {}", name);
+                }
+            }
+            None => println!("Not connected"),
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("degrees-of-separation: {}", e);
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let sg = match SymbolGraph::from_file(&options.file, &options.delimiter) {
+        Ok(sg) => sg,
+        Err(e) => {
+            eprintln!("degrees-of-separation: {}: {}", options.file, e);
+            return ExitCode::from(1);
+        }
+    };
+    let Some(separation) = DegreesOfSeparation::new(&sg, &options.source) else {
+        eprintln!(
+            "degrees-of-separation: `{}` is not in {}",
+            options.source, options.file
+        );
+        return ExitCode::from(2);
+    };
+
+    match run(&sg, &separation) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("degrees-of-separation: standard input: {}", e);
+            ExitCode::from(1)
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::Options;
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        Options::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse(&["movies.txt", "/", "Bacon, Kevin"]),
+            Ok(Options {
+                file: "movies.txt".to_string(),
+                delimiter: "/".to_string(),
+                source: "Bacon, Kevin".to_string(),
+            })
+        );
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["routes.txt", " "]).is_err());
+        assert!(parse(&["routes.txt", "", "JFK"]).is_err());
+        assert!(parse(&["routes.txt", " ", "JFK", "LAX"]).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+//! Run the frequency counter client on a file, or on standard input when no file is given, and
+//! print the most frequent words.
+//!
+//! ```text
+//! frequency-count [FILE] [--min-len N] [--backend bst|rbt|hash|trie] [--top K] [--stats]
+//! ```
+//!
+//! Exits with status 1 if the input cannot be read, and 2 if the arguments are invalid.
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    process::ExitCode,
+};
+
+use algorithms_rust::symboltables::{
+    frequencycounter::{Backend, StreamingFrequencyCounter},
+    symboltable::KeyValueSymbolTable,
+};
+
+const USAGE: &str = "usage: frequency-count [FILE] [--min-len N] \
+                     [--backend bst|rbt|hash|trie] [--top K] [--stats]";
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    file: Option<String>,
+    min_length: usize,
+    backend: Backend,
+    top: usize,
+    stats: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            file: None,
+            min_length: 1,
+            backend: Backend::BalancedTree,
+            top: 1,
+            stats: false,
+        }
+    }
+}
+
+impl Options {
+    fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut options = Options::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--min-len" => options.min_length = Options::number(&arg, args.next())?,
+                "--top" => options.top = Options::number(&arg, args.next())?,
+                "--backend" => {
+                    let value = args.next().ok_or("--backend requires a value")?;
+                    options.backend = value.parse()?;
+                }
+                "--stats" => options.stats = true,
+                _ if arg.starts_with("--") => return Err(format!("unknown option `{}`", arg)),
+                _ if options.file.is_none() => options.file = Some(arg),
+                _ => return Err(format!("unexpected argument `{}`", arg)),
+            }
+        }
+        Ok(options)
+    }
+
+    fn number(option: &str, value: Option<String>) -> Result<usize, String> {
+        let value = value.ok_or_else(|| format!("{} requires a value", option))?;
+        value
+            .parse()
+            .map_err(|_| format!("{} expects a number, got `{}`", option, value))
+    }
+}
+
+fn run(options: &Options) -> io::Result<()> {
+    let reader: Box<dyn BufRead> = match &options.file {
+        Some(file) => Box::new(BufReader::new(File::open(file)?)),
+        None => Box::new(io::stdin().lock()),
+    };
+
+    let mut counter =
+        StreamingFrequencyCounter::with_table(options.min_length, options.backend.table());
+    counter.add_reader(reader)?;
+
+    for (word, count) in counter.most_frequent(options.top) {
+        println!("{} {}", word, count);
+    }
+
+    if options.stats {
+        let frequency_counter = counter.checkpoint();
+        println!();
+        println!("backend:              {}", options.backend);
+        println!("words:                {}", frequency_counter.words);
+        println!("distinct:             {}", frequency_counter.distinct);
+        println!("memory (bytes):       {}", counter.table().memory());
+        println!("{}", frequency_counter.statistics);
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("frequency-count: {}", e);
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            let input = options.file.as_deref().unwrap_or("standard input");
+            eprintln!("frequency-count: {}: {}", input, e);
+            ExitCode::from(1)
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use algorithms_rust::symboltables::frequencycounter::Backend;
+
+    use super::Options;
+
+    fn parse(args: &str) -> Result<Options, String> {
+        Options::parse(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse(""), Ok(Options::default()));
+
+        let options = parse("tale.txt --min-len 8 --backend trie --top 5 --stats").unwrap();
+        assert_eq!(
+            options,
+            Options {
+                file: Some("tale.txt".to_string()),
+                min_length: 8,
+                backend: Backend::Trie,
+                top: 5,
+                stats: true,
+            }
+        );
+        assert_eq!(
+            parse("--backend rbt").unwrap().backend,
+            Backend::BalancedTree
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("--min-len").is_err());
+        assert!(parse("--top many").is_err());
+        assert!(parse("--backend splay").is_err());
+        assert!(parse("--verbose").is_err());
+        assert!(parse("a.txt b.txt").is_err());
+    }
+}
@@ -0,0 +1,119 @@
+//! Print the lines of a file, or of standard input when no file is given, that contain a match of
+//! a regular expression.
+//!
+//! ```text
+//! grep REGEXP [FILE]
+//! grep "(wor|spr)[a-z]+" resources/tinyTale.txt
+//! ```
+//!
+//! The expressions support concatenation, `|`, `*`, `+`, `?`, parentheses, classes such as
+//! `[a-z]` and `[^ ]`, the wildcard `.` and `\` to escape a metacharacter. Exits with status 1 if
+//! the input cannot be read, and 2 if the arguments or the expression are invalid.
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Write},
+    process::ExitCode,
+};
+
+use algorithms_rust::strings::regex::Grep;
+
+const USAGE: &str = "usage: grep REGEXP [FILE]";
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    regexp: String,
+    file: Option<String>,
+}
+
+impl Options {
+    fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut args = args.into_iter();
+        let regexp = args.next().ok_or("missing REGEXP")?;
+        let file = args.next();
+        if let Some(arg) = args.next() {
+            return Err(format!("unexpected argument `{}`", arg));
+        }
+        Ok(Options { regexp, file })
+    }
+}
+
+fn run(grep: &Grep, options: &Options) -> io::Result<()> {
+    let mut reader: Box<dyn BufRead> = match &options.file {
+        Some(file) => Box::new(BufReader::new(File::open(file)?)),
+        None => Box::new(io::stdin().lock()),
+    };
+    let mut out = io::stdout().lock();
+    let mut line = vec![];
+    // Lines are read as bytes, so that files that are not UTF-8 can be searched too
+    while reader.read_until(b'\n', &mut line)? > 0 {
+        let text = line.strip_suffix(b"\n").unwrap_or(&line);
+        let text = text.strip_suffix(b"\r").unwrap_or(text);
+        if grep.is_match(text) {
+            out.write_all(text)?;
+            out.write_all(b"\n")?;
+        }
+        line.clear();
+    }
+    out.flush()
+}
+
+fn main() -> ExitCode {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("grep: {}", e);
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    let grep = match Grep::new(&options.regexp) {
+        Ok(grep) => grep,
+        Err(e) => {
+            eprintln!(
+                "grep: invalid regular expression `{}`: {}",
+                options.regexp, e
+            );
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(&grep, &options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            let input = options.file.as_deref().unwrap_or("standard input");
+            eprintln!("grep: {}: {}", input, e);
+            ExitCode::from(1)
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::Options;
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        Options::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse(&["a|b"]),
+            Ok(Options {
+                regexp: "a|b".to_string(),
+                file: None,
+            })
+        );
+        assert_eq!(
+            parse(&["a b", "tinyTale.txt"]),
+            Ok(Options {
+                regexp: "a b".to_string(),
+                file: Some("tinyTale.txt".to_string()),
+            })
+        );
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["a", "b", "c"]).is_err());
+    }
+}
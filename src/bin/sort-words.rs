@@ -0,0 +1,107 @@
+//! Sort the words of a file, or of standard input when no file is given, with each of the string
+//! sorts and comparison sorts, and print how long each of them took.
+//!
+//! ```text
+//! sort-words [FILE] [--min-len N]
+//! ```
+//!
+//! Exits with status 1 if the input cannot be read, and 2 if the arguments are invalid.
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    process::ExitCode,
+};
+
+use algorithms_rust::{sorting::benchmark::SortBenchmark, symboltables::frequencycounter::Words};
+
+const USAGE: &str = "usage: sort-words [FILE] [--min-len N]";
+
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+    file: Option<String>,
+    min_length: usize,
+}
+
+impl Options {
+    fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut options = Options::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--min-len" => {
+                    let value = args.next().ok_or("--min-len requires a value")?;
+                    options.min_length = value
+                        .parse()
+                        .map_err(|_| format!("--min-len expects a number, got `{}`", value))?;
+                }
+                _ if arg.starts_with("--") => return Err(format!("unknown option `{}`", arg)),
+                _ if options.file.is_none() => options.file = Some(arg),
+                _ => return Err(format!("unexpected argument `{}`", arg)),
+            }
+        }
+        Ok(options)
+    }
+}
+
+fn run(options: &Options) -> io::Result<()> {
+    let reader: Box<dyn BufRead> = match &options.file {
+        Some(file) => Box::new(BufReader::new(File::open(file)?)),
+        None => Box::new(io::stdin().lock()),
+    };
+    let words = Words::new(reader)
+        .filter(|word| {
+            word.as_ref()
+                .map_or(true, |word| word.len() >= options.min_length)
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+
+    println!("words: {}", words.len());
+    print!("{}", SortBenchmark::table(&SortBenchmark::run(&words)));
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("sort-words: {}", e);
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            let input = options.file.as_deref().unwrap_or("standard input");
+            eprintln!("sort-words: {}: {}", input, e);
+            ExitCode::from(1)
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::Options;
+
+    fn parse(args: &str) -> Result<Options, String> {
+        Options::parse(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse(""), Ok(Options::default()));
+        assert_eq!(
+            parse("tale.txt --min-len 3"),
+            Ok(Options {
+                file: Some("tale.txt".to_string()),
+                min_length: 3,
+            })
+        );
+        assert!(parse("--min-len").is_err());
+        assert!(parse("--min-len x").is_err());
+        assert!(parse("a b").is_err());
+    }
+}
@@ -0,0 +1,94 @@
+//! Data Compression
+//!
+//! Lossless codecs that read a stream of bytes and write a shorter stream of bits, which expands
+//! back to the original bytes. The codecs read and write bits through `BinaryIn` and
+//! `BinaryOut`.
+use std::{
+    fmt::{self, Display},
+    io::{self, BufRead, Write},
+    str::FromStr,
+};
+
+pub mod binarystream;
+pub mod huffman;
+pub mod lzw;
+pub mod runlength;
+
+/// The codecs of the crate
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Codec {
+    RunLength,
+    Huffman,
+    Lzw,
+}
+
+impl Codec {
+    pub const ALL: [Codec; 3] = [Codec::RunLength, Codec::Huffman, Codec::Lzw];
+
+    /// Short name of the codec, as accepted by `from_str`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Codec::RunLength => "rle",
+            Codec::Huffman => "huffman",
+            Codec::Lzw => "lzw",
+        }
+    }
+
+    /// Compress the bytes read from `input` and write them to `output`
+    pub fn compress<R: BufRead, W: Write>(&self, input: R, output: W) -> io::Result<()> {
+        match self {
+            Codec::RunLength => runlength::compress(input, output),
+            Codec::Huffman => huffman::compress(input, output),
+            Codec::Lzw => lzw::compress(input, output),
+        }
+    }
+
+    /// Expand the compressed stream read from `input` and write the bytes to `output`
+    pub fn expand<R: BufRead, W: Write>(&self, input: R, output: W) -> io::Result<()> {
+        match self {
+            Codec::RunLength => runlength::expand(input, output),
+            Codec::Huffman => huffman::expand(input, output),
+            Codec::Lzw => lzw::expand(input, output),
+        }
+    }
+}
+
+impl Display for Codec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Codec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Codec::ALL
+            .into_iter()
+            .find(|codec| codec.name() == s)
+            .ok_or_else(|| format!("unknown codec `{}`", s))
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::Codec;
+
+    #[test]
+    fn test_codecs() {
+        let text = fs::read("resources/tinyTale.txt").unwrap();
+        for codec in Codec::ALL {
+            assert_eq!(codec.name().parse(), Ok(codec));
+            let mut compressed = vec![];
+            codec.compress(&text[..], &mut compressed).unwrap();
+            let mut expanded = vec![];
+            codec.expand(&compressed[..], &mut expanded).unwrap();
+            assert_eq!(expanded, text, "{}", codec);
+        }
+        assert!("zip".parse::<Codec>().is_err());
+    }
+}
@@ -0,0 +1,254 @@
+//! Binary Streams
+//!
+//! Read and write a stream of bits, most significant bit of each byte first. The output is padded
+//! with zeros to a whole number of bytes when it is flushed, so the formats that are not made of
+//! whole bytes have to record where their data ends.
+use std::io::{self, BufRead, Write};
+
+/// Stream of bits read from a byte reader
+#[derive(Debug)]
+pub struct BinaryIn<R> {
+    input: R,
+    buffer: u8,
+    n: usize, // number of bits left in the buffer
+}
+
+impl<R: BufRead> BinaryIn<R> {
+    pub fn new(input: R) -> Self {
+        Self {
+            input,
+            buffer: 0,
+            n: 0,
+        }
+    }
+
+    /// Refill the buffer if it is empty, and return whether there are bits left
+    fn fill(&mut self) -> io::Result<bool> {
+        if self.n == 0 {
+            let Some(&byte) = self.input.fill_buf()?.first() else {
+                return Ok(false);
+            };
+            self.input.consume(1);
+            self.buffer = byte;
+            self.n = 8;
+        }
+        Ok(true)
+    }
+
+    fn eof() -> io::Error {
+        io::Error::new(io::ErrorKind::UnexpectedEof, "unexpected end of input")
+    }
+
+    /// Is the input exhausted?
+    pub fn is_empty(&mut self) -> io::Result<bool> {
+        Ok(!self.fill()?)
+    }
+
+    pub fn read_bit(&mut self) -> io::Result<bool> {
+        if !self.fill()? {
+            return Err(Self::eof());
+        }
+        self.n -= 1;
+        Ok((self.buffer >> self.n) & 1 == 1)
+    }
+
+    /// Read the next 8 bits, which need not be aligned on a byte
+    pub fn read_byte(&mut self) -> io::Result<u8> {
+        if !self.fill()? {
+            return Err(Self::eof());
+        }
+        if self.n == 8 {
+            self.n = 0;
+            return Ok(self.buffer);
+        }
+        // Combine the bits left in the buffer with the leading bits of the next byte
+        let n = self.n;
+        let high = self.buffer << (8 - n);
+        self.n = 0;
+        if !self.fill()? {
+            return Err(Self::eof());
+        }
+        self.n = n;
+        Ok(high | self.buffer >> n)
+    }
+
+    /// Read `width` bits as an unsigned integer, most significant bit first
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is larger than 64.
+    pub fn read_bits(&mut self, width: usize) -> io::Result<u64> {
+        assert!(width <= 64, "cannot read {} bits into a u64", width);
+        let mut value = 0;
+        for _ in 0..width {
+            value = value << 1 | self.read_bit()? as u64;
+        }
+        Ok(value)
+    }
+
+    /// Read a 32-bit integer
+    pub fn read_u32(&mut self) -> io::Result<u32> {
+        Ok(self.read_bits(32)? as u32)
+    }
+
+    /// The underlying reader, which loses the bits left in the buffer
+    pub fn into_inner(self) -> R {
+        self.input
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Stream of bits written to a byte writer.
+///
+/// The last byte is only written, padded with zeros, by `flush` or `finish`.
+#[derive(Debug)]
+pub struct BinaryOut<W: Write> {
+    output: W,
+    buffer: u8,
+    n: usize, // number of bits in the buffer
+}
+
+impl<W: Write> BinaryOut<W> {
+    pub fn new(output: W) -> Self {
+        Self {
+            output,
+            buffer: 0,
+            n: 0,
+        }
+    }
+
+    pub fn write_bit(&mut self, bit: bool) -> io::Result<()> {
+        self.buffer = self.buffer << 1 | bit as u8;
+        self.n += 1;
+        if self.n == 8 {
+            self.output.write_all(&[self.buffer])?;
+            self.buffer = 0;
+            self.n = 0;
+        }
+        Ok(())
+    }
+
+    /// Write 8 bits, which need not be aligned on a byte
+    pub fn write_byte(&mut self, byte: u8) -> io::Result<()> {
+        if self.n == 0 {
+            return self.output.write_all(&[byte]);
+        }
+        self.write_bits(byte as u64, 8)
+    }
+
+    /// Write the `width` low bits of `value`, most significant bit first
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is larger than 64 or if `value` does not fit in `width` bits.
+    pub fn write_bits(&mut self, value: u64, width: usize) -> io::Result<()> {
+        assert!(width <= 64, "cannot write {} bits of a u64", width);
+        assert!(
+            width == 64 || value >> width == 0,
+            "{} does not fit in {} bits",
+            value,
+            width
+        );
+        for i in (0..width).rev() {
+            self.write_bit((value >> i) & 1 == 1)?;
+        }
+        Ok(())
+    }
+
+    /// Write a 32-bit integer
+    pub fn write_u32(&mut self, value: u32) -> io::Result<()> {
+        self.write_bits(value as u64, 32)
+    }
+
+    /// Write the bits in the buffer, padded with zeros to a byte, and flush the writer
+    pub fn flush(&mut self) -> io::Result<()> {
+        if self.n > 0 {
+            self.output.write_all(&[self.buffer << (8 - self.n)])?;
+            self.buffer = 0;
+            self.n = 0;
+        }
+        self.output.flush()
+    }
+
+    /// Flush the stream and return the underlying writer
+    pub fn finish(mut self) -> io::Result<W> {
+        self.flush()?;
+        Ok(self.output)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use std::io::ErrorKind;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::{BinaryIn, BinaryOut};
+
+    #[test]
+    fn test_write() {
+        let mut out = BinaryOut::new(vec![]);
+        out.write_bit(true).unwrap();
+        out.write_byte(0xff).unwrap();
+        out.write_bits(0b01, 2).unwrap();
+        assert_eq!(out.finish().unwrap(), vec![0xff, 0b1010_0000]);
+
+        let mut out = BinaryOut::new(vec![]);
+        out.write_byte(b'A').unwrap();
+        out.write_u32(0x01020304).unwrap();
+        assert_eq!(out.finish().unwrap(), vec![b'A', 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_read() {
+        let bytes = [0xff, 0b1010_0000];
+        let mut input = BinaryIn::new(&bytes[..]);
+        assert!(input.read_bit().unwrap());
+        assert_eq!(input.read_byte().unwrap(), 0xff);
+        assert_eq!(input.read_bits(2).unwrap(), 0b01);
+        assert!(!input.is_empty().unwrap());
+        assert_eq!(input.read_bits(5).unwrap(), 0);
+        assert!(input.is_empty().unwrap());
+        assert_eq!(
+            input.read_bit().unwrap_err().kind(),
+            ErrorKind::UnexpectedEof
+        );
+        assert_eq!(
+            BinaryIn::new(&[1u8][..]).read_u32().unwrap_err().kind(),
+            ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..20 {
+            let values: Vec<(u64, usize)> = (0..100)
+                .map(|_| {
+                    let width = rng.gen_range(0..=64);
+                    let value = if width == 0 {
+                        0
+                    } else {
+                        rng.gen::<u64>() >> (64 - width)
+                    };
+                    (value, width)
+                })
+                .collect();
+            let mut out = BinaryOut::new(vec![]);
+            for &(value, width) in &values {
+                out.write_bits(value, width).unwrap();
+            }
+            let bytes = out.finish().unwrap();
+            let bits: usize = values.iter().map(|&(_, width)| width).sum();
+            assert_eq!(bytes.len(), bits.div_ceil(8));
+
+            let mut input = BinaryIn::new(&bytes[..]);
+            for &(value, width) in &values {
+                assert_eq!(input.read_bits(width).unwrap(), value);
+            }
+        }
+    }
+}
@@ -0,0 +1,261 @@
+//! Huffman Coding
+//!
+//! Each byte is coded with a variable number of bits, given by its path in a binary trie with
+//! the bytes at the leaves: 0 for a left link and 1 for a right link. Since no code is a prefix
+//! of another, the codes need no separators. The trie is built from the frequencies of the bytes
+//! of the input by merging the two least frequent tries until one is left, which gives the
+//! shortest prefix-free code for this input.
+//!
+//! The compressed stream starts with the trie, written in preorder with a 0 bit for an internal
+//! node and a 1 bit followed by the byte for a leaf, and the number of bytes of the input as a
+//! 64-bit integer, followed by the codes.
+use std::{
+    cmp::Ordering,
+    io::{self, BufRead, Read, Write},
+};
+
+use super::binarystream::{BinaryIn, BinaryOut};
+use crate::priorityqueues::binaryheap::MinPQ;
+
+/// Number of different bytes
+const R: usize = 256;
+
+/// Node of the Huffman trie
+#[derive(Debug)]
+struct Node {
+    byte: u8,    // used by the leaves only
+    freq: usize, // used to build the trie only
+    left: Option<Box<Node>>,
+    right: Option<Box<Node>>,
+}
+
+impl Node {
+    fn leaf(byte: u8, freq: usize) -> Self {
+        Self {
+            byte,
+            freq,
+            left: None,
+            right: None,
+        }
+    }
+
+    fn internal(left: Node, right: Node) -> Self {
+        Self {
+            byte: 0,
+            freq: left.freq + right.freq,
+            left: Some(Box::new(left)),
+            right: Some(Box::new(right)),
+        }
+    }
+
+    fn is_leaf(&self) -> bool {
+        self.left.is_none() && self.right.is_none()
+    }
+}
+
+// The priority queue orders the tries by frequency
+impl PartialEq for Node {
+    fn eq(&self, other: &Self) -> bool {
+        self.freq == other.freq
+    }
+}
+
+impl Eq for Node {}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.freq.cmp(&other.freq)
+    }
+}
+
+/// Build the Huffman trie of the given frequencies. When only one byte occurs, the trie is a
+/// single leaf and the codes are empty.
+fn build_trie(freq: &[usize; R]) -> Node {
+    let mut pq = MinPQ::new();
+    for (byte, &f) in freq.iter().enumerate() {
+        if f > 0 {
+            pq.insert(Node::leaf(byte as u8, f));
+        }
+    }
+    while pq.len() > 1 {
+        let left = pq.del_min().unwrap();
+        let right = pq.del_min().unwrap();
+        pq.insert(Node::internal(left, right));
+    }
+    pq.del_min().unwrap_or_else(|| Node::leaf(0, 0))
+}
+
+/// Fill in the code of each leaf of the trie, as a vector of bits
+fn build_code(node: &Node, code: &mut Vec<bool>, codes: &mut [Vec<bool>]) {
+    match (&node.left, &node.right) {
+        (Some(left), Some(right)) => {
+            code.push(false);
+            build_code(left, code, codes);
+            code.pop();
+            code.push(true);
+            build_code(right, code, codes);
+            code.pop();
+        }
+        _ => codes[node.byte as usize] = code.clone(),
+    }
+}
+
+fn write_trie<W: Write>(node: &Node, output: &mut BinaryOut<W>) -> io::Result<()> {
+    match (&node.left, &node.right) {
+        (Some(left), Some(right)) => {
+            output.write_bit(false)?;
+            write_trie(left, output)?;
+            write_trie(right, output)
+        }
+        _ => {
+            output.write_bit(true)?;
+            output.write_byte(node.byte)
+        }
+    }
+}
+
+/// Read a trie written by `write_trie`. A trie with R leaves has a height less than R, so a
+/// deeper one is rejected.
+fn read_trie<R: BufRead>(input: &mut BinaryIn<R>, depth: usize) -> io::Result<Node> {
+    if depth >= R {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Huffman trie is too deep",
+        ));
+    }
+    if input.read_bit()? {
+        return Ok(Node::leaf(input.read_byte()?, 0));
+    }
+    let left = read_trie(input, depth + 1)?;
+    let right = read_trie(input, depth + 1)?;
+    Ok(Node::internal(left, right))
+}
+
+/// Compress the bytes read from `input` and write them to `output`. The input is read in full,
+/// since it is scanned twice: once to count the frequencies and once to code the bytes.
+pub fn compress<R: Read, W: Write>(mut input: R, output: W) -> io::Result<()> {
+    let mut data = vec![];
+    input.read_to_end(&mut data)?;
+
+    let mut freq = [0; R];
+    for &byte in &data {
+        freq[byte as usize] += 1;
+    }
+    let root = build_trie(&freq);
+    let mut codes = vec![vec![]; R];
+    build_code(&root, &mut vec![], &mut codes);
+
+    let mut output = BinaryOut::new(output);
+    write_trie(&root, &mut output)?;
+    output.write_bits(data.len() as u64, 64)?;
+    for &byte in &data {
+        for &bit in &codes[byte as usize] {
+            output.write_bit(bit)?;
+        }
+    }
+    output.finish()?;
+    Ok(())
+}
+
+/// Expand the codes read from `input` and write the bytes to `output`
+pub fn expand<R: BufRead, W: Write>(input: R, mut output: W) -> io::Result<()> {
+    let mut input = BinaryIn::new(input);
+    let root = read_trie(&mut input, 0)?;
+    let length = input.read_bits(64)?;
+    let mut buffer = io::BufWriter::new(&mut output);
+    for _ in 0..length {
+        let mut node = &root;
+        while !node.is_leaf() {
+            let next = if input.read_bit()? {
+                &node.right
+            } else {
+                &node.left
+            };
+            node = next.as_deref().expect("internal nodes have two children");
+        }
+        buffer.write_all(&[node.byte])?;
+    }
+    buffer.flush()
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::{build_code, build_trie, compress, expand, R};
+
+    fn round_trip(data: &[u8]) -> Vec<u8> {
+        let mut compressed = vec![];
+        compress(data, &mut compressed).unwrap();
+        let mut expanded = vec![];
+        expand(&compressed[..], &mut expanded).unwrap();
+        assert_eq!(expanded, data);
+        compressed
+    }
+
+    #[test]
+    fn test_codes() {
+        let mut freq = [0; R];
+        for &byte in b"ABRACADABRA!" {
+            freq[byte as usize] += 1;
+        }
+        let root = build_trie(&freq);
+        assert_eq!(root.freq, 12);
+        let mut codes = vec![vec![]; R];
+        build_code(&root, &mut vec![], &mut codes);
+        // A is the most frequent byte, and the code is optimal: 28 bits in all
+        assert_eq!(codes[b'A' as usize].len(), 1);
+        let bits: usize = b"ABRACADABRA!"
+            .iter()
+            .map(|&b| codes[b as usize].len())
+            .sum();
+        assert_eq!(bits, 28);
+    }
+
+    #[test]
+    fn test_compress() {
+        // Trie of 6 leaves and 5 internal nodes, length and codes
+        let compressed = round_trip(b"ABRACADABRA!");
+        assert_eq!(compressed.len(), (6 * 9 + 5 + 64 + 28usize).div_ceil(8));
+        round_trip(b"");
+        round_trip(b"aaaa");
+        round_trip(&[0, 255, 0]);
+
+        let text = fs::read("resources/tinyTale.txt").unwrap();
+        assert!(round_trip(&text).len() < text.len() * 3 / 4);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..50 {
+            let len = rng.gen_range(0..500);
+            let alphabet = rng.gen_range(1..=256);
+            let data: Vec<u8> = (0..len).map(|_| rng.gen_range(0..alphabet) as u8).collect();
+            round_trip(&data);
+        }
+    }
+
+    #[test]
+    fn test_invalid() {
+        let mut expanded = vec![];
+        assert!(expand(&[][..], &mut expanded).is_err());
+        // A trie of internal nodes only
+        assert!(expand(&[0; 64][..], &mut expanded).is_err());
+        // A length longer than the codes
+        let mut compressed = vec![];
+        compress(&b"ab"[..], &mut compressed).unwrap();
+        compressed.truncate(compressed.len() - 1);
+        assert!(expand(&compressed[..], &mut expanded).is_err());
+    }
+}
@@ -0,0 +1,174 @@
+//! LZW Compression
+//!
+//! Lempel-Ziv-Welch coding replaces the longest prefix of the input that is in a table of
+//! strings by its fixed-length codeword, and adds that prefix followed by the next byte to the
+//! table. The table starts with the R single bytes, and the expansion rebuilds the same table as
+//! it reads the codewords, so the table is not written to the output.
+//!
+//! The compression keeps the table in a ternary search trie, whose keys are strings of the
+//! characters U+0000 to U+00FF that stand for the bytes, so that the longest prefix is found by
+//! `longest_prefix_of`. The expansion keeps it in a vector indexed by codeword.
+use std::io::{self, BufRead, Read, Write};
+
+use super::binarystream::{BinaryIn, BinaryOut};
+use crate::symboltables::trie::TernarySearchTrie;
+
+/// Number of different bytes
+const R: usize = 256;
+/// Width of the codewords
+const W: usize = 12;
+/// Number of codewords
+const L: usize = 1 << W;
+/// Codeword that marks the end of the input
+const EOF: usize = R;
+
+/// Compress the bytes read from `input` and write the codewords to `output`
+pub fn compress<I: Read, O: Write>(mut input: I, output: O) -> io::Result<()> {
+    let mut data = vec![];
+    input.read_to_end(&mut data)?;
+    let text: String = data.iter().map(|&byte| char::from(byte)).collect();
+
+    let mut st = TernarySearchTrie::new();
+    for byte in 0..=u8::MAX {
+        st.put(&char::from(byte).to_string(), byte as usize);
+    }
+    let mut code = R + 1;
+    // Length of the longest key, which bounds the prefix to look for
+    let mut longest = 1;
+
+    let mut output = BinaryOut::new(output);
+    let mut rest = text.as_str();
+    while !rest.is_empty() {
+        let window = rest
+            .char_indices()
+            .nth(longest)
+            .map_or(rest, |(end, _)| &rest[..end]);
+        let prefix = st
+            .longest_prefix_of(window)
+            .expect("the table has a key for every byte");
+        output.write_bits(*st.get(prefix).unwrap() as u64, W)?;
+
+        let t = prefix.len();
+        if let Some(next) = rest[t..].chars().next() {
+            if code < L {
+                st.put(&rest[..t + next.len_utf8()], code);
+                code += 1;
+                longest = longest.max(prefix.chars().count() + 1);
+            }
+        }
+        rest = &rest[t..];
+    }
+    output.write_bits(EOF as u64, W)?;
+    output.finish()?;
+    Ok(())
+}
+
+/// Expand the codewords read from `input` and write the bytes to `output`
+pub fn expand<I: BufRead, O: Write>(input: I, mut output: O) -> io::Result<()> {
+    let invalid = |codeword| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid LZW codeword {}", codeword),
+        )
+    };
+    let mut input = BinaryIn::new(input);
+    let mut st: Vec<Vec<u8>> = (0..=u8::MAX).map(|byte| vec![byte]).collect();
+    st.push(vec![]); // EOF has no string
+
+    let mut codeword = input.read_bits(W)? as usize;
+    if codeword == EOF {
+        return Ok(());
+    }
+    let mut value = st.get(codeword).ok_or_else(|| invalid(codeword))?.clone();
+    let mut buffer = io::BufWriter::new(&mut output);
+    loop {
+        buffer.write_all(&value)?;
+        codeword = input.read_bits(W)? as usize;
+        if codeword == EOF {
+            break;
+        }
+        // The codeword that is about to be added is the current string followed by its own
+        // first byte
+        let s = match st.get(codeword) {
+            Some(s) => s.clone(),
+            None if codeword == st.len() => [&value[..], &value[..1]].concat(),
+            None => return Err(invalid(codeword)),
+        };
+        if st.len() < L {
+            st.push([&value[..], &s[..1]].concat());
+        }
+        value = s;
+    }
+    buffer.flush()
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::{compress, expand, W};
+    use crate::compression::binarystream::{BinaryIn, BinaryOut};
+
+    fn round_trip(data: &[u8]) -> Vec<u8> {
+        let mut compressed = vec![];
+        compress(data, &mut compressed).unwrap();
+        let mut expanded = vec![];
+        expand(&compressed[..], &mut expanded).unwrap();
+        assert_eq!(expanded, data);
+        compressed
+    }
+
+    #[test]
+    fn test_codewords() {
+        let compressed = round_trip(b"ABRACADABRABRABRA");
+        let mut input = BinaryIn::new(&compressed[..]);
+        let codewords: Vec<u64> = (0..13).map(|_| input.read_bits(W).unwrap()).collect();
+        assert_eq!(
+            codewords,
+            vec![0x41, 0x42, 0x52, 0x41, 0x43, 0x41, 0x44, 0x101, 0x103, 0x102, 0x108, 0x41, 0x100]
+        );
+        assert_eq!(compressed.len(), (13 * W).div_ceil(8));
+    }
+
+    #[test]
+    fn test_compress() {
+        round_trip(b"");
+        round_trip(b"a");
+        // The tricky case, where a codeword is used right after it is defined
+        round_trip(b"ABABABA");
+        round_trip(&[0, 255, 0, 255, 0, 255, 0]);
+
+        let text = fs::read("resources/tinyTale.txt").unwrap();
+        assert!(round_trip(&text).len() < text.len());
+        let runs = vec![b'a'; 100_000];
+        assert!(round_trip(&runs).len() < 1000);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..20 {
+            // Long enough inputs fill the table of codewords
+            let len = rng.gen_range(0..20_000);
+            let alphabet = rng.gen_range(1..=256);
+            let data: Vec<u8> = (0..len).map(|_| rng.gen_range(0..alphabet) as u8).collect();
+            round_trip(&data);
+        }
+    }
+
+    #[test]
+    fn test_invalid() {
+        let mut expanded = vec![];
+        assert!(expand(&[][..], &mut expanded).is_err());
+        // A codeword that has not been defined yet
+        let mut out = BinaryOut::new(vec![]);
+        out.write_bits(0x41, W).unwrap();
+        out.write_bits(0x200, W).unwrap();
+        let compressed = out.finish().unwrap();
+        assert!(expand(&compressed[..], &mut expanded).is_err());
+    }
+}
@@ -0,0 +1,100 @@
+//! Run-Length Coding
+//!
+//! The bits of the input are coded as the lengths of its runs of equal bits, in 8-bit counts
+//! that alternate between runs of 0s and runs of 1s, starting with 0s. A run longer than 255 is
+//! split by runs of length 0 of the other bit. This pays off on inputs with long runs, such as
+//! bitmaps, and doubles the size of typical text.
+use std::io::{self, BufRead, Write};
+
+use super::binarystream::{BinaryIn, BinaryOut};
+
+/// Largest count that fits in 8 bits
+const MAX_RUN: u8 = u8::MAX;
+
+/// Compress the bits read from `input` and write the counts to `output`
+pub fn compress<R: BufRead, W: Write>(input: R, output: W) -> io::Result<()> {
+    let mut input = BinaryIn::new(input);
+    let mut output = BinaryOut::new(output);
+    let mut run = 0;
+    let mut old = false;
+    while !input.is_empty()? {
+        let bit = input.read_bit()?;
+        if bit != old {
+            output.write_byte(run)?;
+            run = 0;
+            old = bit;
+        } else if run == MAX_RUN {
+            output.write_byte(run)?;
+            output.write_byte(0)?;
+            run = 0;
+        }
+        run += 1;
+    }
+    output.write_byte(run)?;
+    output.finish()?;
+    Ok(())
+}
+
+/// Expand the counts read from `input` and write the bits to `output`
+pub fn expand<R: BufRead, W: Write>(input: R, output: W) -> io::Result<()> {
+    let mut input = BinaryIn::new(input);
+    let mut output = BinaryOut::new(output);
+    let mut bit = false;
+    while !input.is_empty()? {
+        let run = input.read_byte()?;
+        for _ in 0..run {
+            output.write_bit(bit)?;
+        }
+        bit = !bit;
+    }
+    output.finish()?;
+    Ok(())
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::{compress, expand};
+
+    fn round_trip(data: &[u8]) -> Vec<u8> {
+        let mut compressed = vec![];
+        compress(data, &mut compressed).unwrap();
+        let mut expanded = vec![];
+        expand(&compressed[..], &mut expanded).unwrap();
+        assert_eq!(expanded, data);
+        compressed
+    }
+
+    #[test]
+    fn test_compress() {
+        assert_eq!(round_trip(&[0x00, 0xff]), vec![8, 8]);
+        assert_eq!(round_trip(&[0xff]), vec![0, 8]);
+        assert_eq!(round_trip(&[0x0f, 0x0f]), vec![4, 4, 4, 4]);
+        // 320 0s, then 8 1s
+        assert_eq!(
+            round_trip(&[vec![0; 40], vec![0xff]].concat()),
+            vec![255, 0, 65, 8]
+        );
+        assert_eq!(round_trip(&[]), vec![0]);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..50 {
+            let len = rng.gen_range(0..200);
+            // Bitmaps with long runs, and random bytes
+            let runs: Vec<u8> = (0..len)
+                .map(|i| if (i / 37) % 2 == 0 { 0 } else { 0xff })
+                .collect();
+            round_trip(&runs);
+            let noise: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+            round_trip(&noise);
+        }
+        let bitmap = [0u8; 1000];
+        assert!(round_trip(&bitmap).len() < 64);
+    }
+}
@@ -0,0 +1,12 @@
+//! Bags, Queues and Stacks
+//!
+//! Collections of items that differ in which item is removed next: a stack removes the most
+//! recently added item, a queue the least recently added one and a randomized queue one at
+//! random, while a bag only collects items to iterate over them. Each comes as a linked list,
+//! with constant time per operation in the worst case, and as a resizing array, with constant
+//! amortized time per operation and less memory per item.
+pub mod bag;
+pub mod deque;
+pub mod queue;
+pub mod randomizedqueue;
+pub mod stack;
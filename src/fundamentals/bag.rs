@@ -0,0 +1,214 @@
+//! Bags
+//!
+//! Collections that support adding items and iterating over them, but not removing them. The
+//! order of iteration is not specified: `LinkedBag` gives the items from the most recently added
+//! one, as a stack does, and `ResizingArrayBag` in the order in which they were added.
+use super::{
+    deque::{self, Deque},
+    stack::{self, LinkedStack, Stack},
+};
+
+/// The operations of a bag
+pub trait Bag<T> {
+    /// Add an item to the bag
+    fn add(&mut self, item: T);
+
+    /// Number of items in the bag
+    fn len(&self) -> usize;
+
+    /// Is the bag empty or not?
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Bag implemented as a linked list
+#[derive(Debug)]
+pub struct LinkedBag<T> {
+    items: LinkedStack<T>,
+}
+
+impl<T> Default for LinkedBag<T> {
+    fn default() -> Self {
+        Self {
+            items: LinkedStack::new(),
+        }
+    }
+}
+
+impl<T> LinkedBag<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Iterate over the items, from the most recently added one
+    pub fn iter(&self) -> stack::Iter<'_, T> {
+        self.items.iter()
+    }
+}
+
+impl<T> Bag<T> for LinkedBag<T> {
+    fn add(&mut self, item: T) {
+        self.items.push(item);
+    }
+
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+}
+
+impl<T> IntoIterator for LinkedBag<T> {
+    type Item = T;
+    type IntoIter = stack::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LinkedBag<T> {
+    type Item = &'a T;
+    type IntoIter = stack::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> FromIterator<T> for LinkedBag<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self {
+            items: iter.into_iter().collect(),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Bag implemented with a resizing array
+#[derive(Clone, Debug)]
+pub struct ResizingArrayBag<T> {
+    items: Deque<T>,
+}
+
+impl<T> Default for ResizingArrayBag<T> {
+    fn default() -> Self {
+        Self {
+            items: Deque::new(),
+        }
+    }
+}
+
+impl<T> ResizingArrayBag<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Iterate over the items, in the order in which they were added
+    pub fn iter(&self) -> deque::Iter<'_, T> {
+        self.items.iter()
+    }
+
+    /// Number of array accesses of the underlying `Deque`
+    pub fn array_accesses(&self) -> usize {
+        self.items.array_accesses()
+    }
+}
+
+impl<T> Bag<T> for ResizingArrayBag<T> {
+    fn add(&mut self, item: T) {
+        self.items.push_back(item);
+    }
+
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+}
+
+impl<T> IntoIterator for ResizingArrayBag<T> {
+    type Item = T;
+    type IntoIter = deque::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ResizingArrayBag<T> {
+    type Item = &'a T;
+    type IntoIter = deque::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> FromIterator<T> for ResizingArrayBag<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self {
+            items: iter.into_iter().collect(),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::{Bag, LinkedBag, ResizingArrayBag};
+
+    /// Add the numbers and compute their mean and standard deviation, as the Stats client does
+    fn check_bag<B: Bag<f64> + Default>() -> B {
+        let mut bag = B::default();
+        assert!(bag.is_empty());
+        for x in [
+            100.0, 99.0, 101.0, 120.0, 98.0, 107.0, 109.0, 81.0, 101.0, 90.0,
+        ] {
+            bag.add(x);
+        }
+        assert_eq!(bag.len(), 10);
+        bag
+    }
+
+    fn mean_and_std_dev<'a>(numbers: impl Iterator<Item = &'a f64> + Clone) -> (f64, f64) {
+        let n = numbers.clone().count() as f64;
+        let mean = numbers.clone().sum::<f64>() / n;
+        let variance = numbers.map(|x| (x - mean) * (x - mean)).sum::<f64>() / (n - 1.0);
+        (mean, variance.sqrt())
+    }
+
+    #[test]
+    fn test_linked_bag() {
+        let bag: LinkedBag<_> = check_bag();
+        let (mean, std_dev) = mean_and_std_dev(bag.iter());
+        assert_eq!(mean, 100.6);
+        assert!((std_dev - 10.511).abs() < 1e-3);
+        assert_eq!(bag.iter().next(), Some(&90.0));
+        assert_eq!(bag.into_iter().count(), 10);
+
+        let bag: LinkedBag<u32> = (0..5).collect();
+        assert_eq!((&bag).into_iter().sum::<u32>(), 10);
+    }
+
+    #[test]
+    fn test_resizing_array_bag() {
+        let bag: ResizingArrayBag<_> = check_bag();
+        let (mean, std_dev) = mean_and_std_dev(bag.iter());
+        assert_eq!(mean, 100.6);
+        assert!((std_dev - 10.511).abs() < 1e-3);
+        assert_eq!(bag.iter().next(), Some(&100.0));
+        assert_eq!(bag.into_iter().count(), 10);
+
+        let bag: ResizingArrayBag<u32> = (0..5).collect();
+        assert_eq!((&bag).into_iter().sum::<u32>(), 10);
+    }
+
+    #[test]
+    fn test_amortized_cost() {
+        let mut bag = ResizingArrayBag::new();
+        for n in 1..=1000 {
+            bag.add(n);
+            assert!(bag.array_accesses() <= 5 * n);
+        }
+    }
+}
@@ -0,0 +1,379 @@
+//! Deque
+//!
+//! A double-ended queue in a resizing circular array, which is also the storage of the
+//! array-based bags, stacks and queues. The array doubles when it is full and is halved when it
+//! is one quarter full, so that it is always between 25% and 100% full. A single operation that
+//! resizes the array takes linear time, but a sequence of N operations on an empty deque makes
+//! at most 5N array accesses: adding and removing items takes constant amortized time.
+use std::cell::Cell;
+
+/// Double-ended queue: supports adding and removing items at both ends
+#[derive(Clone, Debug)]
+pub struct Deque<T> {
+    a: Box<[Option<T>]>,
+    first: usize, // index of the front item in the array
+    n: usize,     // number of items
+    // Number of reads and writes of array entries
+    accesses: Cell<usize>,
+}
+
+impl<T> Default for Deque<T> {
+    fn default() -> Self {
+        Self {
+            a: Box::new([]),
+            first: 0,
+            n: 0,
+            accesses: Cell::new(0),
+        }
+    }
+}
+
+impl<T> Deque<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of items in the deque
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// Is the deque empty or not?
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Size of the underlying array
+    pub fn capacity(&self) -> usize {
+        self.a.len()
+    }
+
+    /// Number of reads and writes of array entries since the deque was created, including the
+    /// ones made when the array is resized. Iterating does not count.
+    pub fn array_accesses(&self) -> usize {
+        self.accesses.get()
+    }
+
+    /// Add an item at the front
+    pub fn push_front(&mut self, item: T) {
+        self.grow();
+        self.first = (self.first + self.a.len() - 1) % self.a.len();
+        self.write(self.first, item);
+        self.n += 1;
+    }
+
+    /// Add an item at the back
+    pub fn push_back(&mut self, item: T) {
+        self.grow();
+        self.write(self.index(self.n), item);
+        self.n += 1;
+    }
+
+    /// Remove and return the item at the front
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.n == 0 {
+            return None;
+        }
+        let item = self.take(self.first);
+        self.first = (self.first + 1) % self.a.len();
+        self.n -= 1;
+        self.shrink();
+        Some(item)
+    }
+
+    /// Remove and return the item at the back
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.n == 0 {
+            return None;
+        }
+        self.n -= 1;
+        let item = self.take(self.index(self.n));
+        self.shrink();
+        Some(item)
+    }
+
+    /// Return the item at the front, without removing it
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Return the item at the back, without removing it
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.n.checked_sub(1)?)
+    }
+
+    /// Return the item at position `i` from the front
+    pub fn get(&self, i: usize) -> Option<&T> {
+        if i >= self.n {
+            return None;
+        }
+        self.access();
+        self.a[self.index(i)].as_ref()
+    }
+
+    /// Remove and return the item at position `i` from the front, and replace it with the item
+    /// at the back
+    pub fn swap_remove_back(&mut self, i: usize) -> Option<T> {
+        if i >= self.n {
+            return None;
+        }
+        self.n -= 1;
+        let last = self.take(self.index(self.n));
+        let item = if i == self.n {
+            last
+        } else {
+            let k = self.index(i);
+            let item = self.take(k);
+            self.write(k, last);
+            item
+        };
+        self.shrink();
+        Some(item)
+    }
+
+    /// Iterate over the items from front to back
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            deque: self,
+            front: 0,
+            back: self.n,
+        }
+    }
+
+    // Index in the array of the item at position `i` from the front
+    fn index(&self, i: usize) -> usize {
+        (self.first + i) % self.a.len()
+    }
+
+    fn take(&mut self, k: usize) -> T {
+        self.access();
+        self.a[k].take().expect("missing item")
+    }
+
+    fn write(&mut self, k: usize, item: T) {
+        self.access();
+        self.a[k] = Some(item);
+    }
+
+    fn access(&self) {
+        self.accesses.set(self.accesses.get() + 1);
+    }
+
+    fn grow(&mut self) {
+        if self.n == self.a.len() {
+            self.resize((2 * self.n).max(1));
+        }
+    }
+
+    fn shrink(&mut self) {
+        if self.n > 0 && self.n == self.a.len() / 4 {
+            self.resize(self.a.len() / 2);
+        }
+    }
+
+    // Move the items to the front of a new array of the given size
+    fn resize(&mut self, capacity: usize) {
+        let mut a: Vec<Option<T>> = (0..capacity).map(|_| None).collect();
+        for (i, entry) in a.iter_mut().enumerate().take(self.n) {
+            let item = self.take(self.index(i));
+            self.access();
+            *entry = Some(item);
+        }
+        self.a = a.into_boxed_slice();
+        self.first = 0;
+    }
+}
+
+/// Iterator over the items of a deque, from front to back
+pub struct Iter<'a, T> {
+    deque: &'a Deque<T>,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        self.deque.a[self.deque.index(self.front - 1)].as_ref()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.back - self.front, Some(self.back - self.front))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        self.deque.a[self.deque.index(self.back)].as_ref()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            deque: self.deque,
+            front: self.front,
+            back: self.back,
+        }
+    }
+}
+
+/// Iterator that removes the items of a deque, from front to back
+pub struct IntoIter<T>(Deque<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len(), Some(self.0.len()))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for Deque<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Deque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> FromIterator<T> for Deque<T> {
+    /// Create a deque with the items in order from front to back
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque = Self::new();
+        for item in iter {
+            deque.push_back(item);
+        }
+        deque
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use std::collections::VecDeque;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::Deque;
+
+    #[test]
+    fn test_deque() {
+        let mut deque = Deque::new();
+        assert!(deque.is_empty());
+        assert_eq!(deque.pop_front(), None);
+        assert_eq!(deque.back(), None);
+        for i in 0..5 {
+            deque.push_back(i);
+            deque.push_front(-i);
+        }
+        assert_eq!(deque.len(), 10);
+        assert_eq!(deque.front(), Some(&-4));
+        assert_eq!(deque.back(), Some(&4));
+        assert_eq!(
+            deque.iter().copied().collect::<Vec<_>>(),
+            vec![-4, -3, -2, -1, 0, 0, 1, 2, 3, 4]
+        );
+        assert_eq!(deque.iter().next_back(), Some(&4));
+        assert_eq!(deque.swap_remove_back(1), Some(-3));
+        assert_eq!(deque.get(1), Some(&4));
+        assert_eq!(deque.swap_remove_back(8), Some(3));
+        assert_eq!(deque.swap_remove_back(8), None);
+        assert_eq!(
+            deque.into_iter().rev().collect::<Vec<_>>(),
+            vec![2, 1, 0, 0, -1, -2, 4, -4]
+        );
+
+        let deque: Deque<char> = "deque".chars().collect();
+        assert_eq!((&deque).into_iter().collect::<String>(), "deque");
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut deque = Deque::new();
+        let mut expected = VecDeque::new();
+        for i in 0..10_000 {
+            match rng.gen_range(0..5) {
+                0 => {
+                    deque.push_front(i);
+                    expected.push_front(i);
+                }
+                1 => {
+                    deque.push_back(i);
+                    expected.push_back(i);
+                }
+                2 => assert_eq!(deque.pop_front(), expected.pop_front()),
+                3 => assert_eq!(deque.pop_back(), expected.pop_back()),
+                _ => {
+                    let j = rng.gen_range(0..=expected.len());
+                    assert_eq!(deque.swap_remove_back(j), expected.swap_remove_back(j));
+                }
+            }
+            assert_eq!(deque.len(), expected.len());
+            assert!(deque.is_empty() || deque.len() > deque.capacity() / 4);
+        }
+        assert!(deque.iter().eq(expected.iter()));
+    }
+
+    #[test]
+    fn test_amortized_cost() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let mut deque = Deque::new();
+        let mut operations = 0;
+        for _ in 0..100 {
+            // A run of additions, then a run of removals, to make the array grow and shrink
+            for _ in 0..rng.gen_range(0..500) {
+                deque.push_back(0);
+                operations += 1;
+            }
+            for _ in 0..rng.gen_range(0..500) {
+                deque.pop_front();
+                operations += 1;
+            }
+            assert!(deque.array_accesses() <= 5 * operations);
+        }
+
+        // A single push into a full array copies all the items
+        let mut deque: Deque<u32> = (0..1024).collect();
+        assert_eq!(deque.capacity(), 1024);
+        let before = deque.array_accesses();
+        deque.push_front(0);
+        assert_eq!(deque.array_accesses() - before, 2 * 1024 + 1);
+        assert_eq!(deque.capacity(), 2048);
+    }
+}
@@ -0,0 +1,310 @@
+//! Queues
+//!
+//! First-in first-out collections: `dequeue` removes the least recently added item.
+//! `LinkedQueue` keeps the items in a linked list with links to both ends, and
+//! `ResizingArrayQueue` keeps them in a `Deque`.
+use std::ptr;
+
+use super::{
+    deque::{self, Deque},
+    stack::{self, Link, Node},
+};
+
+/// The operations of a queue
+pub trait Queue<T> {
+    /// Add an item at the back of the queue
+    fn enqueue(&mut self, item: T);
+
+    /// Remove and return the item at the front of the queue
+    fn dequeue(&mut self) -> Option<T>;
+
+    /// Number of items in the queue
+    fn len(&self) -> usize;
+
+    /// Is the queue empty or not?
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Queue implemented as a linked list, from the front to the back of the queue.
+///
+/// The nodes are owned through the `Box` links of the list, from the front, and the back of the
+/// queue is a raw pointer to the last node.
+#[derive(Debug)]
+pub struct LinkedQueue<T> {
+    first: Link<T>,     // front of the queue
+    last: *mut Node<T>, // back of the queue, null when the queue is empty
+    n: usize,
+}
+
+impl<T> Default for LinkedQueue<T> {
+    fn default() -> Self {
+        Self {
+            first: None,
+            last: ptr::null_mut(),
+            n: 0,
+        }
+    }
+}
+
+impl<T> LinkedQueue<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the item at the front of the queue, without removing it
+    pub fn peek(&self) -> Option<&T> {
+        self.first.as_ref().map(|node| &node.item)
+    }
+
+    /// Iterate over the items from the front to the back of the queue
+    pub fn iter(&self) -> stack::Iter<'_, T> {
+        stack::Iter::new(&self.first, self.n)
+    }
+}
+
+impl<T> Queue<T> for LinkedQueue<T> {
+    fn enqueue(&mut self, item: T) {
+        let link = if self.last.is_null() {
+            &mut self.first
+        } else {
+            // SAFETY: `last` points to the last node of the list, which is owned by the queue and
+            // not borrowed while the queue is borrowed mutably
+            unsafe { &mut (*self.last).next }
+        };
+        let node = link.insert(Box::new(Node { item, next: None }));
+        self.last = &mut **node;
+        self.n += 1;
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        let node = self.first.take()?;
+        let Node { item, next } = *node;
+        self.first = next;
+        if self.first.is_none() {
+            self.last = ptr::null_mut();
+        }
+        self.n -= 1;
+        Some(item)
+    }
+
+    fn len(&self) -> usize {
+        self.n
+    }
+}
+
+impl<T> Drop for LinkedQueue<T> {
+    // Unlink the nodes one by one, instead of recursively
+    fn drop(&mut self) {
+        self.last = ptr::null_mut();
+        let mut link = self.first.take();
+        while let Some(mut node) = link {
+            link = node.next.take();
+        }
+    }
+}
+
+/// Iterator that dequeues the items of a linked queue
+pub struct IntoIter<T>(LinkedQueue<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.dequeue()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len(), Some(self.0.len()))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for LinkedQueue<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LinkedQueue<T> {
+    type Item = &'a T;
+    type IntoIter = stack::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> FromIterator<T> for LinkedQueue<T> {
+    /// Enqueue the items in order, so that the first one is at the front of the queue
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut queue = Self::new();
+        for item in iter {
+            queue.enqueue(item);
+        }
+        queue
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Queue implemented with a resizing circular array
+#[derive(Clone, Debug)]
+pub struct ResizingArrayQueue<T> {
+    items: Deque<T>,
+}
+
+impl<T> Default for ResizingArrayQueue<T> {
+    fn default() -> Self {
+        Self {
+            items: Deque::new(),
+        }
+    }
+}
+
+impl<T> ResizingArrayQueue<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the item at the front of the queue, without removing it
+    pub fn peek(&self) -> Option<&T> {
+        self.items.front()
+    }
+
+    /// Iterate over the items from the front to the back of the queue
+    pub fn iter(&self) -> deque::Iter<'_, T> {
+        self.items.iter()
+    }
+
+    /// Number of array accesses of the underlying `Deque`
+    pub fn array_accesses(&self) -> usize {
+        self.items.array_accesses()
+    }
+}
+
+impl<T> Queue<T> for ResizingArrayQueue<T> {
+    fn enqueue(&mut self, item: T) {
+        self.items.push_back(item);
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        self.items.pop_front()
+    }
+
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+}
+
+impl<T> IntoIterator for ResizingArrayQueue<T> {
+    type Item = T;
+    type IntoIter = deque::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ResizingArrayQueue<T> {
+    type Item = &'a T;
+    type IntoIter = deque::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> FromIterator<T> for ResizingArrayQueue<T> {
+    /// Enqueue the items in order, so that the first one is at the front of the queue
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self {
+            items: iter.into_iter().collect(),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::{LinkedQueue, Queue, ResizingArrayQueue};
+
+    /// Run the "to be or not to be" client: enqueue the words and dequeue on "-"
+    fn check_queue<Q: Queue<&'static str> + Default>() -> Q {
+        let mut queue = Q::default();
+        assert!(queue.is_empty());
+        assert_eq!(queue.dequeue(), None);
+        let mut dequeued = vec![];
+        for s in "to be or not to - be - - that - - - is".split(' ') {
+            if s == "-" {
+                dequeued.push(queue.dequeue().unwrap());
+            } else {
+                queue.enqueue(s);
+            }
+        }
+        assert_eq!(dequeued, ["to", "be", "or", "not", "to", "be"]);
+        assert_eq!(queue.len(), 2);
+        queue
+    }
+
+    #[test]
+    fn test_linked_queue() {
+        let queue: LinkedQueue<_> = check_queue();
+        assert_eq!(queue.peek(), Some(&"that"));
+        assert_eq!(queue.iter().len(), 2);
+        assert_eq!(queue.iter().collect::<Vec<_>>(), [&"that", &"is"]);
+        assert_eq!(queue.into_iter().collect::<Vec<_>>(), ["that", "is"]);
+
+        let mut queue: LinkedQueue<u32> = (0..5).collect();
+        assert_eq!((&queue).into_iter().sum::<u32>(), 10);
+        // Emptying the queue and filling it again
+        while queue.dequeue().is_some() {}
+        queue.enqueue(7);
+        queue.enqueue(8);
+        assert_eq!(queue.peek(), Some(&7));
+        assert_eq!(queue.iter().collect::<Vec<_>>(), [&7, &8]);
+
+        // Dropping a long list does not overflow the call stack
+        let queue: LinkedQueue<u32> = (0..1_000_000).collect();
+        assert_eq!(queue.len(), 1_000_000);
+    }
+
+    #[test]
+    fn test_resizing_array_queue() {
+        let queue: ResizingArrayQueue<_> = check_queue();
+        assert_eq!(queue.peek(), Some(&"that"));
+        assert_eq!(queue.iter().collect::<Vec<_>>(), [&"that", &"is"]);
+        assert_eq!(queue.into_iter().collect::<Vec<_>>(), ["that", "is"]);
+
+        let queue: ResizingArrayQueue<u32> = (0..5).collect();
+        assert_eq!((&queue).into_iter().sum::<u32>(), 10);
+    }
+
+    #[test]
+    fn test_amortized_cost() {
+        // The items wrap around the end of the array as the queue slides along it
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut queue = ResizingArrayQueue::new();
+        let mut linked = LinkedQueue::new();
+        let mut operations = 0;
+        for i in 0..100_000 {
+            if rng.gen_bool(0.5) {
+                queue.enqueue(i);
+                linked.enqueue(i);
+            } else {
+                assert_eq!(queue.dequeue(), linked.dequeue());
+            }
+            operations += 1;
+        }
+        assert!(queue.iter().eq(linked.iter()));
+        assert!(queue.array_accesses() <= 5 * operations);
+    }
+}
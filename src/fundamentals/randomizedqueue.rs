@@ -0,0 +1,193 @@
+//! Randomized Queue
+//!
+//! A queue that removes an item chosen uniformly at random. The items are kept in a `Deque`:
+//! the random item is swapped with the last one and removed from the back, so that every
+//! operation takes constant amortized time.
+use std::{cell::RefCell, vec};
+
+use rand::{rngs::ThreadRng, seq::SliceRandom, Rng};
+
+use super::{deque::Deque, queue::Queue};
+
+/// Randomized queue: `dequeue` removes a random item, and the iterators give the items in
+/// random order. The random choices are made by the generator `R`, the thread-local one unless
+/// the queue is created `with_rng`.
+#[derive(Clone, Debug)]
+pub struct RandomizedQueue<T, R = ThreadRng> {
+    items: Deque<T>,
+    rng: RefCell<R>, // a cell, since sampling and iterating only borrow the queue
+}
+
+impl<T> Default for RandomizedQueue<T> {
+    fn default() -> Self {
+        Self::with_rng(ThreadRng::default())
+    }
+}
+
+impl<T> RandomizedQueue<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<T, R: Rng> RandomizedQueue<T, R> {
+    /// Create an empty queue that makes its random choices with the given generator
+    pub fn with_rng(rng: R) -> Self {
+        Self {
+            items: Deque::new(),
+            rng: RefCell::new(rng),
+        }
+    }
+
+    /// Return a random item, without removing it
+    pub fn sample(&self) -> Option<&T> {
+        if self.items.is_empty() {
+            return None;
+        }
+        let i = self.rng.borrow_mut().gen_range(0..self.items.len());
+        self.items.get(i)
+    }
+
+    /// Iterate over the items in random order. Each iterator uses its own order.
+    pub fn iter(&self) -> vec::IntoIter<&T> {
+        let mut items: Vec<&T> = self.items.iter().collect();
+        items.shuffle(&mut *self.rng.borrow_mut());
+        items.into_iter()
+    }
+
+    /// Number of array accesses of the underlying `Deque`
+    pub fn array_accesses(&self) -> usize {
+        self.items.array_accesses()
+    }
+}
+
+impl<T, R: Rng> Queue<T> for RandomizedQueue<T, R> {
+    fn enqueue(&mut self, item: T) {
+        self.items.push_back(item);
+    }
+
+    /// Remove and return a random item
+    fn dequeue(&mut self) -> Option<T> {
+        if self.items.is_empty() {
+            return None;
+        }
+        let i = self.rng.get_mut().gen_range(0..self.items.len());
+        self.items.swap_remove_back(i)
+    }
+
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+}
+
+impl<T, R: Rng> IntoIterator for RandomizedQueue<T, R> {
+    type Item = T;
+    type IntoIter = vec::IntoIter<T>;
+
+    /// Iterate over the items in random order
+    fn into_iter(self) -> Self::IntoIter {
+        let mut items: Vec<T> = self.items.into_iter().collect();
+        items.shuffle(&mut self.rng.into_inner());
+        items.into_iter()
+    }
+}
+
+impl<'a, T, R: Rng> IntoIterator for &'a RandomizedQueue<T, R> {
+    type Item = &'a T;
+    type IntoIter = vec::IntoIter<&'a T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> FromIterator<T> for RandomizedQueue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut queue = Self::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+impl<T, R: Rng> Extend<T> for RandomizedQueue<T, R> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.enqueue(item);
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::RandomizedQueue;
+    use crate::fundamentals::queue::Queue;
+
+    fn seeded_queue(n: usize, seed: u64) -> RandomizedQueue<usize, StdRng> {
+        let mut queue = RandomizedQueue::with_rng(StdRng::seed_from_u64(seed));
+        queue.extend(0..n);
+        queue
+    }
+
+    #[test]
+    fn test_randomized_queue() {
+        let mut queue: RandomizedQueue<u32> = (0..10).collect();
+        assert_eq!(queue.len(), 10);
+        assert!(*queue.sample().unwrap() < 10);
+
+        let mut items: Vec<u32> = queue.iter().copied().collect();
+        items.sort_unstable();
+        assert_eq!(items, (0..10).collect::<Vec<_>>());
+
+        let mut dequeued: Vec<u32> = (0..10).map(|_| queue.dequeue().unwrap()).collect();
+        assert!(queue.is_empty());
+        assert_eq!(queue.dequeue(), None);
+        assert_eq!(queue.sample(), None);
+        dequeued.sort_unstable();
+        assert_eq!(dequeued, (0..10).collect::<Vec<_>>());
+
+        let queue: RandomizedQueue<u32> = (0..5).collect();
+        assert_eq!((&queue).into_iter().sum::<u32>(), 10);
+        assert_eq!(queue.into_iter().sum::<u32>(), 10);
+    }
+
+    #[test]
+    fn test_uniform() {
+        // Each of 4 items is dequeued first about a quarter of the time
+        let mut first = [0; 4];
+        for seed in 0..4000 {
+            let mut queue = seeded_queue(4, seed);
+            first[queue.dequeue().unwrap()] += 1;
+        }
+        assert!(first.iter().all(|&count| (800..1200).contains(&count)));
+    }
+
+    #[test]
+    fn test_seeded() {
+        // the same seed makes the same choices
+        let (a, b) = (seeded_queue(100, 1), seeded_queue(100, 1));
+        assert_eq!(a.sample(), b.sample());
+        assert!(a.iter().eq(b.iter()));
+        assert!(a.into_iter().eq(b.into_iter()));
+    }
+
+    #[test]
+    fn test_amortized_cost() {
+        let mut queue = RandomizedQueue::with_rng(StdRng::seed_from_u64(1));
+        let mut operations = 0;
+        for round in 0..50 {
+            for i in 0..100 * round {
+                queue.enqueue(i);
+                operations += 1;
+            }
+            while queue.len() > round {
+                queue.dequeue();
+                operations += 1;
+            }
+        }
+        assert!(queue.array_accesses() <= 5 * operations);
+    }
+}
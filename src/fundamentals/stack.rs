@@ -0,0 +1,331 @@
+//! Stacks
+//!
+//! Last-in first-out collections: `pop` removes the most recently added item. `LinkedStack`
+//! keeps the items in a linked list, and `ResizingArrayStack` keeps them at the back of a
+//! `Deque`.
+use std::iter::Rev;
+
+use super::deque::{self, Deque};
+
+/// The operations of a stack
+pub trait Stack<T> {
+    /// Add an item at the top of the stack
+    fn push(&mut self, item: T);
+
+    /// Remove and return the item at the top of the stack
+    fn pop(&mut self) -> Option<T>;
+
+    /// Return the item at the top of the stack, without removing it
+    fn peek(&self) -> Option<&T>;
+
+    /// Number of items in the stack
+    fn len(&self) -> usize;
+
+    /// Is the stack empty or not?
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+pub(super) type Link<T> = Option<Box<Node<T>>>;
+
+#[derive(Debug)]
+pub(super) struct Node<T> {
+    pub(super) item: T,
+    pub(super) next: Link<T>,
+}
+
+/// Stack implemented as a linked list, from the top to the bottom of the stack
+#[derive(Debug)]
+pub struct LinkedStack<T> {
+    first: Link<T>, // top of the stack
+    n: usize,
+}
+
+impl<T> Default for LinkedStack<T> {
+    fn default() -> Self {
+        Self { first: None, n: 0 }
+    }
+}
+
+impl<T> LinkedStack<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Iterate over the items from the top to the bottom of the stack
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(&self.first, self.n)
+    }
+}
+
+impl<T> Stack<T> for LinkedStack<T> {
+    fn push(&mut self, item: T) {
+        let next = self.first.take();
+        self.first = Some(Box::new(Node { item, next }));
+        self.n += 1;
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let node = self.first.take()?;
+        self.first = node.next;
+        self.n -= 1;
+        Some(node.item)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.first.as_ref().map(|node| &node.item)
+    }
+
+    fn len(&self) -> usize {
+        self.n
+    }
+}
+
+impl<T> Drop for LinkedStack<T> {
+    // Unlink the nodes one by one, instead of recursively
+    fn drop(&mut self) {
+        let mut link = self.first.take();
+        while let Some(mut node) = link {
+            link = node.next.take();
+        }
+    }
+}
+
+/// Iterator over the items of a linked list, in order
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+    n: usize, // number of items left
+}
+
+impl<'a, T> Iter<'a, T> {
+    // Iterate over the n items of the list that starts at the given link
+    pub(super) fn new(first: &'a Link<T>, n: usize) -> Self {
+        Self {
+            next: first.as_deref(),
+            n,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next?;
+        self.next = node.next.as_deref();
+        self.n -= 1;
+        Some(&node.item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.n, Some(self.n))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            next: self.next,
+            n: self.n,
+        }
+    }
+}
+
+/// Iterator that pops the items of a linked stack
+pub struct IntoIter<T>(LinkedStack<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len(), Some(self.0.len()))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for LinkedStack<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LinkedStack<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> FromIterator<T> for LinkedStack<T> {
+    /// Push the items in order, so that the last one is at the top of the stack
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut stack = Self::new();
+        for item in iter {
+            stack.push(item);
+        }
+        stack
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Stack implemented with a resizing array, from the bottom to the top of the stack
+#[derive(Clone, Debug)]
+pub struct ResizingArrayStack<T> {
+    items: Deque<T>,
+}
+
+impl<T> Default for ResizingArrayStack<T> {
+    fn default() -> Self {
+        Self {
+            items: Deque::new(),
+        }
+    }
+}
+
+impl<T> ResizingArrayStack<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Iterate over the items from the top to the bottom of the stack
+    pub fn iter(&self) -> Rev<deque::Iter<'_, T>> {
+        self.items.iter().rev()
+    }
+
+    /// Number of array accesses of the underlying `Deque`
+    pub fn array_accesses(&self) -> usize {
+        self.items.array_accesses()
+    }
+}
+
+impl<T> Stack<T> for ResizingArrayStack<T> {
+    fn push(&mut self, item: T) {
+        self.items.push_back(item);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.items.pop_back()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.items.back()
+    }
+
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+}
+
+impl<T> IntoIterator for ResizingArrayStack<T> {
+    type Item = T;
+    type IntoIter = Rev<deque::IntoIter<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter().rev()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ResizingArrayStack<T> {
+    type Item = &'a T;
+    type IntoIter = Rev<deque::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> FromIterator<T> for ResizingArrayStack<T> {
+    /// Push the items in order, so that the last one is at the top of the stack
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self {
+            items: iter.into_iter().collect(),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::{LinkedStack, ResizingArrayStack, Stack};
+
+    /// Run the "to be or not to be" client: push the words and pop on "-"
+    fn check_stack<S: Stack<&'static str> + Default>() -> S {
+        let mut stack = S::default();
+        assert!(stack.is_empty());
+        assert_eq!(stack.pop(), None);
+        let mut popped = vec![];
+        for s in "to be or not to - be - - that - - - is".split(' ') {
+            if s == "-" {
+                popped.push(stack.pop().unwrap());
+            } else {
+                stack.push(s);
+            }
+        }
+        assert_eq!(popped, ["to", "be", "not", "that", "or", "be"]);
+        assert_eq!(stack.len(), 2);
+        assert_eq!(stack.peek(), Some(&"is"));
+        stack
+    }
+
+    #[test]
+    fn test_linked_stack() {
+        let stack: LinkedStack<_> = check_stack();
+        assert_eq!(stack.iter().len(), 2);
+        assert_eq!(stack.iter().collect::<Vec<_>>(), [&"is", &"to"]);
+        assert_eq!(stack.into_iter().collect::<Vec<_>>(), ["is", "to"]);
+
+        let stack: LinkedStack<u32> = (0..5).collect();
+        assert_eq!(stack.peek(), Some(&4));
+        assert_eq!((&stack).into_iter().sum::<u32>(), 10);
+
+        // Dropping a long list does not overflow the call stack
+        let stack: LinkedStack<u32> = (0..1_000_000).collect();
+        assert_eq!(stack.len(), 1_000_000);
+    }
+
+    #[test]
+    fn test_resizing_array_stack() {
+        let stack: ResizingArrayStack<_> = check_stack();
+        assert_eq!(stack.iter().collect::<Vec<_>>(), [&"is", &"to"]);
+        assert_eq!(stack.into_iter().collect::<Vec<_>>(), ["is", "to"]);
+
+        let stack: ResizingArrayStack<u32> = (0..5).collect();
+        assert_eq!(stack.peek(), Some(&4));
+        assert_eq!((&stack).into_iter().sum::<u32>(), 10);
+    }
+
+    #[test]
+    fn test_amortized_cost() {
+        let mut stack = ResizingArrayStack::new();
+        for n in [1000, 1024, 1025] {
+            let before = stack.array_accesses();
+            for i in 0..n {
+                stack.push(i);
+            }
+            for i in (0..n).rev() {
+                assert_eq!(stack.pop(), Some(i));
+            }
+            assert!(stack.array_accesses() - before <= 5 * 2 * n);
+        }
+        // peek reads one entry
+        stack.push(1);
+        let before = stack.array_accesses();
+        stack.peek();
+        assert_eq!(stack.array_accesses() - before, 1);
+    }
+}
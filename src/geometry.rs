@@ -0,0 +1,8 @@
+//! Geometric Algorithms
+//!
+//! Algorithms on points and segments in the plane: convex hulls, the closest pair of points, and
+//! the intersections of horizontal and vertical segments.
+pub mod closestpair;
+pub mod convexhull;
+pub mod point;
+pub mod segments;
@@ -0,0 +1,165 @@
+//! Closest Pair
+//!
+//! Divide and conquer: split the points sorted by x-coordinate into two halves, find the closest
+//! pair of each half, and then look for a closer pair with one point in each half. Such a pair is
+//! in the strip of width 2 delta around the dividing line, where delta is the smaller of the two
+//! distances, and each point of the strip only has to be compared with the few points that
+//! follow it in order of y-coordinate. Keeping the points of each half sorted by y-coordinate by
+//! merging, as mergesort does, gives a running time proportional to N log N.
+use super::point::Point2D;
+
+/// Two points at the smallest distance among a set of points
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClosestPair {
+    pub p: Point2D,
+    pub q: Point2D,
+    pub distance: f64,
+}
+
+/// Find the closest pair of points, if there are at least two points
+pub fn closest_pair(points: &[Point2D]) -> Option<ClosestPair> {
+    if points.len() < 2 {
+        return None;
+    }
+    let mut by_x = points.to_vec();
+    by_x.sort_by(|p, q| Point2D::x_order(p, q).then(Point2D::y_order(p, q)));
+    // Coincident points are next to each other
+    if let Some(pair) = by_x.windows(2).find(|pair| pair[0] == pair[1]) {
+        return Some(ClosestPair {
+            p: pair[0],
+            q: pair[1],
+            distance: 0.0,
+        });
+    }
+
+    let mut search = Search {
+        by_y: by_x.clone(),
+        aux: by_x.clone(),
+        best: ClosestPair {
+            p: by_x[0],
+            q: by_x[1],
+            distance: f64::INFINITY,
+        },
+    };
+    search.closest(&by_x, 0, by_x.len() - 1);
+    Some(search.best)
+}
+
+struct Search {
+    by_y: Vec<Point2D>, // by_y[lo..=hi] is sorted by y-coordinate after searching it
+    aux: Vec<Point2D>,
+    best: ClosestPair,
+}
+
+impl Search {
+    /// Find the closest pair in by_x[lo..=hi], and sort by_y[lo..=hi] by y-coordinate. Return
+    /// the smallest distance between two of these points.
+    fn closest(&mut self, by_x: &[Point2D], lo: usize, hi: usize) -> f64 {
+        if hi <= lo {
+            return f64::INFINITY;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let median = by_x[mid];
+        let delta1 = self.closest(by_x, lo, mid);
+        let delta2 = self.closest(by_x, mid + 1, hi);
+        let mut delta = delta1.min(delta2);
+        self.merge(lo, mid, hi);
+
+        // The points closer than delta to the dividing line, in order of y-coordinate
+        let mut m = 0;
+        for i in lo..=hi {
+            if (self.by_y[i].x() - median.x()).abs() < delta {
+                self.aux[m] = self.by_y[i];
+                m += 1;
+            }
+        }
+        for i in 0..m {
+            for j in i + 1..m {
+                if self.aux[j].y() - self.aux[i].y() >= delta {
+                    break;
+                }
+                let distance = self.aux[i].distance_to(&self.aux[j]);
+                if distance < delta {
+                    delta = distance;
+                    if distance < self.best.distance {
+                        self.best = ClosestPair {
+                            p: self.aux[i],
+                            q: self.aux[j],
+                            distance,
+                        };
+                    }
+                }
+            }
+        }
+        delta
+    }
+
+    /// Merge the sorted by_y[lo..=mid] and by_y[mid + 1..=hi]
+    fn merge(&mut self, lo: usize, mid: usize, hi: usize) {
+        self.aux[lo..=hi].copy_from_slice(&self.by_y[lo..=hi]);
+        let (mut i, mut j) = (lo, mid + 1);
+        for k in lo..=hi {
+            self.by_y[k] = if i > mid {
+                j += 1;
+                self.aux[j - 1]
+            } else if j > hi || Point2D::y_order(&self.aux[i], &self.aux[j]).is_le() {
+                i += 1;
+                self.aux[i - 1]
+            } else {
+                j += 1;
+                self.aux[j - 1]
+            };
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::closest_pair;
+    use crate::geometry::point::Point2D;
+
+    fn brute_force(points: &[Point2D]) -> f64 {
+        let mut best = f64::INFINITY;
+        for (i, p) in points.iter().enumerate() {
+            for q in &points[i + 1..] {
+                best = best.min(p.distance_to(q));
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn test_closest_pair() {
+        let points: Vec<Point2D> = [(0.0, 0.0), (5.0, 5.0), (1.0, 9.0), (5.5, 4.0), (9.0, 0.0)]
+            .iter()
+            .map(|&(x, y)| Point2D::new(x, y))
+            .collect();
+        let pair = closest_pair(&points).unwrap();
+        assert_eq!(pair.distance, 1.25f64.sqrt());
+        let mut ends = [pair.p, pair.q];
+        ends.sort();
+        assert_eq!(ends, [Point2D::new(5.5, 4.0), Point2D::new(5.0, 5.0)]);
+
+        assert_eq!(closest_pair(&points[..1]), None);
+        let twice = [points[1], points[0], points[1]];
+        assert_eq!(closest_pair(&twice).unwrap().distance, 0.0);
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..200 {
+            let n = rng.gen_range(2..100);
+            let points: Vec<Point2D> = (0..n)
+                .map(|_| Point2D::new(rng.gen_range(-100.0..100.0), rng.gen_range(-100.0..100.0)))
+                .collect();
+            let pair = closest_pair(&points).unwrap();
+            assert_eq!(pair.distance, brute_force(&points));
+            assert_eq!(pair.p.distance_to(&pair.q), pair.distance);
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Convex Hull
+//!
+//! The convex hull of a set of points is the smallest convex polygon that contains them. Both
+//! algorithms sort the points and then scan them once, keeping the hull of the points seen so
+//! far on a stack and popping the points that would make a clockwise turn, so that they take
+//! time proportional to N log N.
+//!
+//! The hulls are returned in counterclockwise order, starting with the lowest point (the
+//! leftmost one if there are several). Points on the edges of the hull are not part of it.
+use super::point::{Orientation, Point2D};
+
+/// Graham scan: sort the points by polar angle with the lowest point, and add them to the hull
+/// in that order
+pub fn graham_scan(points: &[Point2D]) -> Vec<Point2D> {
+    let mut a = points.to_vec();
+    if a.is_empty() {
+        return a;
+    }
+    // The sorts are stable, so points at the same angle stay in order of distance
+    a.sort();
+    let p0 = a[0];
+    a[1..].sort_by(p0.polar_order());
+
+    let mut hull = vec![p0];
+    // Skip the copies of p0, and the points on the first ray from it but the farthest one
+    let Some(k1) = (1..a.len()).find(|&k| a[k] != p0) else {
+        return hull;
+    };
+    let k2 = (k1 + 1..a.len())
+        .find(|&k| Point2D::ccw(&p0, &a[k1], &a[k]) != Orientation::Collinear)
+        .unwrap_or(a.len());
+    hull.push(a[k2 - 1]);
+
+    for &p in &a[k2..] {
+        let mut top = hull.pop().unwrap();
+        while Point2D::ccw(hull.last().unwrap(), &top, &p) != Orientation::Counterclockwise {
+            top = hull.pop().unwrap();
+        }
+        hull.push(top);
+        hull.push(p);
+    }
+    hull
+}
+
+/// Andrew's monotone chain: sort the points by x-coordinate, and build the lower hull from left
+/// to right and the upper hull from right to left
+pub fn monotone_chain(points: &[Point2D]) -> Vec<Point2D> {
+    let mut a = points.to_vec();
+    a.sort_by(|p, q| Point2D::x_order(p, q).then(Point2D::y_order(p, q)));
+    a.dedup();
+    if a.len() <= 2 {
+        a.sort();
+        return a;
+    }
+
+    let mut hull: Vec<Point2D> = vec![];
+    for &p in &a {
+        while hull.len() >= 2
+            && Point2D::ccw(&hull[hull.len() - 2], &hull[hull.len() - 1], &p)
+                != Orientation::Counterclockwise
+        {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+    // The upper hull never pops the points of the lower hull, and ends with the first point
+    // again
+    let lower = hull.len() + 1;
+    for &p in a.iter().rev().skip(1) {
+        while hull.len() >= lower
+            && Point2D::ccw(&hull[hull.len() - 2], &hull[hull.len() - 1], &p)
+                != Orientation::Counterclockwise
+        {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+    hull.pop();
+
+    // Start with the lowest point, as the Graham scan does
+    let start = (0..hull.len()).min_by_key(|&i| hull[i]).unwrap();
+    hull.rotate_left(start);
+    hull
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::{graham_scan, monotone_chain};
+    use crate::geometry::point::{Orientation, Point2D};
+
+    fn points(coordinates: &[(f64, f64)]) -> Vec<Point2D> {
+        coordinates
+            .iter()
+            .map(|&(x, y)| Point2D::new(x, y))
+            .collect()
+    }
+
+    /// Is the hull a convex polygon, with every point inside it?
+    fn check(hull: &[Point2D], points: &[Point2D]) {
+        let n = hull.len();
+        if n < 3 {
+            return;
+        }
+        for i in 0..n {
+            let (a, b) = (&hull[i], &hull[(i + 1) % n]);
+            assert_eq!(
+                Point2D::ccw(a, b, &hull[(i + 2) % n]),
+                Orientation::Counterclockwise
+            );
+            for p in points {
+                assert_ne!(Point2D::ccw(a, b, p), Orientation::Clockwise);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hull() {
+        // A square with points inside it and on its edges
+        let a = points(&[
+            (0.0, 0.0),
+            (2.0, 2.0),
+            (1.0, 1.0),
+            (2.0, 0.0),
+            (1.0, 0.0),
+            (0.0, 2.0),
+            (0.0, 1.0),
+            (2.0, 2.0),
+        ]);
+        let expected = points(&[(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0)]);
+        assert_eq!(graham_scan(&a), expected);
+        assert_eq!(monotone_chain(&a), expected);
+
+        for a in [
+            points(&[]),
+            points(&[(1.0, 1.0)]),
+            points(&[(1.0, 1.0), (1.0, 1.0)]),
+        ] {
+            assert_eq!(graham_scan(&a), monotone_chain(&a));
+            assert_eq!(graham_scan(&a).len(), a.len().min(1));
+        }
+        // Collinear points: the two ends
+        let a = points(&[(2.0, 2.0), (0.0, 0.0), (1.0, 1.0), (3.0, 3.0)]);
+        assert_eq!(graham_scan(&a), points(&[(0.0, 0.0), (3.0, 3.0)]));
+        assert_eq!(monotone_chain(&a), points(&[(0.0, 0.0), (3.0, 3.0)]));
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..200 {
+            // Small coordinates, to get collinear and coincident points
+            let a: Vec<Point2D> = (0..rng.gen_range(0..40))
+                .map(|_| Point2D::new(rng.gen_range(0..10) as f64, rng.gen_range(0..10) as f64))
+                .collect();
+            let hull = graham_scan(&a);
+            check(&hull, &a);
+            assert_eq!(monotone_chain(&a), hull);
+        }
+    }
+}
@@ -0,0 +1,253 @@
+//! Points in the Plane
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+};
+
+/// Orientation of a turn a->b->c
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    Clockwise,
+    Collinear,
+    Counterclockwise,
+}
+
+/// Point with finite coordinates in the plane.
+///
+/// Points are ordered by y-coordinate, and then by x-coordinate. The other orders used by the
+/// geometric algorithms are given as comparators, to be passed to `sort_by`.
+#[derive(Clone, Copy, Debug)]
+pub struct Point2D {
+    x: f64,
+    y: f64,
+}
+
+impl Point2D {
+    /// Create a point. The coordinate -0.0 is taken as 0.0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coordinate is infinite or not a number.
+    pub fn new(x: f64, y: f64) -> Self {
+        assert!(
+            x.is_finite() && y.is_finite(),
+            "coordinates ({}, {}) are not finite",
+            x,
+            y
+        );
+        Self {
+            x: x + 0.0,
+            y: y + 0.0,
+        }
+    }
+
+    pub fn x(&self) -> f64 {
+        self.x
+    }
+
+    pub fn y(&self) -> f64 {
+        self.y
+    }
+
+    /// Polar radius
+    pub fn r(&self) -> f64 {
+        self.x.hypot(self.y)
+    }
+
+    /// Polar angle, between -pi and pi
+    pub fn theta(&self) -> f64 {
+        self.y.atan2(self.x)
+    }
+
+    pub fn distance_to(&self, that: &Point2D) -> f64 {
+        self.distance_squared_to(that).sqrt()
+    }
+
+    pub fn distance_squared_to(&self, that: &Point2D) -> f64 {
+        let dx = self.x - that.x;
+        let dy = self.y - that.y;
+        dx * dx + dy * dy
+    }
+
+    /// Twice the signed area of the triangle a-b-c, which is positive if a->b->c turns
+    /// counterclockwise
+    pub fn area2(a: &Point2D, b: &Point2D, c: &Point2D) -> f64 {
+        (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+    }
+
+    /// Is a->b->c a counterclockwise turn, a clockwise turn, or are the points collinear?
+    pub fn ccw(a: &Point2D, b: &Point2D, c: &Point2D) -> Orientation {
+        let area2 = Point2D::area2(a, b, c);
+        if area2 > 0.0 {
+            Orientation::Counterclockwise
+        } else if area2 < 0.0 {
+            Orientation::Clockwise
+        } else {
+            Orientation::Collinear
+        }
+    }
+
+    /// Compare points by x-coordinate
+    pub fn x_order(p: &Point2D, q: &Point2D) -> Ordering {
+        p.x.total_cmp(&q.x)
+    }
+
+    /// Compare points by y-coordinate
+    pub fn y_order(p: &Point2D, q: &Point2D) -> Ordering {
+        p.y.total_cmp(&q.y)
+    }
+
+    /// Compare points by polar radius
+    pub fn r_order(p: &Point2D, q: &Point2D) -> Ordering {
+        let (r1, r2) = (p.x * p.x + p.y * p.y, q.x * q.x + q.y * q.y);
+        r1.total_cmp(&r2)
+    }
+
+    /// Compare points by the polar angle they make with this point, between 0 and 2 pi. The
+    /// angles are compared with `ccw`, without computing them.
+    pub fn polar_order(&self) -> impl Fn(&Point2D, &Point2D) -> Ordering {
+        let p = *self;
+        move |q1, q2| {
+            let (dy1, dy2) = (q1.y - p.y, q2.y - p.y);
+            let (dx1, dx2) = (q1.x - p.x, q2.x - p.x);
+            if dy1 >= 0.0 && dy2 < 0.0 {
+                // q1 above or level, q2 below
+                Ordering::Less
+            } else if dy2 >= 0.0 && dy1 < 0.0 {
+                Ordering::Greater
+            } else if dy1 == 0.0 && dy2 == 0.0 {
+                // Both level: to the right comes before to the left
+                match (dx1 >= 0.0, dx2 >= 0.0) {
+                    (true, false) => Ordering::Less,
+                    (false, true) => Ordering::Greater,
+                    _ => Ordering::Equal,
+                }
+            } else {
+                match Point2D::ccw(&p, q1, q2) {
+                    Orientation::Counterclockwise => Ordering::Less,
+                    Orientation::Clockwise => Ordering::Greater,
+                    Orientation::Collinear => Ordering::Equal,
+                }
+            }
+        }
+    }
+
+    /// Compare points by the polar angle they make with this point, computed with `atan2`
+    pub fn atan2_order(&self) -> impl Fn(&Point2D, &Point2D) -> Ordering {
+        let p = *self;
+        move |q1, q2| {
+            let angle1 = (q1.y - p.y).atan2(q1.x - p.x);
+            let angle2 = (q2.y - p.y).atan2(q2.x - p.x);
+            angle1.total_cmp(&angle2)
+        }
+    }
+
+    /// Compare points by their distance to this point
+    pub fn distance_to_order(&self) -> impl Fn(&Point2D, &Point2D) -> Ordering {
+        let p = *self;
+        move |q1, q2| {
+            p.distance_squared_to(q1)
+                .total_cmp(&p.distance_squared_to(q2))
+        }
+    }
+}
+
+impl PartialEq for Point2D {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Point2D {}
+
+impl PartialOrd for Point2D {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Point2D {
+    fn cmp(&self, other: &Self) -> Ordering {
+        Point2D::y_order(self, other).then(Point2D::x_order(self, other))
+    }
+}
+
+impl Display for Point2D {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::{Orientation, Point2D};
+
+    fn points(coordinates: &[(f64, f64)]) -> Vec<Point2D> {
+        coordinates
+            .iter()
+            .map(|&(x, y)| Point2D::new(x, y))
+            .collect()
+    }
+
+    #[test]
+    fn test_point() {
+        let p = Point2D::new(3.0, 4.0);
+        assert_eq!(p.r(), 5.0);
+        assert_eq!(p.distance_to(&Point2D::new(0.0, 0.0)), 5.0);
+        assert_eq!(p.to_string(), "(3, 4)");
+        assert_eq!(Point2D::new(-0.0, 1.0), Point2D::new(0.0, 1.0));
+        assert!(Point2D::new(5.0, 1.0) < Point2D::new(0.0, 2.0));
+
+        let (a, b) = (Point2D::new(0.0, 0.0), Point2D::new(1.0, 0.0));
+        assert_eq!(
+            Point2D::ccw(&a, &b, &Point2D::new(1.0, 1.0)),
+            Orientation::Counterclockwise
+        );
+        assert_eq!(
+            Point2D::ccw(&a, &b, &Point2D::new(1.0, -1.0)),
+            Orientation::Clockwise
+        );
+        assert_eq!(
+            Point2D::ccw(&a, &b, &Point2D::new(2.0, 0.0)),
+            Orientation::Collinear
+        );
+    }
+
+    #[test]
+    fn test_orders() {
+        let origin = Point2D::new(0.0, 0.0);
+        // In counterclockwise order from the positive x-axis
+        let expected = points(&[(1.0, 0.0), (1.0, 1.0), (0.0, 2.0), (-1.0, 0.0), (0.0, -1.0)]);
+        let mut shuffled = vec![
+            expected[3],
+            expected[0],
+            expected[4],
+            expected[2],
+            expected[1],
+        ];
+        shuffled.sort_by(origin.polar_order());
+        assert_eq!(shuffled, expected);
+
+        // atan2 measures the angles from -pi
+        let mut by_atan2 = expected.clone();
+        by_atan2.sort_by(origin.atan2_order());
+        assert_eq!(
+            by_atan2,
+            points(&[(0.0, -1.0), (1.0, 0.0), (1.0, 1.0), (0.0, 2.0), (-1.0, 0.0)])
+        );
+
+        let mut by_distance = expected.clone();
+        by_distance.sort_by(Point2D::new(1.0, 0.0).distance_to_order());
+        assert_eq!(by_distance[0], expected[0]);
+        assert_eq!(by_distance[1], expected[1]);
+
+        let mut by_x = expected.clone();
+        by_x.sort_by(Point2D::x_order);
+        assert_eq!(by_x[0].x(), -1.0);
+        let mut by_r = expected;
+        by_r.sort_by(Point2D::r_order);
+        assert_eq!(by_r[4], Point2D::new(0.0, 2.0));
+    }
+}
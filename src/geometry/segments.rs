@@ -0,0 +1,277 @@
+//! Orthogonal Segment Intersection
+//!
+//! A sweep line moves from left to right over horizontal and vertical segments. The
+//! y-coordinates of the horizontal segments that cross the sweep line are kept in a red-black
+//! BST: a horizontal segment is inserted at its left end and deleted at its right end, and a
+//! vertical segment intersects the horizontal segments whose y-coordinates are in its range. The
+//! height of the tree stays logarithmic even when the segments come in order of y, so with R
+//! intersections this takes time proportional to N log N + R.
+//!
+//! Segments include their ends, so segments that touch intersect.
+use std::cmp::Ordering;
+
+use super::point::Point2D;
+use crate::symboltables::balancedtree::BalancedTree;
+
+/// Horizontal or vertical segment
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Segment {
+    start: Point2D,
+    end: Point2D,
+}
+
+impl Segment {
+    /// Horizontal segment from (x0, y) to (x1, y), in either order
+    pub fn horizontal(y: f64, x0: f64, x1: f64) -> Self {
+        let (x0, x1) = if x0 <= x1 { (x0, x1) } else { (x1, x0) };
+        Self {
+            start: Point2D::new(x0, y),
+            end: Point2D::new(x1, y),
+        }
+    }
+
+    /// Vertical segment from (x, y0) to (x, y1), in either order
+    pub fn vertical(x: f64, y0: f64, y1: f64) -> Self {
+        let (y0, y1) = if y0 <= y1 { (y0, y1) } else { (y1, y0) };
+        Self {
+            start: Point2D::new(x, y0),
+            end: Point2D::new(x, y1),
+        }
+    }
+
+    /// The left or lower end
+    pub fn start(&self) -> Point2D {
+        self.start
+    }
+
+    /// The right or upper end
+    pub fn end(&self) -> Point2D {
+        self.end
+    }
+
+    /// Is the segment horizontal? A segment reduced to a point is taken as vertical.
+    pub fn is_horizontal(&self) -> bool {
+        self.start.x() != self.end.x()
+    }
+}
+
+/// Intersection of a horizontal and a vertical segment, given by their indices
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Intersection {
+    pub horizontal: usize,
+    pub vertical: usize,
+    pub point: Point2D,
+}
+
+/// Key of a horizontal segment on the sweep line: its y-coordinate, and then its index to tell
+/// apart segments at the same height
+#[derive(Clone, Copy, Debug)]
+struct SweepKey {
+    y: f64,
+    segment: usize,
+}
+
+impl SweepKey {
+    fn new(y: f64, segment: usize) -> Self {
+        Self { y, segment }
+    }
+}
+
+impl PartialEq for SweepKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for SweepKey {}
+
+impl PartialOrd for SweepKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SweepKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.y
+            .total_cmp(&other.y)
+            .then(self.segment.cmp(&other.segment))
+    }
+}
+
+/// Events of the sweep, in the order in which they are handled at the same x-coordinate
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Event {
+    Insert,
+    Query,
+    Delete,
+}
+
+/// Find the intersections of the horizontal segments with the vertical segments, in order of
+/// x-coordinate and then of y-coordinate
+pub fn orthogonal_intersections(segments: &[Segment]) -> Vec<Intersection> {
+    let mut events = vec![];
+    for (i, segment) in segments.iter().enumerate() {
+        if segment.is_horizontal() {
+            events.push((segment.start.x(), Event::Insert, i));
+            events.push((segment.end.x(), Event::Delete, i));
+        } else {
+            events.push((segment.start.x(), Event::Query, i));
+        }
+    }
+    events.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2)));
+
+    let mut sweep: BalancedTree<SweepKey, ()> = BalancedTree::new();
+    let mut intersections = vec![];
+    for (x, event, i) in events {
+        let segment = &segments[i];
+        match event {
+            Event::Insert => sweep.put(SweepKey::new(segment.start.y(), i), ()),
+            Event::Delete => sweep.delete(SweepKey::new(segment.start.y(), i)),
+            Event::Query => {
+                let lo = SweepKey::new(segment.start.y(), 0);
+                let hi = SweepKey::new(segment.end.y(), usize::MAX);
+                // The rank of the ends of the range gives the number of intersections in
+                // logarithmic time
+                intersections.reserve(sweep.size_in_range(lo, hi));
+                for key in sweep.keys_in_range(lo, hi) {
+                    intersections.push(Intersection {
+                        horizontal: key.segment,
+                        vertical: i,
+                        point: Point2D::new(x, key.y),
+                    });
+                }
+            }
+        }
+    }
+    intersections
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::{orthogonal_intersections, Intersection, Segment};
+    use crate::geometry::point::Point2D;
+
+    fn brute_force(segments: &[Segment]) -> Vec<(usize, usize)> {
+        let mut result = vec![];
+        for (v, vertical) in segments.iter().enumerate() {
+            if vertical.is_horizontal() {
+                continue;
+            }
+            for (h, horizontal) in segments.iter().enumerate() {
+                if horizontal.is_horizontal()
+                    && horizontal.start().x() <= vertical.start().x()
+                    && vertical.start().x() <= horizontal.end().x()
+                    && vertical.start().y() <= horizontal.start().y()
+                    && horizontal.start().y() <= vertical.end().y()
+                {
+                    result.push((h, v));
+                }
+            }
+        }
+        result.sort();
+        result
+    }
+
+    #[test]
+    fn test_intersections() {
+        let segments = [
+            Segment::horizontal(1.0, 0.0, 4.0),
+            Segment::vertical(2.0, 0.0, 3.0),
+            Segment::horizontal(2.0, 5.0, 1.0),
+            Segment::vertical(4.0, 1.0, 5.0),
+            Segment::vertical(6.0, 0.0, 9.0),
+            Segment::horizontal(8.0, 0.0, 3.0),
+        ];
+        assert!(segments[2].is_horizontal());
+        assert_eq!(segments[2].start(), Point2D::new(1.0, 2.0));
+        assert!(!segments[3].is_horizontal());
+
+        let intersections = orthogonal_intersections(&segments);
+        assert_eq!(
+            intersections,
+            vec![
+                Intersection {
+                    horizontal: 0,
+                    vertical: 1,
+                    point: Point2D::new(2.0, 1.0)
+                },
+                Intersection {
+                    horizontal: 2,
+                    vertical: 1,
+                    point: Point2D::new(2.0, 2.0)
+                },
+                // Touching at the end of the horizontal segment
+                Intersection {
+                    horizontal: 0,
+                    vertical: 3,
+                    point: Point2D::new(4.0, 1.0)
+                },
+                Intersection {
+                    horizontal: 2,
+                    vertical: 3,
+                    point: Point2D::new(4.0, 2.0)
+                },
+            ]
+        );
+        assert!(orthogonal_intersections(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_sorted() {
+        // a staircase of horizontal segments inserted in order of y, which would build a linked
+        // list in a BST that is not balanced
+        let n = 50_000;
+        let mut segments: Vec<Segment> = (0..n)
+            .map(|i| Segment::horizontal(i as f64, i as f64, (2 * n) as f64))
+            .collect();
+        segments.push(Segment::vertical((2 * n) as f64, 0.5, 10.5));
+        let intersections = orthogonal_intersections(&segments);
+        assert_eq!(
+            intersections
+                .iter()
+                .map(|intersection| (intersection.horizontal, intersection.vertical))
+                .collect::<Vec<_>>(),
+            (1..=10).map(|h| (h, n)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..100 {
+            // Small coordinates, to get segments that touch and overlap
+            let segments: Vec<Segment> = (0..rng.gen_range(0..40))
+                .map(|_| {
+                    let (a, b, c) = (
+                        rng.gen_range(0..10) as f64,
+                        rng.gen_range(0..10) as f64,
+                        rng.gen_range(0..10) as f64,
+                    );
+                    if rng.gen() {
+                        Segment::horizontal(a, b, c)
+                    } else {
+                        Segment::vertical(a, b, c)
+                    }
+                })
+                .collect();
+            let intersections = orthogonal_intersections(&segments);
+            for intersection in &intersections {
+                let vertical = &segments[intersection.vertical];
+                let horizontal = &segments[intersection.horizontal];
+                assert_eq!(intersection.point.x(), vertical.start().x());
+                assert_eq!(intersection.point.y(), horizontal.start().y());
+            }
+            let mut pairs: Vec<(usize, usize)> = intersections
+                .iter()
+                .map(|intersection| (intersection.horizontal, intersection.vertical))
+                .collect();
+            pairs.sort();
+            assert_eq!(pairs, brute_force(&segments));
+        }
+    }
+}
@@ -0,0 +1,83 @@
+//! Graphs
+//!
+//! Graphs and graph processing algorithms. Vertices are numbered from 0 to V - 1.
+//!
+//! Graphs can be read from the text format used by the book: the number of vertices, the number
+//! of edges and then the edges as pairs of vertices, all separated by whitespace.
+use std::{
+    io::{self, BufRead},
+    str::FromStr,
+};
+
+pub mod arbitrage;
+pub mod digraph;
+pub mod digraphsearch;
+pub mod edgeweighteddigraph;
+pub mod edgeweightedgraph;
+pub mod flownetwork;
+pub mod graph;
+pub mod maxflow;
+pub mod mst;
+pub mod paths;
+pub mod search;
+pub mod shortestpaths;
+pub mod strongcomponents;
+pub mod symbolgraph;
+
+/// Graphs that can be built one edge at a time, such as `Graph` and `Digraph`
+pub trait AddEdge {
+    /// Create a graph with `v` vertices and no edges
+    fn with_vertices(v: usize) -> Self;
+
+    /// Add the edge v-w, or v->w for a directed graph
+    fn add_edge(&mut self, v: usize, w: usize);
+}
+
+// Read a graph in the text format of the book: V, E and then E pairs of vertices
+pub(crate) fn read_graph<G: AddEdge, R: BufRead>(reader: R) -> io::Result<G> {
+    let mut input = Input::new(reader)?;
+    let v = input.next("number of vertices")?;
+    let e: usize = input.next("number of edges")?;
+    let mut graph = G::with_vertices(v);
+    for _ in 0..e {
+        let p = input.next("vertex")?;
+        let q = input.next("vertex")?;
+        if p >= v || q >= v {
+            return Err(invalid_data(format!("edge {}-{} out of range", p, q)));
+        }
+        graph.add_edge(p, q);
+    }
+    Ok(graph)
+}
+
+/// Reads the whitespace separated values of a graph file
+pub(crate) struct Input {
+    tokens: std::vec::IntoIter<String>,
+}
+
+impl Input {
+    pub fn new<R: BufRead>(reader: R) -> io::Result<Self> {
+        let mut tokens = vec![];
+        for line in reader.lines() {
+            tokens.extend(line?.split_ascii_whitespace().map(String::from));
+        }
+        Ok(Self {
+            tokens: tokens.into_iter(),
+        })
+    }
+
+    /// Parse the next value, `what` describes the value in the error if it is missing or invalid
+    pub fn next<T: FromStr>(&mut self, what: &str) -> io::Result<T> {
+        let token = self
+            .tokens
+            .next()
+            .ok_or_else(|| invalid_data(format!("missing {}", what)))?;
+        token
+            .parse()
+            .map_err(|_| invalid_data(format!("invalid {} `{}`", what, token)))
+    }
+}
+
+pub(crate) fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,149 @@
+//! Arbitrage
+//!
+//! An arbitrage opportunity is a cycle of currency exchanges that ends with more money than it
+//! started with: a cycle whose product of exchange rates is greater than 1. Weighting the
+//! exchange from v to w with -ln(rate) turns products into sums, and such a cycle into a negative
+//! cycle, which Bellman-Ford finds.
+//!
+//! Rounding errors make the weights of a cycle of reciprocal rates, such as 0.8 and 1.25, add up
+//! to a tiny negative number instead of 0, so a cycle only counts as an arbitrage opportunity if
+//! its weight is below `-TOLERANCE`.
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+use super::{
+    edgeweighteddigraph::{DirectedEdge, EdgeWeightedDigraph},
+    invalid_data,
+    shortestpaths::bellman_ford,
+    Input,
+};
+
+/// Largest total weight of a cycle that is taken as a rounding error, and not as a profit
+pub const TOLERANCE: f64 = 1e-9;
+
+/// Table of exchange rates between currencies
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExchangeRates {
+    currencies: Vec<String>,
+    rates: Vec<Vec<f64>>, // rates[v][w] is the amount of w given for one v
+}
+
+/// Exchange of an amount of currency for another one
+#[derive(Clone, Debug, PartialEq)]
+pub struct Exchange<'a> {
+    pub from: &'a str,
+    pub to: &'a str,
+    pub rate: f64,
+}
+
+impl ExchangeRates {
+    /// Read the table: the number of currencies V, and then V rows of a currency name followed
+    /// by its V exchange rates
+    pub fn from_reader<R: BufRead>(reader: R) -> io::Result<Self> {
+        let mut input = Input::new(reader)?;
+        let n = input.next("number of currencies")?;
+        let mut currencies = Vec::with_capacity(n);
+        let mut rates = Vec::with_capacity(n);
+        for _ in 0..n {
+            currencies.push(input.next("currency")?);
+            let row = (0..n)
+                .map(|_| input.next("exchange rate"))
+                .collect::<io::Result<Vec<f64>>>()?;
+            if let Some(rate) = row.iter().find(|&&rate| rate <= 0.0) {
+                return Err(invalid_data(format!("invalid exchange rate {}", rate)));
+            }
+            rates.push(row);
+        }
+        Ok(Self { currencies, rates })
+    }
+
+    /// Read the table from a file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    pub fn currencies(&self) -> &[String] {
+        &self.currencies
+    }
+
+    /// Amount of currency `w` given for one unit of currency `v`
+    pub fn rate(&self, v: usize, w: usize) -> f64 {
+        self.rates[v][w]
+    }
+
+    /// Complete digraph of the exchanges, weighted with the negated logarithm of the rates
+    pub fn digraph(&self) -> EdgeWeightedDigraph {
+        let n = self.currencies.len();
+        let mut g = EdgeWeightedDigraph::new(n);
+        for v in 0..n {
+            for w in 0..n {
+                g.add_edge(DirectedEdge::new(v, w, -self.rates[v][w].ln()));
+            }
+        }
+        g
+    }
+
+    /// Find a cycle of exchanges that makes a profit, if there is one
+    pub fn arbitrage(&self) -> Option<Vec<Exchange<'_>>> {
+        if self.currencies.is_empty() {
+            return None;
+        }
+        let cycle = bellman_ford(&self.digraph(), 0).err()?;
+        let weight: f64 = cycle.iter().map(|e| e.weight()).sum();
+        if weight > -TOLERANCE {
+            return None;
+        }
+        let exchanges = cycle
+            .iter()
+            .map(|e| Exchange {
+                from: &self.currencies[e.from()],
+                to: &self.currencies[e.to()],
+                rate: self.rates[e.from()][e.to()],
+            })
+            .collect();
+        Some(exchanges)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use super::ExchangeRates;
+
+    #[test]
+    fn test_arbitrage() {
+        let rates = ExchangeRates::from_file("resources/rates.txt").unwrap();
+        assert_eq!(rates.currencies(), &["USD", "EUR", "GBP", "CHF", "CAD"]);
+        assert_eq!(rates.rate(0, 1), 0.741);
+
+        let exchanges = rates.arbitrage().unwrap();
+        for pair in exchanges.windows(2) {
+            assert_eq!(pair[0].to, pair[1].from);
+        }
+        assert_eq!(exchanges[0].from, exchanges[exchanges.len() - 1].to);
+        let profit: f64 = exchanges.iter().map(|exchange| exchange.rate).product();
+        assert!(profit > 1.0);
+    }
+
+    #[test]
+    fn test_no_arbitrage() {
+        let table = "2\nUSD 1 0.5\nEUR 1.9 1\n";
+        let rates = ExchangeRates::from_reader(Cursor::new(table)).unwrap();
+        assert_eq!(rates.arbitrage(), None);
+
+        // reciprocal rates, whose logarithms do not add up to exactly 0
+        for table in ["2\nUSD 1 0.8\nEUR 1.25 1\n", "2\nUSD 1 0.1\nEUR 10 1\n"] {
+            let rates = ExchangeRates::from_reader(Cursor::new(table)).unwrap();
+            assert_eq!(rates.arbitrage(), None);
+        }
+
+        assert!(ExchangeRates::from_reader(Cursor::new("2\nUSD 1 0.5\nEUR 2")).is_err());
+        assert!(ExchangeRates::from_reader(Cursor::new("1\nUSD 0")).is_err());
+    }
+}
@@ -0,0 +1,161 @@
+//! Directed Graph
+use std::{
+    fmt::{self, Display},
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+use super::{read_graph, AddEdge};
+
+/// Directed graph, represented by the list of vertices each vertex points to.
+///
+/// An edge v->w only appears in the list of `v`. Parallel edges and self-loops are allowed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Digraph {
+    v: usize,
+    e: usize,
+    adj: Vec<Vec<usize>>,
+    indegree: Vec<usize>,
+}
+
+impl Digraph {
+    /// Create a digraph with `v` vertices and no edges
+    pub fn new(v: usize) -> Self {
+        Self {
+            v,
+            e: 0,
+            adj: vec![vec![]; v],
+            indegree: vec![0; v],
+        }
+    }
+
+    /// Read a digraph in the text format of the book: V, E and then E pairs of vertices
+    pub fn from_reader<R: BufRead>(reader: R) -> io::Result<Self> {
+        read_graph(reader)
+    }
+
+    /// Read a digraph from a file in the text format of the book
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Number of vertices
+    pub fn v(&self) -> usize {
+        self.v
+    }
+
+    /// Number of edges
+    pub fn e(&self) -> usize {
+        self.e
+    }
+
+    /// Add the edge v->w
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v` or `w` is not a vertex of the digraph.
+    pub fn add_edge(&mut self, v: usize, w: usize) {
+        self.validate(v);
+        self.validate(w);
+        self.adj[v].push(w);
+        self.indegree[w] += 1;
+        self.e += 1;
+    }
+
+    /// Vertices pointed to by `v`
+    pub fn adj(&self, v: usize) -> &[usize] {
+        &self.adj[v]
+    }
+
+    /// Number of edges leaving `v`
+    pub fn outdegree(&self, v: usize) -> usize {
+        self.adj[v].len()
+    }
+
+    /// Number of edges pointing to `v`
+    pub fn indegree(&self, v: usize) -> usize {
+        self.indegree[v]
+    }
+
+    /// The digraph with all its edges reversed
+    pub fn reverse(&self) -> Self {
+        let mut reverse = Digraph::new(self.v);
+        for v in 0..self.v {
+            for &w in &self.adj[v] {
+                reverse.add_edge(w, v);
+            }
+        }
+        reverse
+    }
+
+    fn validate(&self, v: usize) {
+        assert!(v < self.v, "vertex {} is not between 0 and {}", v, self.v);
+    }
+}
+
+impl AddEdge for Digraph {
+    fn with_vertices(v: usize) -> Self {
+        Self::new(v)
+    }
+
+    fn add_edge(&mut self, v: usize, w: usize) {
+        Digraph::add_edge(self, v, w);
+    }
+}
+
+impl Display for Digraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} vertices, {} edges", self.v, self.e)?;
+        for v in 0..self.v {
+            write!(f, "{}:", v)?;
+            for w in &self.adj[v] {
+                write!(f, " {}", w)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::Digraph;
+
+    #[test]
+    fn test_from_file() {
+        let g = Digraph::from_file("resources/tinyDG.txt").unwrap();
+        assert_eq!(g.v(), 13);
+        assert_eq!(g.e(), 22);
+        assert_eq!(g.adj(6), &[0, 8, 4, 9]);
+        assert_eq!(g.outdegree(6), 4);
+        assert_eq!(g.indegree(6), 2);
+        assert!(g.to_string().starts_with("13 vertices, 22 edges\n0: 1 5\n"));
+    }
+
+    #[test]
+    fn test_reverse() {
+        let g = Digraph::from_file("resources/tinyDG.txt").unwrap();
+        let r = g.reverse();
+        assert_eq!(r.e(), g.e());
+        for v in 0..g.v() {
+            assert_eq!(r.outdegree(v), g.indegree(v));
+            assert_eq!(r.indegree(v), g.outdegree(v));
+            for &w in g.adj(v) {
+                assert!(r.adj(w).contains(&v));
+            }
+        }
+
+        // reversing twice gives the same edges, but not necessarily in the same order
+        let rr = r.reverse();
+        for v in 0..g.v() {
+            let mut adj = rr.adj(v).to_vec();
+            adj.sort_unstable();
+            let mut expected = g.adj(v).to_vec();
+            expected.sort_unstable();
+            assert_eq!(adj, expected);
+        }
+    }
+}
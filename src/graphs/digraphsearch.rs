@@ -0,0 +1,319 @@
+//! Digraph Search Clients
+//!
+//! Reachability, directed cycles, depth-first orders and topological sort. The searches use an
+//! explicit stack, so that large digraphs don't overflow the call stack.
+use super::digraph::Digraph;
+
+/// Vertices reachable from one or more sources, found by depth-first search
+pub struct DirectedDfs {
+    marked: Vec<bool>,
+    count: usize,
+}
+
+impl DirectedDfs {
+    pub fn new(g: &Digraph, s: usize) -> Self {
+        Self::from_sources(g, &[s])
+    }
+
+    /// Vertices reachable from any of the given sources
+    pub fn from_sources(g: &Digraph, sources: &[usize]) -> Self {
+        let mut marked = vec![false; g.v()];
+        let mut count = 0;
+        let mut stack = vec![];
+        for &s in sources {
+            if !marked[s] {
+                marked[s] = true;
+                count += 1;
+                stack.push(s);
+            }
+        }
+        while let Some(v) = stack.pop() {
+            for &w in g.adj(v) {
+                if !marked[w] {
+                    marked[w] = true;
+                    count += 1;
+                    stack.push(w);
+                }
+            }
+        }
+        Self { marked, count }
+    }
+
+    /// Is `v` reachable from a source?
+    pub fn marked(&self, v: usize) -> bool {
+        self.marked[v]
+    }
+
+    /// Number of vertices reachable from the sources, including the sources
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Finds a directed cycle in a digraph, if it has one.
+///
+/// Depth-first search keeps track of the vertices on the current path: an edge to one of them
+/// closes a cycle.
+pub struct DirectedCycle {
+    cycle: Option<Vec<usize>>,
+}
+
+impl DirectedCycle {
+    pub fn new(g: &Digraph) -> Self {
+        let mut marked = vec![false; g.v()];
+        let mut on_stack = vec![false; g.v()];
+        let mut edge_to = vec![0; g.v()];
+        for s in 0..g.v() {
+            if marked[s] {
+                continue;
+            }
+            // each entry is a vertex and the index of the next adjacent vertex to look at
+            let mut stack = vec![(s, 0)];
+            marked[s] = true;
+            on_stack[s] = true;
+            while let Some((v, i)) = stack.pop() {
+                let Some(&w) = g.adj(v).get(i) else {
+                    on_stack[v] = false;
+                    continue;
+                };
+                stack.push((v, i + 1));
+                if !marked[w] {
+                    marked[w] = true;
+                    on_stack[w] = true;
+                    edge_to[w] = v;
+                    stack.push((w, 0));
+                } else if on_stack[w] {
+                    let mut cycle = vec![v];
+                    let mut x = v;
+                    while x != w {
+                        x = edge_to[x];
+                        cycle.push(x);
+                    }
+                    cycle.reverse();
+                    cycle.push(w);
+                    return Self { cycle: Some(cycle) };
+                }
+            }
+        }
+        Self { cycle: None }
+    }
+
+    pub fn has_cycle(&self) -> bool {
+        self.cycle.is_some()
+    }
+
+    /// The vertices of a directed cycle, starting and ending with the same vertex
+    pub fn cycle(&self) -> Option<&[usize]> {
+        self.cycle.as_deref()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Orders of the vertices in a depth-first search of the whole digraph: preorder is the order
+/// of the calls, postorder the order in which they return.
+pub struct DepthFirstOrder {
+    pre: Vec<usize>,
+    post: Vec<usize>,
+    pre_number: Vec<usize>,
+    post_number: Vec<usize>,
+}
+
+impl DepthFirstOrder {
+    pub fn new(g: &Digraph) -> Self {
+        let mut order = Self {
+            pre: Vec::with_capacity(g.v()),
+            post: Vec::with_capacity(g.v()),
+            pre_number: vec![0; g.v()],
+            post_number: vec![0; g.v()],
+        };
+        let mut marked = vec![false; g.v()];
+        for s in 0..g.v() {
+            if marked[s] {
+                continue;
+            }
+            // each entry is a vertex and the index of the next adjacent vertex to look at
+            let mut stack = vec![(s, 0)];
+            marked[s] = true;
+            order.visit(s);
+            while let Some((v, i)) = stack.pop() {
+                let Some(&w) = g.adj(v).get(i) else {
+                    order.post_number[v] = order.post.len();
+                    order.post.push(v);
+                    continue;
+                };
+                stack.push((v, i + 1));
+                if !marked[w] {
+                    marked[w] = true;
+                    order.visit(w);
+                    stack.push((w, 0));
+                }
+            }
+        }
+        order
+    }
+
+    fn visit(&mut self, v: usize) {
+        self.pre_number[v] = self.pre.len();
+        self.pre.push(v);
+    }
+
+    /// Vertices in preorder
+    pub fn pre(&self) -> &[usize] {
+        &self.pre
+    }
+
+    /// Vertices in postorder
+    pub fn post(&self) -> &[usize] {
+        &self.post
+    }
+
+    /// Vertices in reverse postorder
+    pub fn reverse_post(&self) -> Vec<usize> {
+        self.post.iter().rev().copied().collect()
+    }
+
+    /// Position of `v` in preorder
+    pub fn pre_number(&self, v: usize) -> usize {
+        self.pre_number[v]
+    }
+
+    /// Position of `v` in postorder
+    pub fn post_number(&self, v: usize) -> usize {
+        self.post_number[v]
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Topological order of a directed acyclic graph: every edge points from a vertex to a vertex
+/// later in the order. It is the reverse postorder of a depth-first search.
+pub struct Topological {
+    order: Option<Vec<usize>>,
+    rank: Vec<usize>,
+}
+
+impl Topological {
+    pub fn new(g: &Digraph) -> Self {
+        if DirectedCycle::new(g).has_cycle() {
+            return Self {
+                order: None,
+                rank: vec![],
+            };
+        }
+        let order = DepthFirstOrder::new(g).reverse_post();
+        let mut rank = vec![0; g.v()];
+        for (i, &v) in order.iter().enumerate() {
+            rank[v] = i;
+        }
+        Self {
+            order: Some(order),
+            rank,
+        }
+    }
+
+    /// Does the digraph have a topological order, that is, is it acyclic?
+    pub fn has_order(&self) -> bool {
+        self.order.is_some()
+    }
+
+    /// The vertices in topological order, or `None` if the digraph has a cycle
+    pub fn order(&self) -> Option<&[usize]> {
+        self.order.as_deref()
+    }
+
+    /// Position of `v` in the topological order, or `None` if the digraph has a cycle
+    pub fn rank(&self, v: usize) -> Option<usize> {
+        self.order.as_ref().map(|_| self.rank[v])
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use crate::graphs::{digraph::Digraph, symbolgraph::SymbolDigraph};
+
+    use super::{DepthFirstOrder, DirectedCycle, DirectedDfs, Topological};
+
+    #[test]
+    fn test_directed_dfs() {
+        let g = Digraph::from_file("resources/tinyDG.txt").unwrap();
+        let reachable =
+            |dfs: &DirectedDfs| -> Vec<usize> { (0..g.v()).filter(|&v| dfs.marked(v)).collect() };
+
+        let dfs = DirectedDfs::new(&g, 1);
+        assert_eq!(reachable(&dfs), vec![1]);
+        let dfs = DirectedDfs::new(&g, 2);
+        assert_eq!(reachable(&dfs), vec![0, 1, 2, 3, 4, 5]);
+        let dfs = DirectedDfs::from_sources(&g, &[1, 2, 6]);
+        assert_eq!(reachable(&dfs), vec![0, 1, 2, 3, 4, 5, 6, 8, 9, 10, 11, 12]);
+        assert_eq!(dfs.count(), 12);
+    }
+
+    #[test]
+    fn test_directed_cycle() {
+        let g = Digraph::from_file("resources/tinyDG.txt").unwrap();
+        let cycle = DirectedCycle::new(&g);
+        let cycle = cycle.cycle().unwrap();
+        assert_eq!(cycle.first(), cycle.last());
+        assert!(cycle.windows(2).all(|e| g.adj(e[0]).contains(&e[1])));
+
+        let g = Digraph::from_file("resources/tinyDAG.txt").unwrap();
+        assert!(!DirectedCycle::new(&g).has_cycle());
+
+        let mut g = Digraph::new(2);
+        g.add_edge(1, 1);
+        assert_eq!(DirectedCycle::new(&g).cycle(), Some(&[1, 1][..]));
+    }
+
+    #[test]
+    fn test_depth_first_order() {
+        let g = Digraph::from_file("resources/tinyDAG.txt").unwrap();
+        let order = DepthFirstOrder::new(&g);
+        assert_eq!(order.pre(), &[0, 6, 4, 9, 12, 10, 11, 1, 5, 2, 3, 7, 8]);
+        assert_eq!(order.post(), &[4, 12, 10, 11, 9, 6, 1, 5, 0, 3, 2, 7, 8]);
+        assert_eq!(order.reverse_post()[0], 8);
+        for v in 0..g.v() {
+            assert_eq!(order.pre()[order.pre_number(v)], v);
+            assert_eq!(order.post()[order.post_number(v)], v);
+        }
+    }
+
+    #[test]
+    fn test_topological() {
+        let g = Digraph::from_file("resources/tinyDAG.txt").unwrap();
+        let topological = Topological::new(&g);
+        assert!(topological.has_order());
+        for v in 0..g.v() {
+            for &w in g.adj(v) {
+                assert!(topological.rank(v) < topological.rank(w));
+            }
+        }
+
+        let g = Digraph::from_file("resources/tinyDG.txt").unwrap();
+        let topological = Topological::new(&g);
+        assert_eq!(topological.order(), None);
+        assert_eq!(topological.rank(0), None);
+    }
+
+    #[test]
+    fn test_jobs() {
+        let sg = SymbolDigraph::from_file("resources/jobs.txt", "/").unwrap();
+        let order = Topological::new(sg.graph());
+        let jobs: Vec<_> = order
+            .order()
+            .unwrap()
+            .iter()
+            .map(|&v| sg.name_of(v))
+            .collect();
+        assert_eq!(jobs.len(), 13);
+        let position = |job| jobs.iter().position(|&j| j == job).unwrap();
+        assert!(position("Calculus") < position("Linear Algebra"));
+        assert!(position("Linear Algebra") < position("Theoretical CS"));
+        assert!(position("Introduction to CS") < position("Algorithms"));
+        assert!(position("Machine Learning") < position("Neural Networks"));
+    }
+}
@@ -0,0 +1,193 @@
+//! Edge-Weighted Digraph
+use std::{
+    fmt::{self, Display},
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+use super::{digraph::Digraph, invalid_data, Input};
+
+/// Weighted directed edge v->w
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DirectedEdge {
+    from: usize,
+    to: usize,
+    weight: f64,
+}
+
+impl DirectedEdge {
+    pub fn new(from: usize, to: usize, weight: f64) -> Self {
+        Self { from, to, weight }
+    }
+
+    /// Tail vertex of the edge
+    pub fn from(&self) -> usize {
+        self.from
+    }
+
+    /// Head vertex of the edge
+    pub fn to(&self) -> usize {
+        self.to
+    }
+
+    pub fn weight(&self) -> f64 {
+        self.weight
+    }
+}
+
+impl Display for DirectedEdge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}->{} {:.2}", self.from, self.to, self.weight)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Directed graph with weighted edges, represented by the list of edges leaving each vertex
+#[derive(Clone, Debug, PartialEq)]
+pub struct EdgeWeightedDigraph {
+    v: usize,
+    e: usize,
+    adj: Vec<Vec<DirectedEdge>>,
+    indegree: Vec<usize>,
+}
+
+impl EdgeWeightedDigraph {
+    /// Create a digraph with `v` vertices and no edges
+    pub fn new(v: usize) -> Self {
+        Self {
+            v,
+            e: 0,
+            adj: vec![vec![]; v],
+            indegree: vec![0; v],
+        }
+    }
+
+    /// Read a digraph in the text format of the book: V, E and then E edges given as two
+    /// vertices and a weight
+    pub fn from_reader<R: BufRead>(reader: R) -> io::Result<Self> {
+        let mut input = Input::new(reader)?;
+        let v = input.next("number of vertices")?;
+        let e: usize = input.next("number of edges")?;
+        let mut graph = Self::new(v);
+        for _ in 0..e {
+            let p = input.next("vertex")?;
+            let q = input.next("vertex")?;
+            let weight = input.next("weight")?;
+            if p >= v || q >= v {
+                return Err(invalid_data(format!("edge {}->{} out of range", p, q)));
+            }
+            graph.add_edge(DirectedEdge::new(p, q, weight));
+        }
+        Ok(graph)
+    }
+
+    /// Read a digraph from a file in the text format of the book
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Number of vertices
+    pub fn v(&self) -> usize {
+        self.v
+    }
+
+    /// Number of edges
+    pub fn e(&self) -> usize {
+        self.e
+    }
+
+    /// Add an edge
+    ///
+    /// # Panics
+    ///
+    /// Panics if an endpoint of the edge is not a vertex of the digraph.
+    pub fn add_edge(&mut self, e: DirectedEdge) {
+        self.validate(e.from());
+        self.validate(e.to());
+        self.adj[e.from()].push(e);
+        self.indegree[e.to()] += 1;
+        self.e += 1;
+    }
+
+    /// Edges leaving `v`
+    pub fn adj(&self, v: usize) -> &[DirectedEdge] {
+        &self.adj[v]
+    }
+
+    /// Number of edges leaving `v`
+    pub fn outdegree(&self, v: usize) -> usize {
+        self.adj[v].len()
+    }
+
+    /// Number of edges pointing to `v`
+    pub fn indegree(&self, v: usize) -> usize {
+        self.indegree[v]
+    }
+
+    /// All the edges of the digraph
+    pub fn edges(&self) -> Vec<DirectedEdge> {
+        self.adj.iter().flatten().copied().collect()
+    }
+
+    /// The digraph without the weights, to run the algorithms that ignore them
+    pub fn digraph(&self) -> Digraph {
+        let mut digraph = Digraph::new(self.v);
+        for e in self.adj.iter().flatten() {
+            digraph.add_edge(e.from(), e.to());
+        }
+        digraph
+    }
+
+    fn validate(&self, v: usize) {
+        assert!(v < self.v, "vertex {} is not between 0 and {}", v, self.v);
+    }
+}
+
+impl Display for EdgeWeightedDigraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} vertices, {} edges", self.v, self.e)?;
+        for v in 0..self.v {
+            write!(f, "{}:", v)?;
+            for e in &self.adj[v] {
+                write!(f, "  {}", e)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::{DirectedEdge, EdgeWeightedDigraph};
+
+    #[test]
+    fn test_from_file() {
+        let g = EdgeWeightedDigraph::from_file("resources/tinyEWD.txt").unwrap();
+        assert_eq!(g.v(), 8);
+        assert_eq!(g.e(), 15);
+        assert_eq!(g.outdegree(6), 3);
+        assert_eq!(g.indegree(6), 1);
+        assert_eq!(g.edges().len(), 15);
+        assert!(g
+            .to_string()
+            .starts_with("8 vertices, 15 edges\n0:  0->4 0.38  0->2 0.26\n"));
+        assert!(EdgeWeightedDigraph::from_reader("2 1 0 2 0.5".as_bytes()).is_err());
+
+        let digraph = g.digraph();
+        assert_eq!(digraph.e(), 15);
+        assert_eq!(digraph.adj(6), &[2, 0, 4]);
+    }
+
+    #[test]
+    fn test_directed_edge() {
+        let e = DirectedEdge::new(6, 2, -1.2);
+        assert_eq!(e.from(), 6);
+        assert_eq!(e.to(), 2);
+        assert_eq!(e.to_string(), "6->2 -1.20");
+    }
+}
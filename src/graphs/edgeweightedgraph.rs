@@ -0,0 +1,249 @@
+//! Edge-Weighted Graph
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+use super::{invalid_data, Input};
+
+/// Weighted undirected edge v-w.
+///
+/// Edges are ordered by weight, and then by their endpoints so that distinct edges of the same
+/// weight are never equal. Weights are compared with `f64::total_cmp`.
+#[derive(Clone, Copy, Debug)]
+pub struct Edge {
+    v: usize,
+    w: usize,
+    weight: f64,
+}
+
+impl Edge {
+    pub fn new(v: usize, w: usize, weight: f64) -> Self {
+        Self { v, w, weight }
+    }
+
+    pub fn weight(&self) -> f64 {
+        self.weight
+    }
+
+    /// One of the endpoints of the edge
+    pub fn either(&self) -> usize {
+        self.v
+    }
+
+    /// The endpoint of the edge that is not `v`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v` is not an endpoint of the edge.
+    pub fn other(&self, v: usize) -> usize {
+        if v == self.v {
+            self.w
+        } else if v == self.w {
+            self.v
+        } else {
+            panic!("{} is not an endpoint of the edge {}", v, self)
+        }
+    }
+}
+
+impl PartialEq for Edge {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Edge {}
+
+impl PartialOrd for Edge {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Edge {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.weight
+            .total_cmp(&other.weight)
+            .then((self.v, self.w).cmp(&(other.v, other.w)))
+    }
+}
+
+impl Display for Edge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{} {:.5}", self.v, self.w, self.weight)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Undirected graph with weighted edges, represented by the list of incident edges of each
+/// vertex. An edge v-w appears in the list of both `v` and `w`, and a self-loop appears twice in
+/// the list of its vertex.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EdgeWeightedGraph {
+    v: usize,
+    e: usize,
+    adj: Vec<Vec<Edge>>,
+}
+
+impl EdgeWeightedGraph {
+    /// Create a graph with `v` vertices and no edges
+    pub fn new(v: usize) -> Self {
+        Self {
+            v,
+            e: 0,
+            adj: vec![vec![]; v],
+        }
+    }
+
+    /// Read a graph in the text format of the book: V, E and then E edges given as two vertices
+    /// and a weight
+    pub fn from_reader<R: BufRead>(reader: R) -> io::Result<Self> {
+        let mut input = Input::new(reader)?;
+        let v = input.next("number of vertices")?;
+        let e: usize = input.next("number of edges")?;
+        let mut graph = Self::new(v);
+        for _ in 0..e {
+            let p = input.next("vertex")?;
+            let q = input.next("vertex")?;
+            let weight = input.next("weight")?;
+            if p >= v || q >= v {
+                return Err(invalid_data(format!("edge {}-{} out of range", p, q)));
+            }
+            graph.add_edge(Edge::new(p, q, weight));
+        }
+        Ok(graph)
+    }
+
+    /// Read a graph from a file in the text format of the book
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Number of vertices
+    pub fn v(&self) -> usize {
+        self.v
+    }
+
+    /// Number of edges
+    pub fn e(&self) -> usize {
+        self.e
+    }
+
+    /// Add an edge
+    ///
+    /// # Panics
+    ///
+    /// Panics if an endpoint of the edge is not a vertex of the graph.
+    pub fn add_edge(&mut self, e: Edge) {
+        let v = e.either();
+        let w = e.other(v);
+        self.validate(v);
+        self.validate(w);
+        self.adj[v].push(e);
+        self.adj[w].push(e);
+        self.e += 1;
+    }
+
+    /// Edges incident to `v`
+    pub fn adj(&self, v: usize) -> &[Edge] {
+        &self.adj[v]
+    }
+
+    /// Number of edges incident to `v`, a self-loop counts twice
+    pub fn degree(&self, v: usize) -> usize {
+        self.adj[v].len()
+    }
+
+    /// All the edges of the graph, each one once
+    pub fn edges(&self) -> Vec<Edge> {
+        let mut edges = Vec::with_capacity(self.e);
+        for v in 0..self.v {
+            let mut self_loops = 0;
+            for e in &self.adj[v] {
+                let w = e.other(v);
+                if w > v {
+                    edges.push(*e);
+                } else if w == v {
+                    // a self-loop is in the list twice
+                    if self_loops % 2 == 0 {
+                        edges.push(*e);
+                    }
+                    self_loops += 1;
+                }
+            }
+        }
+        edges
+    }
+
+    fn validate(&self, v: usize) {
+        assert!(v < self.v, "vertex {} is not between 0 and {}", v, self.v);
+    }
+}
+
+impl Display for EdgeWeightedGraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} vertices, {} edges", self.v, self.e)?;
+        for v in 0..self.v {
+            write!(f, "{}:", v)?;
+            for e in &self.adj[v] {
+                write!(f, "  {}", e)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::{Edge, EdgeWeightedGraph};
+
+    #[test]
+    fn test_edge() {
+        let e = Edge::new(4, 5, 0.35);
+        assert_eq!(e.other(e.either()), 5);
+        assert_eq!(e.other(5), 4);
+        assert_eq!(e.to_string(), "4-5 0.35000");
+        assert!(Edge::new(1, 2, 0.1) < e);
+        assert!(Edge::new(1, 2, 0.35) < e);
+        assert_ne!(Edge::new(1, 2, 0.35), e);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_edge_other() {
+        Edge::new(4, 5, 0.35).other(3);
+    }
+
+    #[test]
+    fn test_from_file() {
+        let g = EdgeWeightedGraph::from_file("resources/tinyEWG.txt").unwrap();
+        assert_eq!(g.v(), 8);
+        assert_eq!(g.e(), 16);
+        assert_eq!(g.degree(6), 4);
+        assert_eq!(g.edges().len(), 16);
+        assert!(g
+            .to_string()
+            .starts_with("8 vertices, 16 edges\n0:  0-7 0.16000"));
+        assert!(EdgeWeightedGraph::from_reader("2 1 0 1 x".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_self_loops() {
+        let mut g = EdgeWeightedGraph::new(2);
+        g.add_edge(Edge::new(0, 0, 1.0));
+        g.add_edge(Edge::new(0, 0, 2.0));
+        g.add_edge(Edge::new(0, 1, 3.0));
+        assert_eq!(g.degree(0), 5);
+        let mut weights: Vec<_> = g.edges().iter().map(Edge::weight).collect();
+        weights.sort_by(f64::total_cmp);
+        assert_eq!(weights, vec![1.0, 2.0, 3.0]);
+    }
+}
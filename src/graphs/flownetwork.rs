@@ -0,0 +1,291 @@
+//! Flow Network
+use std::{
+    fmt::{self, Display},
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+use super::{invalid_data, Input};
+
+/// Tolerance for the comparisons of flows and capacities
+pub(crate) const EPSILON: f64 = 1e-11;
+
+/// Edge v->w of a flow network, with a capacity and the flow going through it.
+///
+/// In the residual network the edge gives a forward edge v->w with the unused capacity, and a
+/// backward edge w->v with the flow, which can be cancelled.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FlowEdge {
+    from: usize,
+    to: usize,
+    capacity: f64,
+    flow: f64,
+}
+
+impl FlowEdge {
+    /// Create an edge with no flow
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity is negative or not a number.
+    pub fn new(from: usize, to: usize, capacity: f64) -> Self {
+        assert!(capacity >= 0.0, "capacity {} is negative", capacity);
+        Self {
+            from,
+            to,
+            capacity,
+            flow: 0.0,
+        }
+    }
+
+    /// Tail vertex of the edge
+    pub fn from(&self) -> usize {
+        self.from
+    }
+
+    /// Head vertex of the edge
+    pub fn to(&self) -> usize {
+        self.to
+    }
+
+    pub fn capacity(&self) -> f64 {
+        self.capacity
+    }
+
+    pub fn flow(&self) -> f64 {
+        self.flow
+    }
+
+    /// The endpoint of the edge that is not `v`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v` is not an endpoint of the edge.
+    pub fn other(&self, v: usize) -> usize {
+        if v == self.from {
+            self.to
+        } else if v == self.to {
+            self.from
+        } else {
+            panic!("{} is not an endpoint of the edge {}", v, self)
+        }
+    }
+
+    /// Capacity of the residual edge towards `v`: the unused capacity if `v` is the head, and
+    /// the flow if `v` is the tail
+    pub fn residual_capacity_to(&self, v: usize) -> f64 {
+        if v == self.to {
+            self.capacity - self.flow
+        } else if v == self.from {
+            self.flow
+        } else {
+            panic!("{} is not an endpoint of the edge {}", v, self)
+        }
+    }
+
+    /// Send `delta` more flow towards `v`, which cancels flow if `v` is the tail. The flow is
+    /// rounded to 0 or to the capacity when it is within `EPSILON` of them.
+    pub fn add_residual_flow_to(&mut self, v: usize, delta: f64) {
+        if v == self.to {
+            self.flow += delta;
+        } else if v == self.from {
+            self.flow -= delta;
+        } else {
+            panic!("{} is not an endpoint of the edge {}", v, self)
+        }
+        if self.flow.abs() <= EPSILON {
+            self.flow = 0.0;
+        }
+        if (self.flow - self.capacity).abs() <= EPSILON {
+            self.flow = self.capacity;
+        }
+    }
+}
+
+impl Display for FlowEdge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}->{} {}/{}",
+            self.from, self.to, self.flow, self.capacity
+        )
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Network of flow edges.
+///
+/// The edges are kept in a vector, and the list of each vertex holds the indices of the edges
+/// leaving it or pointing to it, so that the algorithms can update the flow of an edge from
+/// either endpoint.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlowNetwork {
+    v: usize,
+    edges: Vec<FlowEdge>,
+    adj: Vec<Vec<usize>>,
+}
+
+impl FlowNetwork {
+    /// Create a network with `v` vertices and no edges
+    pub fn new(v: usize) -> Self {
+        Self {
+            v,
+            edges: vec![],
+            adj: vec![vec![]; v],
+        }
+    }
+
+    /// Read a network in the text format of the book: V, E and then E edges given as two
+    /// vertices and a capacity
+    pub fn from_reader<R: BufRead>(reader: R) -> io::Result<Self> {
+        let mut input = Input::new(reader)?;
+        let v = input.next("number of vertices")?;
+        let e: usize = input.next("number of edges")?;
+        let mut network = Self::new(v);
+        for _ in 0..e {
+            let p = input.next("vertex")?;
+            let q = input.next("vertex")?;
+            let capacity: f64 = input.next("capacity")?;
+            if p >= v || q >= v {
+                return Err(invalid_data(format!("edge {}->{} out of range", p, q)));
+            }
+            if capacity.is_nan() || capacity < 0.0 {
+                return Err(invalid_data(format!("invalid capacity {}", capacity)));
+            }
+            network.add_edge(FlowEdge::new(p, q, capacity));
+        }
+        Ok(network)
+    }
+
+    /// Read a network from a file in the text format of the book
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Number of vertices
+    pub fn v(&self) -> usize {
+        self.v
+    }
+
+    /// Number of edges
+    pub fn e(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Add an edge and return its index
+    ///
+    /// # Panics
+    ///
+    /// Panics if an endpoint of the edge is not a vertex of the network.
+    pub fn add_edge(&mut self, e: FlowEdge) -> usize {
+        self.validate(e.from());
+        self.validate(e.to());
+        let i = self.edges.len();
+        self.edges.push(e);
+        self.adj[e.from()].push(i);
+        if e.to() != e.from() {
+            self.adj[e.to()].push(i);
+        }
+        i
+    }
+
+    /// Indices of the edges incident to `v`, leaving it or pointing to it
+    pub fn adj(&self, v: usize) -> &[usize] {
+        &self.adj[v]
+    }
+
+    /// The edge with index `i`
+    pub fn edge(&self, i: usize) -> &FlowEdge {
+        &self.edges[i]
+    }
+
+    pub(crate) fn edge_mut(&mut self, i: usize) -> &mut FlowEdge {
+        &mut self.edges[i]
+    }
+
+    /// All the edges, in the order they were added
+    pub fn edges(&self) -> &[FlowEdge] {
+        &self.edges
+    }
+
+    /// Flow leaving `v` minus flow entering it
+    pub fn net_outflow(&self, v: usize) -> f64 {
+        self.adj[v]
+            .iter()
+            .map(|&i| &self.edges[i])
+            .map(|e| {
+                if e.from() == e.to() {
+                    0.0
+                } else if e.from() == v {
+                    e.flow()
+                } else {
+                    -e.flow()
+                }
+            })
+            .sum()
+    }
+
+    /// Remove all the flow
+    pub fn reset(&mut self) {
+        for e in &mut self.edges {
+            e.flow = 0.0;
+        }
+    }
+
+    fn validate(&self, v: usize) {
+        assert!(v < self.v, "vertex {} is not between 0 and {}", v, self.v);
+    }
+}
+
+impl Display for FlowNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} vertices, {} edges", self.v, self.edges.len())?;
+        for v in 0..self.v {
+            write!(f, "{}:", v)?;
+            for &i in &self.adj[v] {
+                if self.edges[i].from() == v {
+                    write!(f, "  {}", self.edges[i])?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::{FlowEdge, FlowNetwork};
+
+    #[test]
+    fn test_flow_edge() {
+        let mut e = FlowEdge::new(1, 3, 3.0);
+        assert_eq!(e.residual_capacity_to(3), 3.0);
+        assert_eq!(e.residual_capacity_to(1), 0.0);
+        e.add_residual_flow_to(3, 2.0);
+        assert_eq!(e.flow(), 2.0);
+        assert_eq!(e.residual_capacity_to(3), 1.0);
+        assert_eq!(e.residual_capacity_to(1), 2.0);
+        e.add_residual_flow_to(1, 2.0);
+        assert_eq!(e.flow(), 0.0);
+        assert_eq!(e.other(3), 1);
+        assert_eq!(e.to_string(), "1->3 0/3");
+    }
+
+    #[test]
+    fn test_from_file() {
+        let g = FlowNetwork::from_file("resources/tinyFN.txt").unwrap();
+        assert_eq!(g.v(), 6);
+        assert_eq!(g.e(), 8);
+        assert_eq!(g.adj(3).len(), 3);
+        assert_eq!(g.edge(g.adj(5)[0]).to(), 5);
+        assert!(g
+            .to_string()
+            .starts_with("6 vertices, 8 edges\n0:  0->1 0/2  0->2 0/3\n"));
+        assert!(FlowNetwork::from_reader("2 1 0 1 -1".as_bytes()).is_err());
+    }
+}
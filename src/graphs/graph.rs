@@ -0,0 +1,174 @@
+//! Undirected Graph
+use std::{
+    fmt::{self, Display},
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+use super::{read_graph, AddEdge};
+
+/// Undirected graph, represented by the list of adjacent vertices of each vertex.
+///
+/// An edge v-w appears in the list of both `v` and `w`. Parallel edges and self-loops are
+/// allowed; a self-loop appears twice in the list of its vertex.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Graph {
+    v: usize,
+    e: usize,
+    adj: Vec<Vec<usize>>,
+}
+
+impl Graph {
+    /// Create a graph with `v` vertices and no edges
+    pub fn new(v: usize) -> Self {
+        Self {
+            v,
+            e: 0,
+            adj: vec![vec![]; v],
+        }
+    }
+
+    /// Read a graph in the text format of the book: V, E and then E pairs of vertices
+    pub fn from_reader<R: BufRead>(reader: R) -> io::Result<Self> {
+        read_graph(reader)
+    }
+
+    /// Read a graph from a file in the text format of the book
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Number of vertices
+    pub fn v(&self) -> usize {
+        self.v
+    }
+
+    /// Number of edges
+    pub fn e(&self) -> usize {
+        self.e
+    }
+
+    /// Add the edge v-w
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v` or `w` is not a vertex of the graph.
+    pub fn add_edge(&mut self, v: usize, w: usize) {
+        self.validate(v);
+        self.validate(w);
+        self.adj[v].push(w);
+        self.adj[w].push(v);
+        self.e += 1;
+    }
+
+    /// Vertices adjacent to `v`
+    pub fn adj(&self, v: usize) -> &[usize] {
+        &self.adj[v]
+    }
+
+    /// Number of edges incident to `v`, a self-loop counts twice
+    pub fn degree(&self, v: usize) -> usize {
+        self.adj[v].len()
+    }
+
+    /// Largest degree of a vertex
+    pub fn max_degree(&self) -> usize {
+        (0..self.v).map(|v| self.degree(v)).max().unwrap_or(0)
+    }
+
+    /// Average degree of the vertices: 2E / V
+    pub fn average_degree(&self) -> f64 {
+        if self.v == 0 {
+            return 0.0;
+        }
+        2.0 * self.e as f64 / self.v as f64
+    }
+
+    /// Number of edges from a vertex to itself
+    pub fn self_loops(&self) -> usize {
+        let count: usize = (0..self.v)
+            .map(|v| self.adj[v].iter().filter(|&&w| w == v).count())
+            .sum();
+        count / 2
+    }
+
+    fn validate(&self, v: usize) {
+        assert!(v < self.v, "vertex {} is not between 0 and {}", v, self.v);
+    }
+}
+
+impl AddEdge for Graph {
+    fn with_vertices(v: usize) -> Self {
+        Self::new(v)
+    }
+
+    fn add_edge(&mut self, v: usize, w: usize) {
+        Graph::add_edge(self, v, w);
+    }
+}
+
+impl Display for Graph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} vertices, {} edges", self.v, self.e)?;
+        for v in 0..self.v {
+            write!(f, "{}:", v)?;
+            for w in &self.adj[v] {
+                write!(f, " {}", w)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use std::io::{self, Cursor};
+
+    use super::Graph;
+
+    #[test]
+    fn test_from_file() {
+        let g = Graph::from_file("resources/tinyG.txt").unwrap();
+        assert_eq!(g.v(), 13);
+        assert_eq!(g.e(), 13);
+        assert_eq!(g.adj(0), &[5, 1, 2, 6]);
+        assert_eq!(g.degree(0), 4);
+        assert_eq!(g.max_degree(), 4);
+        assert_eq!(g.average_degree(), 2.0);
+        assert_eq!(g.self_loops(), 0);
+
+        let s = g.to_string();
+        assert!(s.starts_with("13 vertices, 13 edges\n0: 5 1 2 6\n"));
+        assert_eq!(s.lines().count(), 14);
+    }
+
+    #[test]
+    fn test_from_reader_errors() {
+        let parse = |s: &str| Graph::from_reader(Cursor::new(s.to_string()));
+        assert_eq!(parse("").unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert!(parse("3\n2\n0 1\n").is_err());
+        assert!(parse("3\n1\n0 3\n").is_err());
+        assert!(parse("3 x").is_err());
+        assert_eq!(parse("3 1 0 1").unwrap().e(), 1);
+    }
+
+    #[test]
+    fn test_self_loops() {
+        let mut g = Graph::new(3);
+        g.add_edge(0, 0);
+        g.add_edge(0, 1);
+        g.add_edge(0, 1);
+        assert_eq!(g.self_loops(), 1);
+        assert_eq!(g.degree(0), 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_add_edge_out_of_range() {
+        Graph::new(3).add_edge(1, 3);
+    }
+}
@@ -0,0 +1,399 @@
+//! Maximum Flow
+//!
+//! A flow from s to t in a flow network respects the capacity of every edge, and the flow
+//! entering every other vertex equals the flow leaving it. The maxflow-mincut theorem says that
+//! the value of a maximum flow equals the capacity of a minimum s-t cut, and the algorithms give
+//! both: the source side of a minimum cut is the set of vertices reachable from s in the
+//! residual network of a maximum flow.
+use std::collections::VecDeque;
+
+use super::{
+    flownetwork::{FlowEdge, FlowNetwork, EPSILON},
+    graph::Graph,
+    search::Bipartite,
+};
+
+/// Value of a maximum flow and source side of a minimum cut
+#[derive(Clone, Debug, PartialEq)]
+pub struct MaxFlow {
+    value: f64,
+    in_cut: Vec<bool>,
+}
+
+impl MaxFlow {
+    // The source side of the cut is found in the residual network of the flow
+    fn new(g: &FlowNetwork, s: usize, value: f64) -> Self {
+        let (in_cut, _) = residual_bfs(g, s);
+        Self { value, in_cut }
+    }
+
+    /// Value of the flow
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Is `v` on the source side of the minimum cut?
+    pub fn in_cut(&self, v: usize) -> bool {
+        self.in_cut[v]
+    }
+
+    /// The vertices on the source side of the minimum cut
+    pub fn min_cut(&self) -> Vec<usize> {
+        (0..self.in_cut.len()).filter(|&v| self.in_cut[v]).collect()
+    }
+
+    /// Check the flow in `g` and the cut, with local conditions only.
+    ///
+    /// Feasibility: the flow of every edge is between 0 and its capacity, and every vertex other
+    /// than `s` and `t` has as much flow entering as leaving. Optimality: `s` is in the cut and
+    /// `t` is not, and the capacity of the cut equals the value of the flow.
+    pub fn check(&self, g: &FlowNetwork, s: usize, t: usize) -> Result<(), String> {
+        for e in g.edges() {
+            if e.flow() < -EPSILON || e.flow() > e.capacity() + EPSILON {
+                return Err(format!("flow of {} is out of bounds", e));
+            }
+        }
+        if (self.value - g.net_outflow(s)).abs() > EPSILON {
+            return Err(format!("net outflow of the source {} is not the value", s));
+        }
+        if (self.value + g.net_outflow(t)).abs() > EPSILON {
+            return Err(format!("net inflow of the sink {} is not the value", t));
+        }
+        for v in (0..g.v()).filter(|&v| v != s && v != t) {
+            if g.net_outflow(v).abs() > EPSILON {
+                return Err(format!("flow is not conserved at {}", v));
+            }
+        }
+
+        if !self.in_cut(s) || self.in_cut(t) {
+            return Err("the cut does not separate the source from the sink".to_string());
+        }
+        let capacity: f64 = g
+            .edges()
+            .iter()
+            .filter(|e| self.in_cut(e.from()) && !self.in_cut(e.to()))
+            .map(FlowEdge::capacity)
+            .sum();
+        if (capacity - self.value).abs() > EPSILON {
+            return Err(format!(
+                "capacity of the cut {} is not the value {}",
+                capacity, self.value
+            ));
+        }
+        Ok(())
+    }
+}
+
+// Breadth-first search of the residual network from s: the marked vertices, and the index of
+// the last edge on the shortest path to each of them
+fn residual_bfs(g: &FlowNetwork, s: usize) -> (Vec<bool>, Vec<usize>) {
+    let mut marked = vec![false; g.v()];
+    let mut edge_to = vec![0; g.v()];
+    let mut queue = VecDeque::from([s]);
+    marked[s] = true;
+    while let Some(v) = queue.pop_front() {
+        for &i in g.adj(v) {
+            let w = g.edge(i).other(v);
+            if !marked[w] && g.edge(i).residual_capacity_to(w) > EPSILON {
+                marked[w] = true;
+                edge_to[w] = i;
+                queue.push_back(w);
+            }
+        }
+    }
+    (marked, edge_to)
+}
+
+fn validate(g: &FlowNetwork, s: usize, t: usize) {
+    assert!(s < g.v() && t < g.v(), "source or sink is not a vertex");
+    assert_ne!(s, t, "source and sink are the same vertex");
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Ford-Fulkerson algorithm with shortest augmenting paths (Edmonds-Karp).
+///
+/// Starting from the flow already in `g`, which is usually zero, sends flow along the path from
+/// `s` to `t` with the fewest edges in the residual network, until there is none. Needs at most
+/// E V / 2 augmenting paths, so takes time proportional to E² V. The maximum flow is left in
+/// `g`.
+///
+/// # Panics
+///
+/// Panics if `s` and `t` are the same vertex, or are not vertices of `g`.
+pub fn ford_fulkerson(g: &mut FlowNetwork, s: usize, t: usize) -> MaxFlow {
+    validate(g, s, t);
+    let mut value = g.net_outflow(s);
+    loop {
+        let (marked, edge_to) = residual_bfs(g, s);
+        if !marked[t] {
+            break;
+        }
+
+        // bottleneck capacity of the path
+        let mut bottleneck = f64::INFINITY;
+        let mut v = t;
+        while v != s {
+            let e = g.edge(edge_to[v]);
+            bottleneck = bottleneck.min(e.residual_capacity_to(v));
+            v = e.other(v);
+        }
+
+        let mut v = t;
+        while v != s {
+            let e = g.edge_mut(edge_to[v]);
+            e.add_residual_flow_to(v, bottleneck);
+            v = e.other(v);
+        }
+        value += bottleneck;
+    }
+    MaxFlow::new(g, s, value)
+}
+
+/// FIFO push-relabel algorithm (Goldberg-Tarjan).
+///
+/// Starts with a preflow that saturates the edges leaving `s`, and gives `s` the height V.
+/// Vertices with more flow entering than leaving are active: they push their excess along
+/// residual edges to vertices one level lower, and are relabeled to go higher when they cannot.
+/// Excess that cannot reach `t` goes back to `s`. Takes time proportional to V³. The flow already
+/// in `g` is discarded, and the maximum flow is left in `g`.
+///
+/// # Panics
+///
+/// Panics if `s` and `t` are the same vertex, or are not vertices of `g`.
+pub fn push_relabel(g: &mut FlowNetwork, s: usize, t: usize) -> MaxFlow {
+    validate(g, s, t);
+    g.reset();
+    let n = g.v();
+    let mut height = vec![0; n];
+    let mut excess = vec![0.0; n];
+    let mut current = vec![0; n]; // next edge to look at in the list of each vertex
+    let mut active = VecDeque::new();
+    height[s] = n;
+
+    for k in 0..g.adj(s).len() {
+        let i = g.adj(s)[k];
+        let w = g.edge(i).other(s);
+        let delta = g.edge(i).residual_capacity_to(w);
+        if w == s || delta <= EPSILON {
+            continue;
+        }
+        g.edge_mut(i).add_residual_flow_to(w, delta);
+        excess[w] += delta;
+        excess[s] -= delta;
+        if w != t && !active.contains(&w) {
+            active.push_back(w);
+        }
+    }
+
+    while let Some(v) = active.pop_front() {
+        // discharge v
+        while excess[v] > EPSILON {
+            let Some(&i) = g.adj(v).get(current[v]) else {
+                // relabel: the lowest residual edge is admissible from the new height
+                height[v] = 1 + g
+                    .adj(v)
+                    .iter()
+                    .map(|&i| g.edge(i))
+                    .filter(|e| e.from() != e.to())
+                    .filter(|e| e.residual_capacity_to(e.other(v)) > EPSILON)
+                    .map(|e| height[e.other(v)])
+                    .min()
+                    .expect("a vertex with excess has a residual edge");
+                current[v] = 0;
+                continue;
+            };
+            let w = g.edge(i).other(v);
+            let residual = g.edge(i).residual_capacity_to(w);
+            if residual > EPSILON && height[v] == height[w] + 1 {
+                let delta = excess[v].min(residual);
+                g.edge_mut(i).add_residual_flow_to(w, delta);
+                excess[v] -= delta;
+                if excess[w] <= EPSILON && w != s && w != t {
+                    active.push_back(w);
+                }
+                excess[w] += delta;
+            } else {
+                current[v] += 1;
+            }
+        }
+    }
+    MaxFlow::new(g, s, excess[t])
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Maximum matching in a bipartite graph, as a maximum flow.
+///
+/// The network has an edge with capacity 1 from a new source to each vertex on one side, from
+/// each vertex on that side to its neighbors on the other side, and from each of these to a new
+/// sink. The edges between the two sides that carry flow form the matching.
+pub struct BipartiteMatching {
+    mate: Vec<Option<usize>>,
+    size: usize,
+}
+
+impl BipartiteMatching {
+    /// Find a maximum matching, or return `None` if the graph is not bipartite
+    pub fn new(g: &Graph) -> Option<Self> {
+        let bipartite = Bipartite::new(g);
+        if !bipartite.is_bipartite() {
+            return None;
+        }
+        let n = g.v();
+        let (s, t) = (n, n + 1);
+        let mut network = FlowNetwork::new(n + 2);
+        for v in 0..n {
+            if bipartite.color(v) {
+                network.add_edge(FlowEdge::new(v, t, 1.0));
+                continue;
+            }
+            network.add_edge(FlowEdge::new(s, v, 1.0));
+            for &w in g.adj(v) {
+                network.add_edge(FlowEdge::new(v, w, 1.0));
+            }
+        }
+        let flow = ford_fulkerson(&mut network, s, t);
+
+        let mut mate = vec![None; n];
+        for e in network.edges() {
+            if e.from() < n && e.to() < n && e.flow() > 0.5 {
+                mate[e.from()] = Some(e.to());
+                mate[e.to()] = Some(e.from());
+            }
+        }
+        Some(Self {
+            mate,
+            size: flow.value().round() as usize,
+        })
+    }
+
+    /// Number of edges in the matching
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The vertex matched with `v`, if any
+    pub fn mate(&self, v: usize) -> Option<usize> {
+        self.mate[v]
+    }
+
+    pub fn is_matched(&self, v: usize) -> bool {
+        self.mate[v].is_some()
+    }
+
+    /// Is every vertex matched?
+    pub fn is_perfect(&self) -> bool {
+        self.mate.iter().all(Option::is_some)
+    }
+
+    /// The edges of the matching, each one given by its smaller vertex first
+    pub fn edges(&self) -> Vec<(usize, usize)> {
+        (0..self.mate.len())
+            .filter_map(|v| self.mate[v].filter(|&w| v < w).map(|w| (v, w)))
+            .collect()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::graphs::{
+        flownetwork::{FlowEdge, FlowNetwork},
+        graph::Graph,
+    };
+
+    use super::{ford_fulkerson, push_relabel, BipartiteMatching};
+
+    #[test]
+    fn test_tiny_fn() {
+        for max_flow in [ford_fulkerson, push_relabel] {
+            let mut g = FlowNetwork::from_file("resources/tinyFN.txt").unwrap();
+            let flow = max_flow(&mut g, 0, 5);
+            assert_eq!(flow.value(), 4.0);
+            assert_eq!(flow.min_cut(), vec![0, 2]);
+            assert!(flow.in_cut(2));
+            assert!(!flow.in_cut(5));
+            assert_eq!(flow.check(&g, 0, 5), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        let mut g = FlowNetwork::from_file("resources/tinyFN.txt").unwrap();
+        let flow = ford_fulkerson(&mut g, 0, 5);
+        let mut h = g.clone();
+        h.reset();
+        assert!(flow.check(&h, 0, 5).is_err());
+        assert!(flow.check(&g, 5, 0).is_err());
+
+        // another flow of the same value, but not a maximum one for a larger capacity
+        let mut g = FlowNetwork::from_file("resources/tinyFN.txt").unwrap();
+        ford_fulkerson(&mut g, 0, 5);
+        let i = g.add_edge(FlowEdge::new(0, 5, 1.0));
+        assert!(flow.check(&g, 0, 5).is_err());
+
+        // the flow already in the network is kept by Ford-Fulkerson
+        let flow = ford_fulkerson(&mut g, 0, 5);
+        assert_eq!(flow.value(), 5.0);
+        assert_eq!(g.edge(i).flow(), 1.0);
+        assert_eq!(flow.check(&g, 0, 5), Ok(()));
+    }
+
+    #[test]
+    fn test_random_networks() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..30 {
+            let v = rng.gen_range(2..30);
+            let mut g = FlowNetwork::new(v);
+            for _ in 0..rng.gen_range(0..4 * v) {
+                let capacity = rng.gen_range(0..20) as f64 / 2.0;
+                g.add_edge(FlowEdge::new(
+                    rng.gen_range(0..v),
+                    rng.gen_range(0..v),
+                    capacity,
+                ));
+            }
+            let (s, t) = (0, v - 1);
+
+            let mut h = g.clone();
+            let flow = ford_fulkerson(&mut g, s, t);
+            assert_eq!(flow.check(&g, s, t), Ok(()));
+            let other = push_relabel(&mut h, s, t);
+            assert_eq!(other.check(&h, s, t), Ok(()));
+            assert_eq!(flow.value(), other.value());
+        }
+    }
+
+    #[test]
+    fn test_bipartite_matching() {
+        // 0, 1, 2 on one side, 3, 4, 5 on the other: 0 and 1 only like 3
+        let mut g = Graph::new(6);
+        for (v, w) in [(0, 3), (1, 3), (2, 3), (2, 4), (2, 5)] {
+            g.add_edge(v, w);
+        }
+        let matching = BipartiteMatching::new(&g).unwrap();
+        assert_eq!(matching.size(), 2);
+        assert_eq!(matching.edges().len(), 2);
+        assert!(!matching.is_perfect());
+        for (v, w) in matching.edges() {
+            assert!(g.adj(v).contains(&w));
+            assert_eq!(matching.mate(w), Some(v));
+        }
+
+        g.add_edge(1, 4);
+        g.add_edge(0, 5);
+        let matching = BipartiteMatching::new(&g).unwrap();
+        assert_eq!(matching.size(), 3);
+        assert!(matching.is_perfect());
+        assert!((0..6).all(|v| matching.is_matched(v)));
+
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 0);
+        assert!(BipartiteMatching::new(&g).is_none());
+    }
+}
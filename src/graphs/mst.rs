@@ -0,0 +1,344 @@
+//! Minimum Spanning Trees
+//!
+//! A minimum spanning tree of a connected edge-weighted graph is a subset of its edges that
+//! connects all the vertices with the smallest total weight. All the algorithms rely on the cut
+//! property: for any partition of the vertices in two sets, the lightest edge crossing it belongs
+//! to a minimum spanning tree.
+//!
+//! When the graph is not connected the algorithms return a minimum spanning forest, made of a
+//! minimum spanning tree of each connected component.
+use crate::{
+    priorityqueues::{binaryheap::MinPQ, indexminpq::IndexMinPQ},
+    unionfind::{UnionFind, WeightedQuickUnionPathCompressionUF},
+};
+
+use super::edgeweightedgraph::{Edge, EdgeWeightedGraph};
+
+/// Edges of a minimum spanning forest and their total weight
+#[derive(Clone, Debug)]
+pub struct SpanningForest {
+    edges: Vec<Edge>,
+    weight: f64,
+}
+
+impl SpanningForest {
+    fn new(edges: Vec<Edge>) -> Self {
+        let weight = edges.iter().map(Edge::weight).sum();
+        Self { edges, weight }
+    }
+
+    /// The edges, in the order in which the algorithm added them
+    pub fn edges(&self) -> &[Edge] {
+        &self.edges
+    }
+
+    /// Total weight of the edges
+    pub fn weight(&self) -> f64 {
+        self.weight
+    }
+
+    /// Check that this is a minimum spanning forest of `g`.
+    ///
+    /// The edges must be edges of `g`, form a forest that connects every pair of vertices
+    /// connected in `g`, and satisfy the cut optimality condition: removing an edge of the forest
+    /// cuts its tree in two, and no edge of `g` crossing that cut may be lighter than the removed
+    /// edge.
+    pub fn check(&self, g: &EdgeWeightedGraph) -> Result<(), String> {
+        let weight: f64 = self.edges.iter().map(Edge::weight).sum();
+        if (weight - self.weight).abs() > 1e-12 {
+            return Err(format!(
+                "weight {} is not the sum of the edges {}",
+                self.weight, weight
+            ));
+        }
+
+        let mut edges = g.edges();
+        edges.sort();
+        for e in &self.edges {
+            if edges.binary_search(e).is_err() {
+                return Err(format!("{} is not an edge of the graph", e));
+            }
+        }
+
+        let mut uf = WeightedQuickUnionPathCompressionUF::new(g.v());
+        for e in &self.edges {
+            let v = e.either();
+            if uf.connected(v, e.other(v)) {
+                return Err(format!("{} closes a cycle", e));
+            }
+            uf.union(v, e.other(v));
+        }
+        for e in g.edges() {
+            let v = e.either();
+            if !uf.connected(v, e.other(v)) {
+                return Err(format!("the endpoints of {} are not connected", e));
+            }
+        }
+
+        for (i, e) in self.edges.iter().enumerate() {
+            let mut uf = WeightedQuickUnionPathCompressionUF::new(g.v());
+            for (j, f) in self.edges.iter().enumerate() {
+                if i != j {
+                    uf.union(f.either(), f.other(f.either()));
+                }
+            }
+            for f in g.edges() {
+                let v = f.either();
+                if !uf.connected(v, f.other(v)) && f.weight() < e.weight() {
+                    return Err(format!("{} is lighter than {} across its cut", f, e));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Lazy version of Prim's algorithm.
+///
+/// Grows a tree from a vertex by adding the lightest edge leaving it. The edges leaving the tree
+/// are kept in a `MinPQ`, and edges that no longer leave it are only discarded when they reach
+/// the top of the queue. Takes time proportional to E log E and space proportional to E.
+pub fn lazy_prim(g: &EdgeWeightedGraph) -> SpanningForest {
+    let mut marked = vec![false; g.v()];
+    let mut pq = MinPQ::new();
+    let mut edges = vec![];
+
+    let visit = |v: usize, marked: &mut Vec<bool>, pq: &mut MinPQ<Edge>| {
+        marked[v] = true;
+        for e in g.adj(v) {
+            if !marked[e.other(v)] {
+                pq.insert(*e);
+            }
+        }
+    };
+
+    for s in 0..g.v() {
+        if marked[s] {
+            continue;
+        }
+        visit(s, &mut marked, &mut pq);
+        while let Some(e) = pq.del_min() {
+            let v = e.either();
+            let w = e.other(v);
+            if marked[v] && marked[w] {
+                continue;
+            }
+            edges.push(e);
+            visit(if marked[v] { w } else { v }, &mut marked, &mut pq);
+        }
+    }
+    SpanningForest::new(edges)
+}
+
+/// Eager version of Prim's algorithm.
+///
+/// Instead of all the edges leaving the tree, an `IndexMinPQ` keeps only the lightest edge that
+/// connects each vertex to the tree. Takes time proportional to E log V and space proportional
+/// to V.
+pub fn prim(g: &EdgeWeightedGraph) -> SpanningForest {
+    let mut marked = vec![false; g.v()];
+    let mut pq = IndexMinPQ::new(g.v()); // lightest edge from the tree to each vertex
+    let mut edges = vec![];
+
+    let scan = |v: usize, marked: &mut Vec<bool>, pq: &mut IndexMinPQ<Edge>| {
+        marked[v] = true;
+        for e in g.adj(v) {
+            let w = e.other(v);
+            if marked[w] {
+                continue;
+            }
+            match pq.key_of(w) {
+                None => pq.insert(w, *e),
+                Some(best) if e < best => pq.decrease_key(w, *e),
+                Some(_) => {}
+            }
+        }
+    };
+
+    for s in 0..g.v() {
+        if marked[s] {
+            continue;
+        }
+        scan(s, &mut marked, &mut pq);
+        while let Some(&e) = pq.min_key() {
+            let v = pq.del_min().unwrap();
+            edges.push(e);
+            scan(v, &mut marked, &mut pq);
+        }
+    }
+    SpanningForest::new(edges)
+}
+
+/// Kruskal's algorithm.
+///
+/// Takes the edges in increasing order of weight, and adds each one that does not close a cycle
+/// with the edges already taken, which union-find tells. Takes time proportional to E log E.
+pub fn kruskal(g: &EdgeWeightedGraph) -> SpanningForest {
+    let mut pq = MinPQ::from_vec(g.edges());
+    let mut uf = WeightedQuickUnionPathCompressionUF::new(g.v());
+    let mut edges = vec![];
+    while edges.len() + 1 < g.v() {
+        let Some(e) = pq.del_min() else {
+            break;
+        };
+        let v = e.either();
+        let w = e.other(v);
+        if !uf.connected(v, w) {
+            uf.union(v, w);
+            edges.push(e);
+        }
+    }
+    SpanningForest::new(edges)
+}
+
+/// Borůvka's algorithm.
+///
+/// In each round, finds the lightest edge leaving each tree of the forest and adds them all.
+/// Every round at least halves the number of trees, so there are at most lg V rounds and it
+/// takes time proportional to E log V. Ties are broken by the order of `Edge`, so that the edges
+/// chosen in a round never form a cycle.
+pub fn boruvka(g: &EdgeWeightedGraph) -> SpanningForest {
+    let all_edges = g.edges();
+    let mut uf = WeightedQuickUnionPathCompressionUF::new(g.v());
+    let mut edges = vec![];
+    loop {
+        // lightest edge leaving each tree, indexed by the root of the tree
+        let mut closest: Vec<Option<Edge>> = vec![None; g.v()];
+        for e in &all_edges {
+            let i = uf.find(e.either());
+            let j = uf.find(e.other(e.either()));
+            if i == j {
+                continue;
+            }
+            for root in [i, j] {
+                if closest[root].is_none_or(|best| *e < best) {
+                    closest[root] = Some(*e);
+                }
+            }
+        }
+
+        let added = edges.len();
+        for e in closest.into_iter().flatten() {
+            let v = e.either();
+            let w = e.other(v);
+            // the same edge can be the closest of both of its trees
+            if !uf.connected(v, w) {
+                uf.union(v, w);
+                edges.push(e);
+            }
+        }
+        if edges.len() == added {
+            break;
+        }
+    }
+    SpanningForest::new(edges)
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::graphs::edgeweightedgraph::{Edge, EdgeWeightedGraph};
+
+    use super::{boruvka, kruskal, lazy_prim, prim, SpanningForest};
+
+    const ALGORITHMS: [fn(&EdgeWeightedGraph) -> SpanningForest; 4] =
+        [lazy_prim, prim, kruskal, boruvka];
+
+    fn sorted_edges(forest: &SpanningForest) -> Vec<Edge> {
+        let mut edges = forest.edges().to_vec();
+        edges.sort();
+        edges
+    }
+
+    #[test]
+    fn test_tiny_ewg() {
+        let g = EdgeWeightedGraph::from_file("resources/tinyEWG.txt").unwrap();
+        let expected = vec![
+            Edge::new(0, 7, 0.16),
+            Edge::new(2, 3, 0.17),
+            Edge::new(1, 7, 0.19),
+            Edge::new(0, 2, 0.26),
+            Edge::new(5, 7, 0.28),
+            Edge::new(4, 5, 0.35),
+            Edge::new(6, 2, 0.40),
+        ];
+        for mst in ALGORITHMS {
+            let forest = mst(&g);
+            assert_eq!(forest.check(&g), Ok(()));
+            assert!((forest.weight() - 1.81).abs() < 1e-9);
+            assert_eq!(sorted_edges(&forest), expected);
+        }
+    }
+
+    #[test]
+    fn test_spanning_forest() {
+        // two components and an isolated vertex
+        let mut g = EdgeWeightedGraph::new(6);
+        g.add_edge(Edge::new(0, 1, 1.0));
+        g.add_edge(Edge::new(1, 2, 2.0));
+        g.add_edge(Edge::new(0, 2, 3.0));
+        g.add_edge(Edge::new(3, 4, 1.5));
+        g.add_edge(Edge::new(3, 3, 0.5));
+        for mst in ALGORITHMS {
+            let forest = mst(&g);
+            assert_eq!(forest.check(&g), Ok(()));
+            assert_eq!(forest.edges().len(), 3);
+            assert_eq!(forest.weight(), 4.5);
+        }
+        assert_eq!(kruskal(&EdgeWeightedGraph::new(0)).edges().len(), 0);
+    }
+
+    #[test]
+    fn test_check() {
+        let g = EdgeWeightedGraph::from_file("resources/tinyEWG.txt").unwrap();
+        let mut edges = kruskal(&g).edges().to_vec();
+
+        // a heavier edge across the cut of 6-2
+        edges.retain(|e| e.weight() != 0.40);
+        edges.push(Edge::new(3, 6, 0.52));
+        assert!(SpanningForest::new(edges.clone()).check(&g).is_err());
+
+        // not spanning
+        edges.pop();
+        assert!(SpanningForest::new(edges.clone()).check(&g).is_err());
+
+        // a cycle
+        edges.push(Edge::new(1, 3, 0.29));
+        edges.push(Edge::new(6, 2, 0.40));
+        assert!(SpanningForest::new(edges.clone()).check(&g).is_err());
+
+        // a lighter edge that is not in the graph
+        edges.truncate(edges.len() - 2);
+        edges.push(Edge::new(6, 2, 0.01));
+        assert_eq!(
+            SpanningForest::new(edges).check(&g),
+            Err("6-2 0.01000 is not an edge of the graph".to_string())
+        );
+    }
+
+    #[test]
+    fn test_random_graphs() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..20 {
+            let v = rng.gen_range(1..60);
+            let mut g = EdgeWeightedGraph::new(v);
+            for _ in 0..rng.gen_range(0..4 * v) {
+                // few distinct weights, to have ties
+                let weight = rng.gen_range(0..10) as f64;
+                g.add_edge(Edge::new(rng.gen_range(0..v), rng.gen_range(0..v), weight));
+            }
+
+            let weight = kruskal(&g).weight();
+            for mst in ALGORITHMS {
+                let forest = mst(&g);
+                assert_eq!(forest.check(&g), Ok(()));
+                assert_eq!(forest.weight(), weight);
+            }
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! Paths
+//!
+//! Find the paths from a source vertex to the other vertices of a graph, with depth-first search
+//! or breadth-first search.
+use std::collections::VecDeque;
+
+use super::graph::Graph;
+
+/// Paths from a source vertex found by depth-first search.
+///
+/// The search is done with an explicit stack instead of recursion, so that large graphs don't
+/// overflow the call stack. It visits the vertices in the same order as the recursive version.
+pub struct DepthFirstPaths {
+    s: usize,
+    marked: Vec<bool>,
+    edge_to: Vec<usize>, // last edge on the known path from s to each vertex
+}
+
+impl DepthFirstPaths {
+    pub fn new(g: &Graph, s: usize) -> Self {
+        let mut paths = Self {
+            s,
+            marked: vec![false; g.v()],
+            edge_to: vec![s; g.v()],
+        };
+        paths.dfs(g, s);
+        paths
+    }
+
+    fn dfs(&mut self, g: &Graph, s: usize) {
+        // each entry is a vertex and the index of the next adjacent vertex to look at
+        let mut stack = vec![(s, 0)];
+        self.marked[s] = true;
+        while let Some((v, i)) = stack.pop() {
+            if let Some(&w) = g.adj(v).get(i) {
+                stack.push((v, i + 1));
+                if !self.marked[w] {
+                    self.marked[w] = true;
+                    self.edge_to[w] = v;
+                    stack.push((w, 0));
+                }
+            }
+        }
+    }
+
+    /// Is there a path from the source to `v`?
+    pub fn has_path_to(&self, v: usize) -> bool {
+        self.marked[v]
+    }
+
+    /// A path from the source to `v`, or `None` if there is none
+    pub fn path_to(&self, v: usize) -> Option<Vec<usize>> {
+        if !self.has_path_to(v) {
+            return None;
+        }
+        Some(path(&self.edge_to, self.s, v))
+    }
+}
+
+// The path from s to v, following the edges back from v
+fn path(edge_to: &[usize], s: usize, v: usize) -> Vec<usize> {
+    let mut path = vec![v];
+    let mut x = v;
+    while x != s {
+        x = edge_to[x];
+        path.push(x);
+    }
+    path.reverse();
+    path
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Shortest paths (with the fewest edges) from one or more source vertices, found by
+/// breadth-first search.
+pub struct BreadthFirstPaths {
+    marked: Vec<bool>,
+    edge_to: Vec<usize>,
+    dist_to: Vec<usize>, // number of edges on the shortest path to each vertex
+}
+
+impl BreadthFirstPaths {
+    pub fn new(g: &Graph, s: usize) -> Self {
+        Self::from_sources(g, &[s])
+    }
+
+    /// Shortest paths from the nearest of the given sources
+    pub fn from_sources(g: &Graph, sources: &[usize]) -> Self {
+        let mut paths = Self {
+            marked: vec![false; g.v()],
+            edge_to: vec![0; g.v()],
+            dist_to: vec![usize::MAX; g.v()],
+        };
+
+        let mut queue = VecDeque::new();
+        for &s in sources {
+            paths.marked[s] = true;
+            paths.edge_to[s] = s;
+            paths.dist_to[s] = 0;
+            queue.push_back(s);
+        }
+        while let Some(v) = queue.pop_front() {
+            for &w in g.adj(v) {
+                if !paths.marked[w] {
+                    paths.marked[w] = true;
+                    paths.edge_to[w] = v;
+                    paths.dist_to[w] = paths.dist_to[v] + 1;
+                    queue.push_back(w);
+                }
+            }
+        }
+        paths
+    }
+
+    /// Is there a path from a source to `v`?
+    pub fn has_path_to(&self, v: usize) -> bool {
+        self.marked[v]
+    }
+
+    /// Number of edges on the shortest path to `v`, or `None` if there is no path
+    pub fn dist_to(&self, v: usize) -> Option<usize> {
+        self.marked[v].then_some(self.dist_to[v])
+    }
+
+    /// A shortest path from a source to `v`, or `None` if there is none
+    pub fn path_to(&self, v: usize) -> Option<Vec<usize>> {
+        if !self.has_path_to(v) {
+            return None;
+        }
+        // sources are their own parent
+        let mut path = vec![v];
+        let mut x = v;
+        while self.edge_to[x] != x {
+            x = self.edge_to[x];
+            path.push(x);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use crate::graphs::graph::Graph;
+
+    use super::{BreadthFirstPaths, DepthFirstPaths};
+
+    #[test]
+    fn test_depth_first_paths() {
+        let g = Graph::from_file("resources/tinyCG.txt").unwrap();
+        let paths = DepthFirstPaths::new(&g, 0);
+        for v in 0..g.v() {
+            let path = paths.path_to(v).unwrap();
+            assert_eq!(path[0], 0);
+            assert_eq!(*path.last().unwrap(), v);
+            assert!(path.windows(2).all(|e| g.adj(e[0]).contains(&e[1])));
+        }
+        // 0 -> 5 -> 3 -> 2 -> 4, following the first unvisited vertex in each list
+        assert_eq!(paths.path_to(4), Some(vec![0, 5, 3, 2, 4]));
+
+        let g = Graph::from_file("resources/tinyG.txt").unwrap();
+        let paths = DepthFirstPaths::new(&g, 0);
+        assert!(paths.has_path_to(3));
+        assert!(!paths.has_path_to(7));
+        assert_eq!(paths.path_to(9), None);
+    }
+
+    #[test]
+    fn test_breadth_first_paths() {
+        let g = Graph::from_file("resources/tinyCG.txt").unwrap();
+        let paths = BreadthFirstPaths::new(&g, 0);
+        let distances: Vec<_> = (0..g.v()).map(|v| paths.dist_to(v).unwrap()).collect();
+        assert_eq!(distances, vec![0, 1, 1, 2, 2, 1]);
+        assert_eq!(paths.path_to(4), Some(vec![0, 2, 4]));
+        assert_eq!(paths.path_to(0), Some(vec![0]));
+
+        let paths = BreadthFirstPaths::from_sources(&g, &[1, 4]);
+        assert_eq!(paths.dist_to(0), Some(1));
+        assert_eq!(paths.dist_to(5), Some(2));
+        assert_eq!(paths.path_to(3), Some(vec![4, 3]));
+    }
+
+    #[test]
+    fn test_long_path() {
+        let n = 200_000;
+        let mut g = Graph::new(n);
+        for v in 1..n {
+            g.add_edge(v - 1, v);
+        }
+        let paths = DepthFirstPaths::new(&g, 0);
+        assert_eq!(paths.path_to(n - 1).unwrap().len(), n);
+    }
+}
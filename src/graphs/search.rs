@@ -0,0 +1,322 @@
+//! Graph Search Clients
+//!
+//! Connected components, cycle detection and two-coloring of undirected graphs. The searches
+//! use an explicit stack or queue, so that large graphs don't overflow the call stack.
+use std::collections::VecDeque;
+
+use super::graph::Graph;
+
+/// Connected components of a graph, found by depth-first search
+pub struct ConnectedComponents {
+    id: Vec<usize>,   // component of each vertex
+    size: Vec<usize>, // number of vertices in each component
+}
+
+impl ConnectedComponents {
+    pub fn new(g: &Graph) -> Self {
+        let mut marked = vec![false; g.v()];
+        let mut id = vec![0; g.v()];
+        let mut size = vec![];
+        for s in 0..g.v() {
+            if marked[s] {
+                continue;
+            }
+            let count = size.len();
+            size.push(0);
+            let mut stack = vec![s];
+            marked[s] = true;
+            while let Some(v) = stack.pop() {
+                id[v] = count;
+                size[count] += 1;
+                for &w in g.adj(v) {
+                    if !marked[w] {
+                        marked[w] = true;
+                        stack.push(w);
+                    }
+                }
+            }
+        }
+        Self { id, size }
+    }
+
+    /// Number of components
+    pub fn count(&self) -> usize {
+        self.size.len()
+    }
+
+    /// Component of `v`, between 0 and `count() - 1`
+    pub fn id(&self, v: usize) -> usize {
+        self.id[v]
+    }
+
+    /// Number of vertices in the component of `v`
+    pub fn size(&self, v: usize) -> usize {
+        self.size[self.id[v]]
+    }
+
+    /// Are `v` and `w` in the same component?
+    pub fn connected(&self, v: usize, w: usize) -> bool {
+        self.id[v] == self.id[w]
+    }
+
+    /// Vertices of each component, in increasing order
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut components = vec![vec![]; self.count()];
+        for (v, &id) in self.id.iter().enumerate() {
+            components[id].push(v);
+        }
+        components
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Finds a cycle in a graph, if it has one.
+///
+/// Self-loops and parallel edges are cycles of length 1 and 2. Otherwise depth-first search finds
+/// a cycle when it reaches a marked vertex other than the one it came from.
+pub struct Cycle {
+    cycle: Option<Vec<usize>>,
+}
+
+impl Cycle {
+    pub fn new(g: &Graph) -> Self {
+        let cycle = Self::self_loop(g)
+            .or_else(|| Self::parallel_edges(g))
+            .or_else(|| Self::dfs(g));
+        Self { cycle }
+    }
+
+    fn self_loop(g: &Graph) -> Option<Vec<usize>> {
+        (0..g.v())
+            .find(|&v| g.adj(v).contains(&v))
+            .map(|v| vec![v, v])
+    }
+
+    fn parallel_edges(g: &Graph) -> Option<Vec<usize>> {
+        let mut marked = vec![false; g.v()];
+        for v in 0..g.v() {
+            for &w in g.adj(v) {
+                if marked[w] {
+                    return Some(vec![v, w, v]);
+                }
+                marked[w] = true;
+            }
+            for &w in g.adj(v) {
+                marked[w] = false;
+            }
+        }
+        None
+    }
+
+    fn dfs(g: &Graph) -> Option<Vec<usize>> {
+        let mut marked = vec![false; g.v()];
+        let mut edge_to = vec![0; g.v()];
+        for s in 0..g.v() {
+            if marked[s] {
+                continue;
+            }
+            // each entry is a vertex, the vertex it was reached from and the index of the next
+            // adjacent vertex to look at
+            let mut stack = vec![(s, s, 0)];
+            marked[s] = true;
+            while let Some((v, parent, i)) = stack.pop() {
+                let Some(&w) = g.adj(v).get(i) else {
+                    continue;
+                };
+                stack.push((v, parent, i + 1));
+                if !marked[w] {
+                    marked[w] = true;
+                    edge_to[w] = v;
+                    stack.push((w, v, 0));
+                } else if w != parent {
+                    // w is on the path from s to v
+                    let mut cycle = vec![v];
+                    let mut x = v;
+                    while x != w {
+                        x = edge_to[x];
+                        cycle.push(x);
+                    }
+                    cycle.push(v);
+                    cycle.reverse();
+                    return Some(cycle);
+                }
+            }
+        }
+        None
+    }
+
+    pub fn has_cycle(&self) -> bool {
+        self.cycle.is_some()
+    }
+
+    /// The vertices of a cycle, starting and ending with the same vertex
+    pub fn cycle(&self) -> Option<&[usize]> {
+        self.cycle.as_deref()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Two-coloring of a graph by breadth-first search, such that no edge connects two vertices of
+/// the same color. If there is none, the graph has a cycle of odd length.
+pub struct Bipartite {
+    color: Vec<bool>,
+    odd_cycle: Option<Vec<usize>>,
+}
+
+impl Bipartite {
+    pub fn new(g: &Graph) -> Self {
+        let mut marked = vec![false; g.v()];
+        let mut color = vec![false; g.v()];
+        let mut edge_to = vec![0; g.v()];
+        for s in 0..g.v() {
+            if marked[s] {
+                continue;
+            }
+            let mut queue = VecDeque::from([s]);
+            marked[s] = true;
+            while let Some(v) = queue.pop_front() {
+                for &w in g.adj(v) {
+                    if !marked[w] {
+                        marked[w] = true;
+                        color[w] = !color[v];
+                        edge_to[w] = v;
+                        queue.push_back(w);
+                    } else if color[w] == color[v] {
+                        let odd_cycle = Some(Self::find_odd_cycle(&edge_to, v, w));
+                        return Self { color, odd_cycle };
+                    }
+                }
+            }
+        }
+        Self {
+            color,
+            odd_cycle: None,
+        }
+    }
+
+    // v and w have the same color, so they are at the same distance from the source: the cycle
+    // goes down from their common ancestor to v, across the edge v-w and back up to the ancestor
+    fn find_odd_cycle(edge_to: &[usize], v: usize, w: usize) -> Vec<usize> {
+        let mut up = vec![];
+        let mut down = vec![];
+        let (mut x, mut y) = (v, w);
+        while x != y {
+            up.push(x);
+            down.push(y);
+            x = edge_to[x];
+            y = edge_to[y];
+        }
+        let mut cycle = vec![x];
+        cycle.extend(up.into_iter().rev());
+        cycle.extend(down);
+        cycle.push(x);
+        cycle
+    }
+
+    pub fn is_bipartite(&self) -> bool {
+        self.odd_cycle.is_none()
+    }
+
+    /// Side of `v` in the two-coloring
+    ///
+    /// # Panics
+    ///
+    /// Panics if the graph is not bipartite.
+    pub fn color(&self, v: usize) -> bool {
+        assert!(self.is_bipartite(), "graph is not bipartite");
+        self.color[v]
+    }
+
+    /// The vertices of a cycle of odd length, starting and ending with the same vertex
+    pub fn odd_cycle(&self) -> Option<&[usize]> {
+        self.odd_cycle.as_deref()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use crate::graphs::graph::Graph;
+
+    use super::{Bipartite, ConnectedComponents, Cycle};
+
+    // Every consecutive pair of vertices in the cycle is an edge of the graph
+    fn is_cycle(g: &Graph, cycle: &[usize]) -> bool {
+        cycle.len() >= 2
+            && cycle[0] == cycle[cycle.len() - 1]
+            && cycle.windows(2).all(|e| g.adj(e[0]).contains(&e[1]))
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let g = Graph::from_file("resources/tinyG.txt").unwrap();
+        let cc = ConnectedComponents::new(&g);
+        assert_eq!(cc.count(), 3);
+        assert!(cc.connected(0, 6));
+        assert!(!cc.connected(0, 7));
+        assert_eq!(cc.size(4), 7);
+        assert_eq!(cc.size(8), 2);
+        assert_eq!(cc.id(12), 2);
+        assert_eq!(
+            cc.components(),
+            vec![vec![0, 1, 2, 3, 4, 5, 6], vec![7, 8], vec![9, 10, 11, 12]]
+        );
+        assert_eq!(ConnectedComponents::new(&Graph::new(0)).count(), 0);
+    }
+
+    #[test]
+    fn test_cycle() {
+        let g = Graph::from_file("resources/tinyG.txt").unwrap();
+        let cycle = Cycle::new(&g);
+        assert!(cycle.has_cycle());
+        assert!(is_cycle(&g, cycle.cycle().unwrap()));
+
+        // a tree has no cycle
+        let mut g = Graph::new(6);
+        for (v, w) in [(0, 1), (0, 2), (1, 3), (1, 4), (2, 5)] {
+            g.add_edge(v, w);
+        }
+        assert!(!Cycle::new(&g).has_cycle());
+
+        g.add_edge(4, 5);
+        let cycle = Cycle::new(&g);
+        assert_eq!(cycle.cycle().unwrap().len(), 6);
+        assert!(is_cycle(&g, cycle.cycle().unwrap()));
+
+        let mut g = Graph::new(3);
+        g.add_edge(1, 2);
+        g.add_edge(2, 1);
+        assert_eq!(Cycle::new(&g).cycle(), Some(&[1, 2, 1][..]));
+        g.add_edge(0, 0);
+        assert_eq!(Cycle::new(&g).cycle(), Some(&[0, 0][..]));
+    }
+
+    #[test]
+    fn test_bipartite() {
+        let g = Graph::from_file("resources/tinyCG.txt").unwrap();
+        let bipartite = Bipartite::new(&g);
+        assert!(!bipartite.is_bipartite());
+        let cycle = bipartite.odd_cycle().unwrap();
+        assert!(is_cycle(&g, cycle));
+        assert_eq!(cycle.len() % 2, 0); // an odd number of edges
+
+        // an even cycle and a separate edge
+        let mut g = Graph::new(6);
+        for (v, w) in [(0, 1), (1, 2), (2, 3), (3, 0), (4, 5)] {
+            g.add_edge(v, w);
+        }
+        let bipartite = Bipartite::new(&g);
+        assert!(bipartite.is_bipartite());
+        for v in 0..g.v() {
+            for &w in g.adj(v) {
+                assert_ne!(bipartite.color(v), bipartite.color(w));
+            }
+        }
+
+        g.add_edge(5, 5);
+        assert_eq!(Bipartite::new(&g).odd_cycle(), Some(&[5, 5][..]));
+    }
+}
@@ -0,0 +1,495 @@
+//! Shortest Paths
+//!
+//! Single-source shortest paths in edge-weighted digraphs: Dijkstra's algorithm for nonnegative
+//! weights, relaxation in topological order for acyclic digraphs, and Bellman-Ford for arbitrary
+//! weights. Floyd-Warshall computes the shortest paths between all pairs of vertices.
+//!
+//! All of them relax edges: the edge v->w is relaxed by checking whether going through v is a
+//! shorter way to reach w than the best one known so far.
+use std::{cmp::Ordering, collections::VecDeque};
+
+use crate::priorityqueues::indexminpq::IndexMinPQ;
+
+use super::{
+    digraphsearch::Topological,
+    edgeweighteddigraph::{DirectedEdge, EdgeWeightedDigraph},
+};
+
+/// Shortest paths tree from a source vertex
+#[derive(Clone, Debug)]
+pub struct ShortestPaths {
+    source: usize,
+    dist_to: Vec<f64>, // length of the shortest known path to each vertex
+    edge_to: Vec<Option<DirectedEdge>>, // last edge on that path
+}
+
+impl ShortestPaths {
+    fn new(g: &EdgeWeightedDigraph, source: usize) -> Self {
+        let mut dist_to = vec![f64::INFINITY; g.v()];
+        dist_to[source] = 0.0;
+        Self {
+            source,
+            dist_to,
+            edge_to: vec![None; g.v()],
+        }
+    }
+
+    /// Is there a path from the source to `v`?
+    pub fn has_path_to(&self, v: usize) -> bool {
+        v == self.source || self.edge_to[v].is_some()
+    }
+
+    /// Length of the shortest path from the source to `v`, or `None` if there is no path
+    pub fn dist_to(&self, v: usize) -> Option<f64> {
+        self.has_path_to(v).then_some(self.dist_to[v])
+    }
+
+    /// Edges of the shortest path from the source to `v`, or `None` if there is no path
+    pub fn path_to(&self, v: usize) -> Option<Vec<DirectedEdge>> {
+        if !self.has_path_to(v) {
+            return None;
+        }
+        let mut path = vec![];
+        let mut e = self.edge_to[v];
+        while let Some(edge) = e {
+            path.push(edge);
+            e = self.edge_to[edge.from()];
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    // Relax the edge e, and tell whether it gave a shorter path to its head
+    fn relax(&mut self, e: &DirectedEdge) -> bool {
+        let (v, w) = (e.from(), e.to());
+        if self.dist_to[w] > self.dist_to[v] + e.weight() {
+            self.dist_to[w] = self.dist_to[v] + e.weight();
+            self.edge_to[w] = Some(*e);
+            return true;
+        }
+        false
+    }
+}
+
+/// Distances ordered with `f64::total_cmp`, to be used as keys of a priority queue
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Distance(f64);
+
+impl Eq for Distance {}
+
+impl PartialOrd for Distance {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Distance {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Dijkstra's algorithm.
+///
+/// Relaxes the edges leaving the vertex closest to the source that has not been relaxed yet,
+/// which an `IndexMinPQ` of the distances gives. Takes time proportional to E log V.
+///
+/// # Panics
+///
+/// Panics if an edge has a negative weight.
+pub fn dijkstra(g: &EdgeWeightedDigraph, s: usize) -> ShortestPaths {
+    let edges = g.edges();
+    if let Some(e) = edges.iter().find(|e| e.weight() < 0.0) {
+        panic!("edge {} has a negative weight", e);
+    }
+
+    let mut paths = ShortestPaths::new(g, s);
+    let mut pq = IndexMinPQ::new(g.v());
+    pq.insert(s, Distance(0.0));
+    while let Some(v) = pq.del_min() {
+        for e in g.adj(v) {
+            if paths.relax(e) {
+                let w = e.to();
+                let distance = Distance(paths.dist_to[w]);
+                if pq.contains(w) {
+                    pq.decrease_key(w, distance);
+                } else {
+                    pq.insert(w, distance);
+                }
+            }
+        }
+    }
+    paths
+}
+
+/// Shortest paths in an acyclic digraph, by relaxing the vertices in topological order. Takes
+/// time proportional to E + V, and works with negative weights.
+///
+/// Returns `None` if the digraph has a cycle.
+pub fn acyclic_shortest_paths(g: &EdgeWeightedDigraph, s: usize) -> Option<ShortestPaths> {
+    let mut paths = ShortestPaths::new(g, s);
+    relax_in_topological_order(g, &mut paths)?;
+    Some(paths)
+}
+
+/// Longest paths in an acyclic digraph: the shortest paths with all the weights negated, as in
+/// critical path scheduling.
+///
+/// Returns `None` if the digraph has a cycle.
+pub fn acyclic_longest_paths(g: &EdgeWeightedDigraph, s: usize) -> Option<ShortestPaths> {
+    let mut negated = EdgeWeightedDigraph::new(g.v());
+    for e in g.edges() {
+        negated.add_edge(DirectedEdge::new(e.from(), e.to(), -e.weight()));
+    }
+    let mut paths = ShortestPaths::new(&negated, s);
+    relax_in_topological_order(&negated, &mut paths)?;
+
+    for d in paths.dist_to.iter_mut() {
+        *d = -*d;
+    }
+    for e in paths.edge_to.iter_mut().flatten() {
+        *e = DirectedEdge::new(e.from(), e.to(), -e.weight());
+    }
+    Some(paths)
+}
+
+fn relax_in_topological_order(g: &EdgeWeightedDigraph, paths: &mut ShortestPaths) -> Option<()> {
+    let topological = Topological::new(&g.digraph());
+    for &v in topological.order()? {
+        for e in g.adj(v) {
+            paths.relax(e);
+        }
+    }
+    Some(())
+}
+
+/// Queue-based Bellman-Ford algorithm.
+///
+/// Only the edges leaving vertices whose distance changed are relaxed, which a queue keeps track
+/// of. Takes time proportional to E V in the worst case, but is usually much faster.
+///
+/// If a negative cycle can be reached from the source there are no shortest paths, and the edges
+/// of the cycle are returned as the error. The search for a cycle in the edges of the paths is
+/// done after every V relaxations.
+pub fn bellman_ford(g: &EdgeWeightedDigraph, s: usize) -> Result<ShortestPaths, Vec<DirectedEdge>> {
+    let mut paths = ShortestPaths::new(g, s);
+    let mut queue = VecDeque::from([s]);
+    let mut on_queue = vec![false; g.v()];
+    on_queue[s] = true;
+    let mut relaxations = 0;
+    while let Some(v) = queue.pop_front() {
+        on_queue[v] = false;
+        for e in g.adj(v) {
+            let w = e.to();
+            if paths.relax(e) && !on_queue[w] {
+                queue.push_back(w);
+                on_queue[w] = true;
+            }
+            relaxations += 1;
+            if relaxations % g.v() == 0 {
+                if let Some(cycle) = find_cycle(&paths.edge_to) {
+                    return Err(cycle);
+                }
+            }
+        }
+    }
+    Ok(paths)
+}
+
+// Find a cycle in the edges of a shortest paths tree, where each vertex has at most one edge
+// pointing to it. Follows the edges backwards from each vertex, until reaching the source, a
+// vertex already seen in a previous walk, or a vertex already seen in this walk: a cycle.
+fn find_cycle(edge_to: &[Option<DirectedEdge>]) -> Option<Vec<DirectedEdge>> {
+    let mut walk = vec![None; edge_to.len()]; // walk in which each vertex was seen
+    for start in 0..edge_to.len() {
+        let mut v = start;
+        while walk[v].is_none() {
+            walk[v] = Some(start);
+            match edge_to[v] {
+                Some(e) => v = e.from(),
+                None => break,
+            }
+        }
+        if walk[v] == Some(start) && edge_to[v].is_some() {
+            // the walk came back to v, which is on the cycle
+            let mut cycle = vec![];
+            let mut x = v;
+            loop {
+                let e = edge_to[x]?;
+                cycle.push(e);
+                x = e.from();
+                if x == v {
+                    break;
+                }
+            }
+            cycle.reverse();
+            return Some(cycle);
+        }
+    }
+    None
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Floyd-Warshall algorithm for the shortest paths between all pairs of vertices.
+///
+/// After considering the vertices 0 to i - 1 as intermediate vertices, `dist[s][t]` is the
+/// length of the shortest path from s to t through those vertices only. Takes time proportional
+/// to V³ and space proportional to V², so it is suited to dense digraphs. Works with negative
+/// weights, and finds a negative cycle if there is one.
+pub struct FloydWarshall {
+    dist: Vec<Vec<f64>>,
+    edge_to: Vec<Vec<Option<DirectedEdge>>>, // last edge on the shortest path from s to t
+    negative_cycle: Option<Vec<DirectedEdge>>,
+}
+
+impl FloydWarshall {
+    pub fn new(g: &EdgeWeightedDigraph) -> Self {
+        let n = g.v();
+        let mut dist = vec![vec![f64::INFINITY; n]; n];
+        let mut edge_to = vec![vec![None; n]; n];
+        for e in g.edges() {
+            if e.weight() < dist[e.from()][e.to()] {
+                dist[e.from()][e.to()] = e.weight();
+                edge_to[e.from()][e.to()] = Some(e);
+            }
+        }
+        // a negative self-loop is a negative cycle
+        for v in 0..n {
+            if dist[v][v] >= 0.0 {
+                dist[v][v] = 0.0;
+                edge_to[v][v] = None;
+            }
+        }
+
+        for i in 0..n {
+            for s in 0..n {
+                if edge_to[s][i].is_none() {
+                    continue;
+                }
+                for t in 0..n {
+                    if dist[s][i] + dist[i][t] < dist[s][t] {
+                        dist[s][t] = dist[s][i] + dist[i][t];
+                        edge_to[s][t] = edge_to[i][t];
+                    }
+                }
+                if dist[s][s] < 0.0 {
+                    let negative_cycle = find_cycle(&edge_to[s]);
+                    return Self {
+                        dist,
+                        edge_to,
+                        negative_cycle,
+                    };
+                }
+            }
+        }
+        Self {
+            dist,
+            edge_to,
+            negative_cycle: None,
+        }
+    }
+
+    pub fn has_negative_cycle(&self) -> bool {
+        self.negative_cycle.is_some()
+    }
+
+    /// The edges of a negative cycle, if there is one
+    pub fn negative_cycle(&self) -> Option<&[DirectedEdge]> {
+        self.negative_cycle.as_deref()
+    }
+
+    /// Length of the shortest path from `s` to `t`, or `None` if there is no path
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is a negative cycle.
+    pub fn dist(&self, s: usize, t: usize) -> Option<f64> {
+        assert!(!self.has_negative_cycle(), "there is a negative cycle");
+        (self.dist[s][t] < f64::INFINITY).then_some(self.dist[s][t])
+    }
+
+    /// Edges of the shortest path from `s` to `t`, or `None` if there is no path
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is a negative cycle.
+    pub fn path(&self, s: usize, t: usize) -> Option<Vec<DirectedEdge>> {
+        self.dist(s, t)?;
+        let mut path = vec![];
+        let mut e = self.edge_to[s][t];
+        while let Some(edge) = e {
+            path.push(edge);
+            e = self.edge_to[s][edge.from()];
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::graphs::edgeweighteddigraph::{DirectedEdge, EdgeWeightedDigraph};
+
+    use super::{
+        acyclic_longest_paths, acyclic_shortest_paths, bellman_ford, dijkstra, FloydWarshall,
+        ShortestPaths,
+    };
+
+    fn distances(paths: &ShortestPaths, v: usize) -> Vec<f64> {
+        (0..v)
+            .map(|w| (paths.dist_to(w).unwrap() * 100.0).round() / 100.0)
+            .collect()
+    }
+
+    // The path is made of consecutive edges from the source and its length is the distance
+    fn check_paths(g: &EdgeWeightedDigraph, paths: &ShortestPaths, s: usize) {
+        for v in 0..g.v() {
+            let Some(path) = paths.path_to(v) else {
+                assert_eq!(paths.dist_to(v), None);
+                continue;
+            };
+            let mut x = s;
+            let mut length = 0.0;
+            for e in &path {
+                assert_eq!(e.from(), x);
+                x = e.to();
+                length += e.weight();
+            }
+            assert_eq!(x, v);
+            assert!((length - paths.dist_to(v).unwrap()).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let g = EdgeWeightedDigraph::from_file("resources/tinyEWD.txt").unwrap();
+        let paths = dijkstra(&g, 0);
+        assert_eq!(
+            distances(&paths, g.v()),
+            vec![0.0, 1.05, 0.26, 0.99, 0.38, 0.73, 1.51, 0.60]
+        );
+        check_paths(&g, &paths, 0);
+        let path: Vec<_> = paths.path_to(6).unwrap().iter().map(|e| e.to()).collect();
+        assert_eq!(path, vec![2, 7, 3, 6]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_dijkstra_negative_weight() {
+        let g = EdgeWeightedDigraph::from_file("resources/tinyEWDn.txt").unwrap();
+        dijkstra(&g, 0);
+    }
+
+    #[test]
+    fn test_acyclic() {
+        let g = EdgeWeightedDigraph::from_file("resources/tinyEWDAG.txt").unwrap();
+        let paths = acyclic_shortest_paths(&g, 5).unwrap();
+        assert_eq!(
+            distances(&paths, g.v()),
+            vec![0.73, 0.32, 0.62, 0.61, 0.35, 0.0, 1.13, 0.28]
+        );
+        check_paths(&g, &paths, 5);
+
+        let paths = acyclic_longest_paths(&g, 5).unwrap();
+        assert_eq!(
+            distances(&paths, g.v()),
+            vec![2.44, 0.32, 2.77, 0.61, 2.06, 0.0, 1.13, 2.43]
+        );
+        check_paths(&g, &paths, 5);
+
+        let paths = acyclic_shortest_paths(&g, 6).unwrap();
+        assert_eq!(paths.dist_to(5), None);
+        assert_eq!(paths.path_to(1), None);
+
+        let g = EdgeWeightedDigraph::from_file("resources/tinyEWD.txt").unwrap();
+        assert!(acyclic_shortest_paths(&g, 0).is_none());
+    }
+
+    #[test]
+    fn test_bellman_ford() {
+        let g = EdgeWeightedDigraph::from_file("resources/tinyEWDn.txt").unwrap();
+        let paths = bellman_ford(&g, 0).unwrap();
+        assert_eq!(
+            distances(&paths, g.v()),
+            vec![0.0, 0.93, 0.26, 0.99, 0.26, 0.61, 1.51, 0.60]
+        );
+        check_paths(&g, &paths, 0);
+
+        let g = EdgeWeightedDigraph::from_file("resources/tinyEWDnc.txt").unwrap();
+        let cycle = bellman_ford(&g, 0).unwrap_err();
+        let mut cycle: Vec<_> = cycle.iter().map(|e| e.to_string()).collect();
+        cycle.sort();
+        assert_eq!(cycle, vec!["4->5 0.35", "5->4 -0.66"]);
+
+        let mut g = EdgeWeightedDigraph::new(2);
+        g.add_edge(DirectedEdge::new(0, 0, -1.0));
+        assert_eq!(bellman_ford(&g, 0).unwrap_err().len(), 1);
+    }
+
+    #[test]
+    fn test_floyd_warshall() {
+        let g = EdgeWeightedDigraph::from_file("resources/tinyEWDn.txt").unwrap();
+        let all_pairs = FloydWarshall::new(&g);
+        assert!(!all_pairs.has_negative_cycle());
+        for s in 0..g.v() {
+            let paths = bellman_ford(&g, s).unwrap();
+            for t in 0..g.v() {
+                let expected = paths.dist_to(t).unwrap();
+                assert!((all_pairs.dist(s, t).unwrap() - expected).abs() < 1e-9);
+                let length: f64 = all_pairs
+                    .path(s, t)
+                    .unwrap()
+                    .iter()
+                    .map(|e| e.weight())
+                    .sum();
+                assert!((length - expected).abs() < 1e-9);
+            }
+        }
+        assert_eq!(all_pairs.path(3, 3), Some(vec![]));
+
+        let g = EdgeWeightedDigraph::from_file("resources/tinyEWDnc.txt").unwrap();
+        let all_pairs = FloydWarshall::new(&g);
+        let cycle = all_pairs.negative_cycle().unwrap();
+        assert!(cycle.iter().map(|e| e.weight()).sum::<f64>() < 0.0);
+    }
+
+    #[test]
+    fn test_random_digraphs() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..20 {
+            let v = rng.gen_range(1..30);
+            let mut g = EdgeWeightedDigraph::new(v);
+            for _ in 0..rng.gen_range(0..4 * v) {
+                let weight = rng.gen_range(0..100) as f64 / 10.0;
+                g.add_edge(DirectedEdge::new(
+                    rng.gen_range(0..v),
+                    rng.gen_range(0..v),
+                    weight,
+                ));
+            }
+
+            let all_pairs = FloydWarshall::new(&g);
+            for s in 0..v {
+                let paths = dijkstra(&g, s);
+                check_paths(&g, &paths, s);
+                let other = bellman_ford(&g, s).unwrap();
+                for t in 0..v {
+                    let d = paths.dist_to(t);
+                    assert_eq!(d.is_some(), other.dist_to(t).is_some());
+                    assert_eq!(d.is_some(), all_pairs.dist(s, t).is_some());
+                    if let Some(d) = d {
+                        assert!((d - other.dist_to(t).unwrap()).abs() < 1e-9);
+                        assert!((d - all_pairs.dist(s, t).unwrap()).abs() < 1e-9);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,264 @@
+//! Strong Components
+//!
+//! Two vertices of a digraph are strongly connected if each one can be reached from the other.
+//! The strong components are the maximal sets of strongly connected vertices.
+use super::{
+    digraph::Digraph,
+    digraphsearch::{DepthFirstOrder, DirectedDfs},
+};
+
+/// Strong components found with the Kosaraju-Sharir algorithm.
+///
+/// A depth-first search of the reversed digraph gives a reverse postorder in which every vertex
+/// comes before the vertices of the components it can reach but that cannot reach it. A
+/// depth-first search of the digraph that starts from the vertices in this order then visits
+/// exactly one strong component from each start.
+pub struct KosarajuSharirSCC {
+    id: Vec<usize>,
+    count: usize,
+}
+
+impl KosarajuSharirSCC {
+    pub fn new(g: &Digraph) -> Self {
+        let order = DepthFirstOrder::new(&g.reverse()).reverse_post();
+        let mut marked = vec![false; g.v()];
+        let mut id = vec![0; g.v()];
+        let mut count = 0;
+        for s in order {
+            if marked[s] {
+                continue;
+            }
+            let mut stack = vec![s];
+            marked[s] = true;
+            while let Some(v) = stack.pop() {
+                id[v] = count;
+                for &w in g.adj(v) {
+                    if !marked[w] {
+                        marked[w] = true;
+                        stack.push(w);
+                    }
+                }
+            }
+            count += 1;
+        }
+        Self { id, count }
+    }
+
+    /// Number of strong components
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Strong component of `v`, between 0 and `count() - 1`
+    pub fn id(&self, v: usize) -> usize {
+        self.id[v]
+    }
+
+    /// Are `v` and `w` strongly connected?
+    pub fn strongly_connected(&self, v: usize, w: usize) -> bool {
+        self.id[v] == self.id[w]
+    }
+
+    /// Vertices of each strong component, in increasing order
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        components(&self.id, self.count)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Strong components found with Tarjan's algorithm, in a single depth-first search.
+///
+/// `low[v]` is the smallest preorder number of a vertex on the stack reachable from `v` through
+/// its descendants. When `low[v]` is the preorder number of `v` itself, `v` is the first vertex
+/// of its component to be visited and the component is on the top of the stack.
+pub struct TarjanSCC {
+    id: Vec<usize>,
+    count: usize,
+}
+
+impl TarjanSCC {
+    pub fn new(g: &Digraph) -> Self {
+        let n = g.v();
+        let mut marked = vec![false; n];
+        let mut low = vec![0; n];
+        let mut pre = vec![0; n];
+        let mut id = vec![0; n];
+        let mut pre_count = 0;
+        let mut count = 0;
+        let mut component = vec![]; // vertices whose component is not known yet
+
+        for s in 0..n {
+            if marked[s] {
+                continue;
+            }
+            // each entry is a vertex and the index of the next adjacent vertex to look at
+            let mut stack = vec![(s, 0)];
+            while let Some((v, i)) = stack.pop() {
+                if i == 0 {
+                    marked[v] = true;
+                    pre[v] = pre_count;
+                    low[v] = pre_count;
+                    pre_count += 1;
+                    component.push(v);
+                }
+                if let Some(&w) = g.adj(v).get(i) {
+                    stack.push((v, i + 1));
+                    if !marked[w] {
+                        stack.push((w, 0));
+                    } else {
+                        // vertices of finished components have a low of n
+                        low[v] = low[v].min(low[w]);
+                    }
+                    continue;
+                }
+
+                if low[v] == pre[v] {
+                    while let Some(w) = component.pop() {
+                        id[w] = count;
+                        low[w] = n;
+                        if w == v {
+                            break;
+                        }
+                    }
+                    count += 1;
+                }
+                if let Some(&(parent, _)) = stack.last() {
+                    low[parent] = low[parent].min(low[v]);
+                }
+            }
+        }
+        Self { id, count }
+    }
+
+    /// Number of strong components
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Strong component of `v`, between 0 and `count() - 1`
+    pub fn id(&self, v: usize) -> usize {
+        self.id[v]
+    }
+
+    /// Are `v` and `w` strongly connected?
+    pub fn strongly_connected(&self, v: usize, w: usize) -> bool {
+        self.id[v] == self.id[w]
+    }
+
+    /// Vertices of each strong component, in increasing order
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        components(&self.id, self.count)
+    }
+}
+
+fn components(id: &[usize], count: usize) -> Vec<Vec<usize>> {
+    let mut components = vec![vec![]; count];
+    for (v, &id) in id.iter().enumerate() {
+        components[id].push(v);
+    }
+    components
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Reachability between every pair of vertices, with a depth-first search from each vertex.
+///
+/// Takes time proportional to V (V + E) and space proportional to V², so it is only suited to
+/// small or dense digraphs.
+pub struct TransitiveClosure {
+    reachable: Vec<DirectedDfs>,
+}
+
+impl TransitiveClosure {
+    pub fn new(g: &Digraph) -> Self {
+        Self {
+            reachable: (0..g.v()).map(|v| DirectedDfs::new(g, v)).collect(),
+        }
+    }
+
+    /// Is there a directed path from `v` to `w`?
+    pub fn reachable(&self, v: usize, w: usize) -> bool {
+        self.reachable[v].marked(w)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::graphs::{digraph::Digraph, symbolgraph::SymbolDigraph};
+
+    use super::{KosarajuSharirSCC, TarjanSCC, TransitiveClosure};
+
+    fn sorted(mut components: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+        components.sort();
+        components
+    }
+
+    #[test]
+    fn test_strong_components() {
+        let g = Digraph::from_file("resources/tinyDG.txt").unwrap();
+        let expected = vec![
+            vec![0, 2, 3, 4, 5],
+            vec![1],
+            vec![6, 8],
+            vec![7],
+            vec![9, 10, 11, 12],
+        ];
+
+        let kosaraju = KosarajuSharirSCC::new(&g);
+        assert_eq!(kosaraju.count(), 5);
+        assert_eq!(sorted(kosaraju.components()), expected);
+        assert!(kosaraju.strongly_connected(0, 5));
+        assert!(!kosaraju.strongly_connected(6, 7));
+
+        let tarjan = TarjanSCC::new(&g);
+        assert_eq!(tarjan.count(), 5);
+        assert_eq!(sorted(tarjan.components()), expected);
+        assert_eq!(tarjan.id(9), tarjan.id(12));
+    }
+
+    #[test]
+    fn test_random_digraphs() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..20 {
+            let v = rng.gen_range(1..50);
+            let mut g = Digraph::new(v);
+            for _ in 0..rng.gen_range(0..3 * v) {
+                g.add_edge(rng.gen_range(0..v), rng.gen_range(0..v));
+            }
+
+            let kosaraju = KosarajuSharirSCC::new(&g);
+            let tarjan = TarjanSCC::new(&g);
+            let closure = TransitiveClosure::new(&g);
+            assert_eq!(kosaraju.count(), tarjan.count());
+            for p in 0..v {
+                for q in 0..v {
+                    let expected = closure.reachable(p, q) && closure.reachable(q, p);
+                    assert_eq!(kosaraju.strongly_connected(p, q), expected);
+                    assert_eq!(tarjan.strongly_connected(p, q), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_transitive_closure() {
+        let g = Digraph::from_file("resources/tinyDG.txt").unwrap();
+        let closure = TransitiveClosure::new(&g);
+        assert!(closure.reachable(7, 1));
+        assert!(closure.reachable(1, 1));
+        assert!(!closure.reachable(1, 7));
+        assert!(!closure.reachable(0, 6));
+
+        let sg = SymbolDigraph::from_file("resources/jobs.txt", "/").unwrap();
+        let closure = TransitiveClosure::new(sg.graph());
+        let job = |name| sg.index_of(name).unwrap();
+        assert!(closure.reachable(job("Calculus"), job("Robotics")));
+        assert!(!closure.reachable(job("Robotics"), job("Calculus")));
+        assert_eq!(TarjanSCC::new(sg.graph()).count(), sg.graph().v());
+    }
+}
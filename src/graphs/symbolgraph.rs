@@ -0,0 +1,193 @@
+//! Symbol Graph
+//!
+//! Graphs whose vertices are names, read from files where each line lists a name followed by the
+//! names it is connected to, separated by a delimiter. In `movies.txt` a line is a movie and its
+//! performers separated by `/`, and in `routes.txt` a line is a pair of airports separated by a
+//! space. In `jobs.txt`, read as a digraph, a line is a job followed by the jobs that must come
+//! after it.
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+use crate::symboltables::balancedtree::BalancedTree;
+
+use super::{digraph::Digraph, graph::Graph, paths::BreadthFirstPaths, AddEdge};
+
+/// Graph with named vertices, undirected by default.
+///
+/// The names are given indices in the order they are first seen: a `BalancedTree` maps each name
+/// to its index, and a vector maps each index back to its name.
+pub struct SymbolGraph<G = Graph> {
+    indices: BalancedTree<String, usize>, // index of each name
+    names: Vec<String>,                   // name of each index
+    graph: G,
+}
+
+/// Digraph with named vertices
+pub type SymbolDigraph = SymbolGraph<Digraph>;
+
+impl<G: AddEdge> SymbolGraph<G> {
+    /// Read the graph: the first name of each line is connected to each of the other names, or
+    /// has an edge to each of them in a digraph.
+    /// Empty lines are skipped.
+    pub fn from_reader<R: BufRead>(reader: R, delimiter: &str) -> io::Result<Self> {
+        let mut indices = BalancedTree::new();
+        let mut names = vec![];
+        let mut edges = vec![];
+        for line in reader.lines() {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            let mut vertices = line.split(delimiter).map(|name| {
+                if let Some(index) = indices.get(name.to_string()) {
+                    return index;
+                }
+                indices.put(name.to_string(), names.len());
+                names.push(name.to_string());
+                names.len() - 1
+            });
+            if let Some(v) = vertices.next() {
+                edges.extend(vertices.map(|w| (v, w)));
+            }
+        }
+
+        let mut graph = G::with_vertices(names.len());
+        for (v, w) in edges {
+            graph.add_edge(v, w);
+        }
+        Ok(Self {
+            indices,
+            names,
+            graph,
+        })
+    }
+
+    /// Read the graph from a file
+    pub fn from_file<P: AsRef<Path>>(path: P, delimiter: &str) -> io::Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?), delimiter)
+    }
+}
+
+impl<G> SymbolGraph<G> {
+    /// Is `name` a vertex of the graph?
+    pub fn contains(&self, name: &str) -> bool {
+        self.index_of(name).is_some()
+    }
+
+    /// Index of the vertex `name`
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.indices.get(name.to_string())
+    }
+
+    /// Name of the vertex `v`
+    pub fn name_of(&self, v: usize) -> &str {
+        &self.names[v]
+    }
+
+    /// The underlying graph, with vertices numbered in the order the names were first seen
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Shortest chains of connections from a source name to the other names of a symbol graph.
+///
+/// In the movies graph, performers are connected through the movies they played in, so a chain
+/// between two performers alternates performers and movies and the Kevin Bacon number of a
+/// performer is half the length of the chain from Kevin Bacon.
+pub struct DegreesOfSeparation<'a> {
+    sg: &'a SymbolGraph,
+    paths: BreadthFirstPaths,
+}
+
+impl<'a> DegreesOfSeparation<'a> {
+    /// Search from `source`, or return `None` if it is not in the graph
+    pub fn new(sg: &'a SymbolGraph, source: &str) -> Option<Self> {
+        let s = sg.index_of(source)?;
+        Some(Self {
+            sg,
+            paths: BreadthFirstPaths::new(sg.graph(), s),
+        })
+    }
+
+    /// Number of edges on a shortest chain from the source to `name`, or `None` if `name` is not
+    /// connected to the source
+    pub fn degrees(&self, name: &str) -> Option<usize> {
+        self.paths.dist_to(self.sg.index_of(name)?)
+    }
+
+    /// A shortest chain of names from the source to `name`
+    pub fn path_to(&self, name: &str) -> Option<Vec<&'a str>> {
+        let path = self.paths.path_to(self.sg.index_of(name)?)?;
+        Some(path.into_iter().map(|v| self.sg.name_of(v)).collect())
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use super::{DegreesOfSeparation, SymbolGraph};
+
+    #[test]
+    fn test_routes() {
+        let sg: SymbolGraph = SymbolGraph::from_file("resources/routes.txt", " ").unwrap();
+        assert_eq!(sg.graph().v(), 10);
+        assert_eq!(sg.graph().e(), 18);
+        assert_eq!(sg.index_of("JFK"), Some(0));
+        assert_eq!(sg.name_of(1), "MCO");
+        assert!(sg.contains("LAX"));
+        assert!(!sg.contains("SFO"));
+
+        let jfk = sg.index_of("JFK").unwrap();
+        let mut neighbors: Vec<_> = sg.graph().adj(jfk).iter().map(|&w| sg.name_of(w)).collect();
+        neighbors.sort_unstable();
+        assert_eq!(neighbors, vec!["ATL", "MCO", "ORD"]);
+    }
+
+    #[test]
+    fn test_degrees_of_separation() {
+        let sg = SymbolGraph::from_file("resources/routes.txt", " ").unwrap();
+        let separation = DegreesOfSeparation::new(&sg, "JFK").unwrap();
+        assert_eq!(separation.degrees("JFK"), Some(0));
+        assert_eq!(separation.degrees("HOU"), Some(2));
+        assert_eq!(separation.degrees("LAS"), Some(3));
+        assert_eq!(separation.path_to("LAS").unwrap().len(), 4);
+        assert_eq!(separation.path_to("DEN"), Some(vec!["JFK", "ORD", "DEN"]));
+        assert_eq!(separation.path_to("SFO"), None);
+        assert!(DegreesOfSeparation::new(&sg, "SFO").is_none());
+    }
+
+    #[test]
+    fn test_movies() {
+        let movies = "\
+            Movie A (2000)/Bacon, Kevin/Actor, One\n\
+            \n\
+            Movie B (2001)/Actor, One/Actor, Two\n\
+            Movie C (2002)/Actor, Three\n";
+        let sg: SymbolGraph = SymbolGraph::from_reader(Cursor::new(movies), "/").unwrap();
+        assert_eq!(sg.graph().v(), 7);
+        assert_eq!(sg.graph().e(), 5);
+
+        let separation = DegreesOfSeparation::new(&sg, "Bacon, Kevin").unwrap();
+        assert_eq!(separation.degrees("Actor, Two"), Some(4));
+        assert_eq!(
+            separation.path_to("Actor, Two").unwrap(),
+            vec![
+                "Bacon, Kevin",
+                "Movie A (2000)",
+                "Actor, One",
+                "Movie B (2001)",
+                "Actor, Two"
+            ]
+        );
+        assert_eq!(separation.degrees("Actor, Three"), None);
+    }
+}
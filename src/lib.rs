@@ -1 +1,9 @@
+pub mod compression;
+pub mod fundamentals;
+pub mod geometry;
+pub mod graphs;
+pub mod priorityqueues;
+pub mod sorting;
+pub mod strings;
 pub mod symboltables;
+pub mod unionfind;
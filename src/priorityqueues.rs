@@ -0,0 +1,9 @@
+//! Priority Queues
+//!
+//! Collections that support inserting values and removing the largest (or smallest) one.
+pub mod binaryheap;
+pub mod binomial;
+pub mod fibonacci;
+pub mod indexminpq;
+pub mod mergeable;
+pub mod pairing;
@@ -0,0 +1,335 @@
+//! Binary Heap
+//!
+//! Priority queues implemented as a binary heap in an array: the children of the node at index
+//! `k` are at `2k + 1` and `2k + 2`, and no child is larger (for `MaxPQ`) or smaller (for `MinPQ`)
+//! than its parent.
+use std::{
+    cmp::{Ordering, Reverse},
+    fmt::Debug,
+};
+
+use crate::symboltables::item::Item;
+
+const INITIAL_CAPACITY: usize = 1;
+
+/// Max priority queue: supports inserting values and removing the largest one.
+///
+/// `insert` and `del_max` take logarithmic time, `peek` constant time. The array doubles when it
+/// is full and is halved when it is one quarter full.
+#[derive(Clone, Debug)]
+pub struct MaxPQ<T> {
+    pq: Vec<T>,
+}
+
+impl<T: Ord> Default for MaxPQ<T> {
+    fn default() -> Self {
+        Self::with_capacity(INITIAL_CAPACITY)
+    }
+}
+
+impl<T: Ord> MaxPQ<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty queue with room for `capacity` values
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            pq: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Create a queue holding the given values.
+    ///
+    /// The heap is built bottom-up by sinking every parent node, which takes linear time.
+    pub fn from_vec(values: Vec<T>) -> Self {
+        let mut pq = Self { pq: values };
+        let n = pq.pq.len();
+        for k in (0..n / 2).rev() {
+            pq.sink(k, n);
+        }
+        pq
+    }
+
+    /// Number of values in the queue
+    pub fn len(&self) -> usize {
+        self.pq.len()
+    }
+
+    /// Is the queue empty or not?
+    pub fn is_empty(&self) -> bool {
+        self.pq.is_empty()
+    }
+
+    /// Add a value to the queue
+    pub fn insert(&mut self, value: T) {
+        if self.pq.len() == self.pq.capacity() {
+            self.pq.reserve_exact(self.pq.capacity().max(1));
+        }
+        self.pq.push(value);
+        self.swim(self.pq.len() - 1);
+    }
+
+    /// Return the largest value, without removing it
+    pub fn peek(&self) -> Option<&T> {
+        self.pq.first()
+    }
+
+    /// Remove and return the largest value
+    pub fn del_max(&mut self) -> Option<T> {
+        if self.pq.is_empty() {
+            return None;
+        }
+        let last = self.pq.len() - 1;
+        self.pq.swap(0, last);
+        let max = self.pq.pop();
+        self.sink(0, last);
+        if !self.pq.is_empty() && self.pq.len() <= self.pq.capacity() / 4 {
+            self.pq.shrink_to(self.pq.capacity() / 2);
+        }
+        max
+    }
+
+    /// Iterate over the values, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.pq.iter()
+    }
+
+    /// Heapsort: repeatedly move the largest value to the end of the heap. Returns the values in
+    /// ascending order.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut n = self.pq.len();
+        while n > 1 {
+            n -= 1;
+            self.pq.swap(0, n);
+            self.sink(0, n);
+        }
+        self.pq
+    }
+
+    /// Check the heap invariant: no value is larger than its parent
+    pub fn is_max_heap(&self) -> bool {
+        (1..self.pq.len()).all(|k| self.pq[k] <= self.pq[(k - 1) / 2])
+    }
+
+    // Move the value at `k` up the heap while it is larger than its parent
+    fn swim(&mut self, mut k: usize) {
+        while k > 0 && self.less((k - 1) / 2, k) {
+            self.pq.swap((k - 1) / 2, k);
+            k = (k - 1) / 2;
+        }
+    }
+
+    // Move the value at `k` down the heap `pq[..n]` while it is smaller than one of its children
+    fn sink(&mut self, mut k: usize, n: usize) {
+        while 2 * k + 1 < n {
+            let mut j = 2 * k + 1;
+            if j + 1 < n && self.less(j, j + 1) {
+                j += 1;
+            }
+            if !self.less(k, j) {
+                break;
+            }
+            self.pq.swap(k, j);
+            k = j;
+        }
+    }
+
+    fn less(&self, i: usize, j: usize) -> bool {
+        self.pq[i] < self.pq[j]
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Min priority queue: supports inserting values and removing the smallest one.
+///
+/// A `MaxPQ` on the reversed order of the values.
+#[derive(Clone, Debug)]
+pub struct MinPQ<T> {
+    pq: MaxPQ<Reverse<T>>,
+}
+
+impl<T: Ord> Default for MinPQ<T> {
+    fn default() -> Self {
+        Self {
+            pq: MaxPQ::default(),
+        }
+    }
+}
+
+impl<T: Ord> MinPQ<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty queue with room for `capacity` values
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            pq: MaxPQ::with_capacity(capacity),
+        }
+    }
+
+    /// Create a queue holding the given values, in linear time
+    pub fn from_vec(values: Vec<T>) -> Self {
+        Self {
+            pq: MaxPQ::from_vec(values.into_iter().map(Reverse).collect()),
+        }
+    }
+
+    /// Number of values in the queue
+    pub fn len(&self) -> usize {
+        self.pq.len()
+    }
+
+    /// Is the queue empty or not?
+    pub fn is_empty(&self) -> bool {
+        self.pq.is_empty()
+    }
+
+    /// Add a value to the queue
+    pub fn insert(&mut self, value: T) {
+        self.pq.insert(Reverse(value));
+    }
+
+    /// Return the smallest value, without removing it
+    pub fn peek(&self) -> Option<&T> {
+        self.pq.peek().map(|value| &value.0)
+    }
+
+    /// Remove and return the smallest value
+    pub fn del_min(&mut self) -> Option<T> {
+        self.pq.del_max().map(|value| value.0)
+    }
+
+    /// Iterate over the values, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.pq.iter().map(|value| &value.0)
+    }
+
+    /// Heapsort. Returns the values in ascending order.
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        let mut values: Vec<T> = self
+            .pq
+            .into_sorted_vec()
+            .into_iter()
+            .map(|value| value.0)
+            .collect();
+        values.reverse();
+        values
+    }
+
+    /// Check the heap invariant: no value is smaller than its parent
+    pub fn is_min_heap(&self) -> bool {
+        self.pq.is_max_heap()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Orders items by their key, so that they can be kept in a priority queue
+#[derive(Clone, Debug)]
+pub struct ByKey<I: Item>(pub I);
+
+impl<I: Item> PartialEq for ByKey<I> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.key() == other.0.key()
+    }
+}
+
+impl<I: Item> Eq for ByKey<I> {}
+
+impl<I: Item> PartialOrd for ByKey<I> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<I: Item> Ord for ByKey<I> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.key().cmp(&other.0.key())
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::{
+        sorting::test::random_items,
+        symboltables::item::{DoubleItem, Item},
+    };
+
+    use super::{ByKey, MaxPQ, MinPQ};
+
+    #[test]
+    fn test_max_pq() {
+        // P Q E - X A M - P L E -
+        let mut pq = MaxPQ::new();
+        let mut removed = String::new();
+        for s in "P Q E - X A M - P L E -".split(' ') {
+            if s == "-" {
+                removed.push(pq.del_max().unwrap());
+            } else {
+                pq.insert(s.chars().next().unwrap());
+            }
+            assert!(pq.is_max_heap());
+        }
+        assert_eq!(removed, "QXP");
+        assert_eq!(pq.len(), 6);
+        assert_eq!(pq.peek(), Some(&'P'));
+        assert_eq!(pq.into_sorted_vec(), vec!['A', 'E', 'E', 'L', 'M', 'P']);
+    }
+
+    #[test]
+    fn test_resize() {
+        let mut pq = MaxPQ::new();
+        for i in 0..1000 {
+            pq.insert(i);
+        }
+        assert!(pq.pq.capacity() >= 1000);
+        for i in (0..1000).rev() {
+            assert_eq!(pq.del_max(), Some(i));
+        }
+        assert!(pq.pq.capacity() < 1000);
+        assert_eq!(pq.del_max(), None);
+        assert!(pq.is_empty());
+    }
+
+    #[test]
+    fn test_from_vec() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let values: Vec<u32> = (0..500).map(|_| rng.gen_range(0..100)).collect();
+        let pq = MaxPQ::from_vec(values.clone());
+        assert!(pq.is_max_heap());
+
+        let mut expected = values.clone();
+        expected.sort_unstable();
+        assert_eq!(pq.into_sorted_vec(), expected);
+
+        let pq = MinPQ::from_vec(values);
+        assert!(pq.is_min_heap());
+        assert_eq!(pq.peek(), expected.first());
+        assert_eq!(pq.into_sorted_vec(), expected);
+    }
+
+    #[test]
+    fn test_min_pq_items() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let items = random_items(&mut rng, 200);
+        let mut keys: Vec<_> = items.iter().map(|item| item.key()).collect();
+        keys.sort_unstable();
+
+        let mut pq: MinPQ<ByKey<DoubleItem>> = MinPQ::new();
+        for item in items {
+            pq.insert(ByKey(item));
+        }
+        assert_eq!(pq.len(), 200);
+        for key in keys {
+            assert_eq!(pq.del_min().unwrap().0.key(), key);
+        }
+        assert!(pq.del_min().is_none());
+    }
+}
@@ -0,0 +1,248 @@
+//! Binomial Queue
+use std::{
+    mem,
+    rc::{Rc, Weak},
+};
+
+use super::mergeable::{
+    drop_trees, into_key, link, take_children, Handle, HeapStatistics, Link, Node, PriorityQueue,
+};
+
+/// Binomial queue: a forest of heap-ordered binomial trees, at most one of each size.
+///
+/// The tree at index `i` of the forest has 2^i nodes, so the forest mirrors the binary
+/// representation of the number of keys. Two queues are joined by adding their trees in the
+/// same way as binary numbers are added, linking two trees of equal size into a carry.
+///
+/// `insert`, `del_min`, `decrease_key` and `meld` all take logarithmic time.
+/// See: Algorithms in C++, 3rd edition, Robert Sedgewick, Addison-Wesley, 1998, section 9.7.
+pub struct BinomialQueue<K> {
+    trees: Vec<Option<Link<K>>>,
+    n: usize,
+    statistics: HeapStatistics,
+}
+
+impl<K> Default for BinomialQueue<K> {
+    fn default() -> Self {
+        Self {
+            trees: vec![],
+            n: 0,
+            statistics: HeapStatistics::default(),
+        }
+    }
+}
+
+impl<K: Ord> BinomialQueue<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of nodes of the trees in the forest, by size
+    pub fn tree_sizes(&self) -> Vec<usize> {
+        (0..self.trees.len())
+            .filter(|&i| self.trees[i].is_some())
+            .map(|i| 1 << i)
+            .collect()
+    }
+
+    // Add a tree with 2^order nodes to the forest, carrying it over into larger trees while
+    // there is already a tree of the same size.
+    fn add_tree(&mut self, mut tree: Link<K>, mut order: usize) {
+        loop {
+            if order == self.trees.len() {
+                self.trees.push(None);
+            }
+            match self.trees[order].take() {
+                None => {
+                    tree.borrow_mut().position = order;
+                    self.trees[order] = Some(tree);
+                    return;
+                }
+                Some(other) => {
+                    tree = link(other, tree, &mut self.statistics);
+                    order += 1;
+                }
+            }
+        }
+    }
+
+    // Index of the tree with the smallest root
+    fn min_order(&self) -> Option<usize> {
+        let mut min: Option<usize> = None;
+        for (i, tree) in self.trees.iter().enumerate() {
+            if let Some(tree) = tree {
+                let smaller = match min {
+                    None => true,
+                    Some(m) => tree.borrow().key < self.trees[m].as_ref().unwrap().borrow().key,
+                };
+                if smaller {
+                    min = Some(i);
+                }
+            }
+        }
+        min
+    }
+
+    // Exchange a node with its parent, moving the subtrees along so that every node keeps the
+    // same number of children in the same positions.
+    fn exchange_with_parent(&mut self, node: &Link<K>, parent: &Link<K>) {
+        self.statistics.exchange();
+        let grandparent = parent.borrow().parent.upgrade();
+        let node_position = node.borrow().position;
+        let parent_position = parent.borrow().position;
+
+        let node_children = mem::take(&mut node.borrow_mut().children);
+        let mut parent_children = mem::take(&mut parent.borrow_mut().children);
+        parent_children[node_position] = parent.clone();
+        for child in &parent_children {
+            child.borrow_mut().parent = Rc::downgrade(node);
+        }
+        for child in &node_children {
+            child.borrow_mut().parent = Rc::downgrade(parent);
+        }
+        node.borrow_mut().children = parent_children;
+        parent.borrow_mut().children = node_children;
+        parent.borrow_mut().position = node_position;
+        node.borrow_mut().position = parent_position;
+
+        match grandparent {
+            Some(grandparent) => {
+                node.borrow_mut().parent = Rc::downgrade(&grandparent);
+                grandparent.borrow_mut().children[parent_position] = node.clone();
+            }
+            None => {
+                node.borrow_mut().parent = Weak::new();
+                self.trees[parent_position] = Some(node.clone());
+            }
+        }
+    }
+}
+
+impl<K: Ord + Clone> PriorityQueue<K> for BinomialQueue<K> {
+    fn insert(&mut self, key: K) -> Handle<K> {
+        self.statistics.operation();
+        let node = Node::new(key);
+        let handle = Handle::new(&node);
+        self.add_tree(node, 0);
+        self.n += 1;
+        handle
+    }
+
+    fn peek(&self) -> Option<K> {
+        let min = self.min_order()?;
+        self.trees[min]
+            .as_ref()
+            .map(|tree| tree.borrow().key.clone())
+    }
+
+    // The children of a root of order k are binomial trees of orders 0 to k - 1, which are
+    // joined with the rest of the forest.
+    fn del_min(&mut self) -> Option<K> {
+        self.statistics.operation();
+        let min = self.min_order()?;
+        for _ in self.trees.iter().flatten() {
+            self.statistics.compare();
+        }
+        let root = self.trees[min].take()?;
+        for (order, child) in take_children(&root).into_iter().enumerate() {
+            self.add_tree(child, order);
+        }
+        while let Some(None) = self.trees.last() {
+            self.trees.pop();
+        }
+        self.n -= 1;
+        Some(into_key(root))
+    }
+
+    // The node is exchanged with its parent until the heap order is restored
+    fn decrease_key(&mut self, handle: &Handle<K>, key: K) {
+        self.statistics.operation();
+        let node = handle.node();
+        assert!(
+            key < node.borrow().key,
+            "decrease_key() would not decrease the key"
+        );
+        node.borrow_mut().key = key;
+        loop {
+            let Some(parent) = node.borrow().parent.upgrade() else {
+                break;
+            };
+            self.statistics.compare();
+            if node.borrow().key >= parent.borrow().key {
+                break;
+            }
+            self.exchange_with_parent(&node, &parent);
+        }
+    }
+
+    fn meld(&mut self, mut other: Self) {
+        self.statistics.operation();
+        for (order, tree) in mem::take(&mut other.trees).into_iter().enumerate() {
+            if let Some(tree) = tree {
+                self.add_tree(tree, order);
+            }
+        }
+        self.n += other.n;
+        self.statistics += other.statistics;
+    }
+
+    fn len(&self) -> usize {
+        self.n
+    }
+
+    fn statistics(&self) -> HeapStatistics {
+        self.statistics
+    }
+}
+
+impl<K> Drop for BinomialQueue<K> {
+    fn drop(&mut self) {
+        drop_trees(mem::take(&mut self.trees).into_iter().flatten());
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use crate::priorityqueues::mergeable::{test::check_queue, PriorityQueue};
+
+    use super::BinomialQueue;
+
+    #[test]
+    fn test_binomial_queue() {
+        check_queue::<BinomialQueue<u32>>();
+    }
+
+    #[test]
+    fn test_tree_sizes() {
+        // 13 = 1101 in binary
+        let mut pq = BinomialQueue::new();
+        for key in 0..13 {
+            pq.insert(key);
+        }
+        assert_eq!(pq.tree_sizes(), vec![1, 4, 8]);
+
+        pq.del_min();
+        assert_eq!(pq.tree_sizes(), vec![4, 8]);
+        assert_eq!(pq.peek(), Some(1));
+    }
+
+    #[test]
+    fn test_decrease_key() {
+        let mut pq = BinomialQueue::new();
+        let handles: Vec<_> = (0..16).map(|key| pq.insert(key * 10)).collect();
+        assert_eq!(pq.tree_sizes(), vec![16]);
+
+        // the largest key is at the bottom of the single tree, 4 levels deep
+        pq.decrease_key(&handles[15], 5);
+        assert_eq!(pq.statistics().exchanges(), 3);
+        // a child of the root with a key that is still larger than the root stays in place
+        pq.decrease_key(&handles[15], 1);
+        assert_eq!(pq.statistics().exchanges(), 3);
+        assert_eq!(pq.peek(), Some(0));
+        assert_eq!(pq.del_min(), Some(0));
+        assert_eq!(pq.del_min(), Some(1));
+        assert!(!handles[15].is_valid());
+    }
+}
@@ -0,0 +1,243 @@
+//! Fibonacci Heap
+use std::mem;
+
+use super::mergeable::{
+    cut, drop_trees, into_key, link, take_children, Handle, HeapStatistics, Link, Node,
+    PriorityQueue,
+};
+
+/// Fibonacci heap: a collection of heap-ordered trees that is only tidied up by `del_min`.
+///
+/// `insert` and `meld` just add trees to the root list. `del_min` moves the children of the
+/// smallest root to the root list and then consolidates it, linking trees whose roots have the
+/// same number of children until all the degrees differ. `decrease_key` cuts the node from its
+/// parent, and a parent that loses a second child is cut as well (cascading cut), which keeps the
+/// degrees logarithmic.
+///
+/// `insert`, `meld` and `decrease_key` take constant amortized time and `del_min` O(log N).
+/// See: Introduction to Algorithms, 3rd edition, Cormen, Leiserson, Rivest, Stein, chapter 19.
+pub struct FibonacciHeap<K> {
+    roots: Vec<Link<K>>,
+    min: Option<usize>, // index of the smallest root
+    n: usize,
+    statistics: HeapStatistics,
+}
+
+impl<K> Default for FibonacciHeap<K> {
+    fn default() -> Self {
+        Self {
+            roots: vec![],
+            min: None,
+            n: 0,
+            statistics: HeapStatistics::default(),
+        }
+    }
+}
+
+impl<K: Ord> FibonacciHeap<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of trees in the root list
+    pub fn tree_count(&self) -> usize {
+        self.roots.len()
+    }
+
+    // Add a tree to the root list, keeping track of the smallest root
+    fn add_root(&mut self, tree: Link<K>) {
+        {
+            let mut root = tree.borrow_mut();
+            root.position = self.roots.len();
+            root.marked = false;
+        }
+        self.roots.push(tree);
+        self.update_min(self.roots.len() - 1);
+    }
+
+    fn update_min(&mut self, i: usize) {
+        let smaller = match self.min {
+            None => true,
+            Some(min) => {
+                self.statistics.compare();
+                self.roots[i].borrow().key < self.roots[min].borrow().key
+            }
+        };
+        if smaller {
+            self.min = Some(i);
+        }
+    }
+
+    // Link roots of the same degree until every root has a different degree
+    fn consolidate(&mut self) {
+        let mut by_degree: Vec<Option<Link<K>>> = vec![];
+        for mut tree in mem::take(&mut self.roots) {
+            loop {
+                let degree = tree.borrow().children.len();
+                if degree >= by_degree.len() {
+                    by_degree.resize(degree + 1, None);
+                }
+                match by_degree[degree].take() {
+                    None => {
+                        by_degree[degree] = Some(tree);
+                        break;
+                    }
+                    Some(other) => tree = link(other, tree, &mut self.statistics),
+                }
+            }
+        }
+        self.min = None;
+        for tree in by_degree.into_iter().flatten() {
+            self.add_root(tree);
+        }
+    }
+
+    // Cut the node from its parent and move it to the root list. The parent is marked when it
+    // loses its first child, and cut as well when it loses a second one.
+    fn cascading_cut(&mut self, node: Link<K>) {
+        let mut node = node;
+        while let Some(parent) = cut(&node, &mut self.statistics) {
+            self.add_root(node);
+            if parent.borrow().parent.upgrade().is_none() {
+                break;
+            }
+            if !parent.borrow().marked {
+                parent.borrow_mut().marked = true;
+                break;
+            }
+            node = parent;
+        }
+    }
+}
+
+impl<K: Ord + Clone> PriorityQueue<K> for FibonacciHeap<K> {
+    fn insert(&mut self, key: K) -> Handle<K> {
+        self.statistics.operation();
+        let node = Node::new(key);
+        let handle = Handle::new(&node);
+        self.add_root(node);
+        self.n += 1;
+        handle
+    }
+
+    fn peek(&self) -> Option<K> {
+        self.min.map(|min| self.roots[min].borrow().key.clone())
+    }
+
+    fn del_min(&mut self) -> Option<K> {
+        self.statistics.operation();
+        let min = self.min?;
+        let root = self.roots.swap_remove(min);
+        for child in take_children(&root) {
+            self.roots.push(child);
+        }
+        self.consolidate();
+        self.n -= 1;
+        Some(into_key(root))
+    }
+
+    fn decrease_key(&mut self, handle: &Handle<K>, key: K) {
+        self.statistics.operation();
+        let node = handle.node();
+        assert!(
+            key < node.borrow().key,
+            "decrease_key() would not decrease the key"
+        );
+        node.borrow_mut().key = key;
+
+        let parent = node.borrow().parent.upgrade();
+        match parent {
+            None => {
+                let position = node.borrow().position;
+                self.update_min(position);
+            }
+            Some(parent) => {
+                self.statistics.compare();
+                if node.borrow().key < parent.borrow().key {
+                    self.cascading_cut(node);
+                }
+            }
+        }
+    }
+
+    fn meld(&mut self, mut other: Self) {
+        self.statistics.operation();
+        for tree in mem::take(&mut other.roots) {
+            self.add_root(tree);
+        }
+        self.n += other.n;
+        self.statistics += other.statistics;
+    }
+
+    fn len(&self) -> usize {
+        self.n
+    }
+
+    fn statistics(&self) -> HeapStatistics {
+        self.statistics
+    }
+}
+
+impl<K> Drop for FibonacciHeap<K> {
+    fn drop(&mut self) {
+        drop_trees(mem::take(&mut self.roots));
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use crate::priorityqueues::mergeable::{test::check_queue, PriorityQueue};
+
+    use super::FibonacciHeap;
+
+    #[test]
+    fn test_fibonacci_heap() {
+        check_queue::<FibonacciHeap<u32>>();
+    }
+
+    #[test]
+    fn test_consolidate() {
+        let mut pq = FibonacciHeap::new();
+        for key in 0..9 {
+            pq.insert(key);
+        }
+        assert_eq!(pq.tree_count(), 9);
+        assert_eq!(pq.statistics().links(), 0);
+
+        // 8 keys are left, linked into a single tree of degree 3
+        assert_eq!(pq.del_min(), Some(0));
+        assert_eq!(pq.tree_count(), 1);
+        assert_eq!(pq.statistics().links(), 7);
+    }
+
+    #[test]
+    fn test_cascading_cut() {
+        let mut pq = FibonacciHeap::new();
+        let handles: Vec<_> = (0..17).map(|key| pq.insert(key * 10)).collect();
+        pq.del_min();
+        assert_eq!(pq.tree_count(), 1);
+
+        // a child of the root is just cut
+        pq.decrease_key(&handles[16], 1);
+        assert_eq!(pq.tree_count(), 2);
+        assert_eq!(pq.statistics().cuts(), 1);
+
+        // 150 is a child of 140, which is a child of 120, which is a child of 80: cutting 150 and
+        // 140 marks their parents, and cutting 130 (another child of 120) cuts 120 as well
+        pq.decrease_key(&handles[15], 2);
+        pq.decrease_key(&handles[14], 3);
+        assert_eq!(pq.statistics().cuts(), 3);
+        pq.decrease_key(&handles[13], 4);
+        assert_eq!(pq.statistics().cuts(), 5);
+        assert_eq!(pq.tree_count(), 6);
+        assert_eq!(pq.peek(), Some(1));
+
+        let mut previous = 0;
+        while let Some(key) = pq.del_min() {
+            assert!(key >= previous);
+            previous = key;
+        }
+    }
+}
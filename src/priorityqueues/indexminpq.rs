@@ -0,0 +1,288 @@
+//! Indexed Priority Queue
+use super::binaryheap::MinPQ;
+
+/// Indexed min priority queue.
+///
+/// Every key in the queue is associated with an index between 0 and `max_n - 1`, the same way as
+/// keys are used as indices in a `KeyIndexedSymbolTable`. The index is used to refer to the key
+/// when it has to be changed or deleted, which is what Dijkstra's and Prim's algorithms need.
+///
+/// The indices are kept in a binary heap ordered by their keys, together with the inverse array
+/// that gives the position of each index in the heap. `insert`, `del_min`, `delete` and all the
+/// key changes take logarithmic time.
+#[derive(Clone, Debug)]
+pub struct IndexMinPQ<K> {
+    pq: Vec<usize>,         // binary heap of indices
+    qp: Vec<Option<usize>>, // position of each index in the heap: pq[qp[i]] = i
+    keys: Vec<Option<K>>,   // key of each index
+}
+
+impl<K: Ord> IndexMinPQ<K> {
+    /// Create an empty queue for the indices `0..max_n`
+    pub fn new(max_n: usize) -> Self {
+        let mut keys = Vec::with_capacity(max_n);
+        keys.resize_with(max_n, || None);
+        Self {
+            pq: Vec::with_capacity(max_n),
+            qp: vec![None; max_n],
+            keys,
+        }
+    }
+
+    /// Number of keys in the queue
+    pub fn len(&self) -> usize {
+        self.pq.len()
+    }
+
+    /// Is the queue empty or not?
+    pub fn is_empty(&self) -> bool {
+        self.pq.is_empty()
+    }
+
+    /// Is there a key associated with index `i`?
+    pub fn contains(&self, i: usize) -> bool {
+        self.qp[i].is_some()
+    }
+
+    /// Associate `key` with index `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is out of range or already in the queue.
+    pub fn insert(&mut self, i: usize, key: K) {
+        assert!(!self.contains(i), "index {} is already in the queue", i);
+        self.qp[i] = Some(self.pq.len());
+        self.pq.push(i);
+        self.keys[i] = Some(key);
+        self.swim(self.pq.len() - 1);
+    }
+
+    /// Index associated with the smallest key
+    pub fn min_index(&self) -> Option<usize> {
+        self.pq.first().copied()
+    }
+
+    /// The smallest key
+    pub fn min_key(&self) -> Option<&K> {
+        self.pq.first().and_then(|&i| self.keys[i].as_ref())
+    }
+
+    /// The key associated with index `i`
+    pub fn key_of(&self, i: usize) -> Option<&K> {
+        self.keys[i].as_ref()
+    }
+
+    /// Remove the smallest key and return its index
+    pub fn del_min(&mut self) -> Option<usize> {
+        let i = self.min_index()?;
+        self.delete(i);
+        Some(i)
+    }
+
+    /// Remove the key associated with index `i` and return it
+    pub fn delete(&mut self, i: usize) -> Option<K> {
+        let k = self.qp[i]?;
+        let last = self.pq.len() - 1;
+        self.exch(k, last);
+        self.pq.pop();
+        if k < last {
+            self.swim(k);
+            self.sink(k);
+        }
+        self.qp[i] = None;
+        self.keys[i].take()
+    }
+
+    /// Change the key associated with index `i`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not in the queue.
+    pub fn change_key(&mut self, i: usize, key: K) {
+        let k = self.position(i);
+        self.keys[i] = Some(key);
+        self.swim(k);
+        self.sink(self.position(i));
+    }
+
+    /// Decrease the key associated with index `i`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not in the queue or if `key` is not smaller than its current key.
+    pub fn decrease_key(&mut self, i: usize, key: K) {
+        let k = self.position(i);
+        assert!(
+            Some(&key) < self.keys[i].as_ref(),
+            "decrease_key() would not decrease the key"
+        );
+        self.keys[i] = Some(key);
+        self.swim(k);
+    }
+
+    /// Increase the key associated with index `i`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not in the queue or if `key` is not larger than its current key.
+    pub fn increase_key(&mut self, i: usize, key: K) {
+        let k = self.position(i);
+        assert!(
+            Some(&key) > self.keys[i].as_ref(),
+            "increase_key() would not increase the key"
+        );
+        self.keys[i] = Some(key);
+        self.sink(k);
+    }
+
+    /// Iterate over the indices and their keys in ascending order of the keys, without changing
+    /// the queue.
+    pub fn iter(&self) -> Iter<'_, K> {
+        let mut heap = MinPQ::new();
+        if let Some(&i) = self.pq.first() {
+            heap.insert((self.key(i), 0));
+        }
+        Iter { pq: self, heap }
+    }
+
+    fn position(&self, i: usize) -> usize {
+        self.qp[i].unwrap_or_else(|| panic!("index {} is not in the queue", i))
+    }
+
+    fn key(&self, i: usize) -> &K {
+        self.keys[i].as_ref().expect("index in the heap has a key")
+    }
+
+    fn greater(&self, a: usize, b: usize) -> bool {
+        self.key(self.pq[a]) > self.key(self.pq[b])
+    }
+
+    fn exch(&mut self, a: usize, b: usize) {
+        self.pq.swap(a, b);
+        self.qp[self.pq[a]] = Some(a);
+        self.qp[self.pq[b]] = Some(b);
+    }
+
+    fn swim(&mut self, mut k: usize) {
+        while k > 0 && self.greater((k - 1) / 2, k) {
+            self.exch((k - 1) / 2, k);
+            k = (k - 1) / 2;
+        }
+    }
+
+    fn sink(&mut self, mut k: usize) {
+        let n = self.pq.len();
+        while 2 * k + 1 < n {
+            let mut j = 2 * k + 1;
+            if j + 1 < n && self.greater(j, j + 1) {
+                j += 1;
+            }
+            if !self.greater(k, j) {
+                break;
+            }
+            self.exch(k, j);
+            k = j;
+        }
+    }
+}
+
+/// Iterator over the indices of an `IndexMinPQ` in order of their keys.
+///
+/// The heap positions still to be visited are kept in a second priority queue: after a position
+/// is visited its children are added, so only the frontier of the heap is held.
+pub struct Iter<'a, K> {
+    pq: &'a IndexMinPQ<K>,
+    heap: MinPQ<(&'a K, usize)>,
+}
+
+impl<'a, K: Ord> Iterator for Iter<'a, K> {
+    type Item = (usize, &'a K);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, k) = self.heap.del_min()?;
+        for child in [2 * k + 1, 2 * k + 2] {
+            if child < self.pq.pq.len() {
+                self.heap.insert((self.pq.key(self.pq.pq[child]), child));
+            }
+        }
+        Some((self.pq.pq[k], key))
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::IndexMinPQ;
+
+    const WORDS: [&str; 10] = [
+        "it", "was", "the", "best", "of", "times", "it", "was", "the", "worst",
+    ];
+
+    fn queue() -> IndexMinPQ<&'static str> {
+        let mut pq = IndexMinPQ::new(WORDS.len());
+        for (i, s) in WORDS.into_iter().enumerate() {
+            pq.insert(i, s);
+        }
+        pq
+    }
+
+    #[test]
+    fn test_del_min() {
+        let mut pq = queue();
+        assert_eq!(pq.len(), 10);
+        assert_eq!(pq.min_key(), Some(&"best"));
+
+        let mut keys = vec![];
+        while let Some(i) = pq.del_min() {
+            keys.push(WORDS[i]);
+        }
+        assert_eq!(
+            keys,
+            vec!["best", "it", "it", "of", "the", "the", "times", "was", "was", "worst"]
+        );
+        assert!(pq.is_empty());
+    }
+
+    #[test]
+    fn test_change_keys() {
+        let mut pq = queue();
+        pq.decrease_key(9, "a");
+        assert_eq!(pq.min_index(), Some(9));
+        pq.increase_key(9, "zzz");
+        assert_eq!(pq.min_index(), Some(3));
+        pq.change_key(3, "zz");
+        assert_eq!(pq.key_of(3), Some(&"zz"));
+        assert_eq!(pq.iter().last(), Some((9, &"zzz")));
+
+        assert_eq!(pq.delete(0), Some("it"));
+        assert!(!pq.contains(0));
+        assert_eq!(pq.delete(0), None);
+        assert_eq!(pq.min_index(), Some(6));
+        assert_eq!(pq.len(), 9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_decrease_key_larger() {
+        queue().decrease_key(3, "zebra");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_insert_twice() {
+        queue().insert(3, "again");
+    }
+
+    #[test]
+    fn test_iter() {
+        let pq = queue();
+        let keys: Vec<_> = pq.iter().map(|(_, &key)| key).collect();
+        assert_eq!(
+            keys,
+            vec!["best", "it", "it", "of", "the", "the", "times", "was", "was", "worst"]
+        );
+        assert_eq!(pq.len(), 10);
+        assert!(pq.iter().all(|(i, key)| pq.key_of(i) == Some(key)));
+    }
+}
@@ -0,0 +1,338 @@
+//! Mergeable Heaps
+//!
+//! The interface shared by the heap-ordered forests (binomial queue, pairing heap and Fibonacci
+//! heap) and the building blocks they have in common. The trees are made of reference counted
+//! nodes with weak links to their parents, so that a `Handle` to a node stays valid while the node
+//! moves around in the forest, or into another queue by `meld`.
+use std::{
+    cell::RefCell,
+    fmt::{self, Display},
+    mem,
+    ops::AddAssign,
+    rc::{Rc, Weak},
+};
+
+pub(super) type Link<K> = Rc<RefCell<Node<K>>>;
+
+pub(super) struct Node<K> {
+    pub(super) key: K,
+    pub(super) parent: Weak<RefCell<Node<K>>>,
+    pub(super) children: Vec<Link<K>>,
+    pub(super) position: usize, // index in the children of the parent, or in the roots
+    pub(super) marked: bool,    // lost a child since it became a child itself (Fibonacci heap)
+}
+
+impl<K> Node<K> {
+    pub(super) fn new(key: K) -> Link<K> {
+        Rc::new(RefCell::new(Self {
+            key,
+            parent: Weak::new(),
+            children: vec![],
+            position: 0,
+            marked: false,
+        }))
+    }
+}
+
+/// Refers to a key in a mergeable heap, so that the key can be decreased later.
+///
+/// The handle does not keep the key alive: once the key is removed the handle is no longer valid.
+pub struct Handle<K>(Weak<RefCell<Node<K>>>);
+
+impl<K> Clone for Handle<K> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<K> Handle<K> {
+    pub(super) fn new(node: &Link<K>) -> Self {
+        Self(Rc::downgrade(node))
+    }
+
+    /// Is the key still in the queue?
+    pub fn is_valid(&self) -> bool {
+        self.0.strong_count() > 0
+    }
+
+    pub(super) fn node(&self) -> Link<K> {
+        self.0
+            .upgrade()
+            .expect("handle to a key that is no longer in the queue")
+    }
+}
+
+impl<K: Clone> Handle<K> {
+    /// The current key, if it is still in the queue
+    pub fn key(&self) -> Option<K> {
+        self.0.upgrade().map(|node| node.borrow().key.clone())
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Counts of the operations done on a heap and of the elementary steps they took, to compare the
+/// amortized costs of the heaps.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HeapStatistics {
+    operations: usize,
+    compares: usize,
+    links: usize,
+    cuts: usize,
+    exchanges: usize,
+}
+
+impl HeapStatistics {
+    /// Number of operations (inserts, deletes, key decreases and melds)
+    pub fn operations(&self) -> usize {
+        self.operations
+    }
+
+    /// Number of key compares
+    pub fn compares(&self) -> usize {
+        self.compares
+    }
+
+    /// Number of times a tree was made the child of another tree
+    pub fn links(&self) -> usize {
+        self.links
+    }
+
+    /// Number of times a tree was cut from its parent
+    pub fn cuts(&self) -> usize {
+        self.cuts
+    }
+
+    /// Number of times a node was exchanged with its parent
+    pub fn exchanges(&self) -> usize {
+        self.exchanges
+    }
+
+    /// Average number of elementary steps (compares, links, cuts and exchanges) per operation
+    pub fn amortized_cost(&self) -> f64 {
+        if self.operations == 0 {
+            return 0.0;
+        }
+        let steps = self.compares + self.links + self.cuts + self.exchanges;
+        steps as f64 / self.operations as f64
+    }
+
+    pub(super) fn operation(&mut self) {
+        self.operations += 1;
+    }
+
+    pub(super) fn compare(&mut self) {
+        self.compares += 1;
+    }
+
+    pub(super) fn exchange(&mut self) {
+        self.exchanges += 1;
+    }
+}
+
+impl AddAssign for HeapStatistics {
+    fn add_assign(&mut self, other: Self) {
+        self.operations += other.operations;
+        self.compares += other.compares;
+        self.links += other.links;
+        self.cuts += other.cuts;
+        self.exchanges += other.exchanges;
+    }
+}
+
+impl Display for HeapStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "operations: {}, compares: {}, links: {}, cuts: {}, exchanges: {} (amortized cost {:.2})",
+            self.operations,
+            self.compares,
+            self.links,
+            self.cuts,
+            self.exchanges,
+            self.amortized_cost()
+        )
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Interface of min priority queues that can be merged, and whose keys can be decreased through
+/// the handle returned by `insert`.
+pub trait PriorityQueue<K: Ord + Clone> {
+    /// Add a key to the queue
+    fn insert(&mut self, key: K) -> Handle<K>;
+
+    /// Return the smallest key, without removing it
+    fn peek(&self) -> Option<K>;
+
+    /// Remove and return the smallest key
+    fn del_min(&mut self) -> Option<K>;
+
+    /// Decrease the key the handle refers to.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is no longer in the queue or if `key` is not smaller than it.
+    fn decrease_key(&mut self, handle: &Handle<K>, key: K);
+
+    /// Move all the keys of `other` into this queue. Handles to keys of `other` stay valid.
+    fn meld(&mut self, other: Self)
+    where
+        Self: Sized;
+
+    /// Number of keys in the queue
+    fn len(&self) -> usize;
+
+    /// Is the queue empty or not?
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the collected statistics
+    fn statistics(&self) -> HeapStatistics;
+}
+
+// -------------------------------------------------------------------------------------------------
+
+// Make the tree with the larger root a child of the other one, and return the remaining root
+pub(super) fn link<K: Ord>(a: Link<K>, b: Link<K>, statistics: &mut HeapStatistics) -> Link<K> {
+    statistics.compares += 1;
+    statistics.links += 1;
+    let (root, child) = if b.borrow().key < a.borrow().key {
+        (b, a)
+    } else {
+        (a, b)
+    };
+    add_child(&root, child);
+    root
+}
+
+pub(super) fn add_child<K>(parent: &Link<K>, child: Link<K>) {
+    {
+        let mut node = child.borrow_mut();
+        node.parent = Rc::downgrade(parent);
+        node.position = parent.borrow().children.len();
+        node.marked = false;
+    }
+    parent.borrow_mut().children.push(child);
+}
+
+// Remove the node from the children of its parent, and return the parent
+pub(super) fn cut<K>(node: &Link<K>, statistics: &mut HeapStatistics) -> Option<Link<K>> {
+    let parent = node.borrow().parent.upgrade()?;
+    statistics.cuts += 1;
+    let position = node.borrow().position;
+    {
+        let mut p = parent.borrow_mut();
+        p.children.swap_remove(position);
+        if let Some(moved) = p.children.get(position) {
+            moved.borrow_mut().position = position;
+        }
+    }
+    node.borrow_mut().parent = Weak::new();
+    Some(parent)
+}
+
+// Detach the children of a node that is removed, so that they become roots
+pub(super) fn take_children<K>(node: &Link<K>) -> Vec<Link<K>> {
+    let children = mem::take(&mut node.borrow_mut().children);
+    for child in &children {
+        child.borrow_mut().parent = Weak::new();
+    }
+    children
+}
+
+// The key of a node that is no longer in the heap
+pub(super) fn into_key<K>(node: Link<K>) -> K {
+    match Rc::try_unwrap(node) {
+        Ok(node) => node.into_inner().key,
+        Err(_) => panic!("removed node is still linked"),
+    }
+}
+
+// Drop the trees one node at a time, since dropping a deep tree recursively can overflow the stack
+pub(super) fn drop_trees<K>(trees: impl IntoIterator<Item = Link<K>>) {
+    let mut stack: Vec<Link<K>> = trees.into_iter().collect();
+    while let Some(node) = stack.pop() {
+        stack.append(&mut node.borrow_mut().children);
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+pub(super) mod test {
+    use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+    use super::{HeapStatistics, PriorityQueue};
+
+    /// Exercise a mergeable heap with random keys, key decreases and melds
+    pub fn check_queue<Q: PriorityQueue<u32> + Default>() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut pq = Q::default();
+        assert!(pq.is_empty());
+        assert_eq!(pq.del_min(), None);
+
+        let mut keys: Vec<u32> = (0..500).map(|_| rng.gen_range(1000..100_000)).collect();
+        let mut handles: Vec<_> = keys.iter().map(|&key| pq.insert(key)).collect();
+        assert_eq!(pq.peek(), keys.iter().min().copied());
+
+        // remove some keys so that the trees get some structure
+        let mut expected = keys.clone();
+        expected.sort_unstable();
+        for &key in &expected[..50] {
+            assert_eq!(pq.del_min(), Some(key));
+        }
+        let removed = expected[49];
+        keys.retain(|&key| key > removed);
+        handles.retain(|handle| handle.is_valid());
+        assert_eq!(handles.len(), keys.len());
+
+        // decrease some of the remaining keys
+        let mut order: Vec<usize> = (0..handles.len()).collect();
+        order.shuffle(&mut rng);
+        for &i in &order[..100] {
+            let key = handles[i].key().unwrap();
+            let smaller = rng.gen_range(0..key);
+            pq.decrease_key(&handles[i], smaller);
+            assert_eq!(handles[i].key(), Some(smaller));
+        }
+
+        // meld with a second queue
+        let mut other = Q::default();
+        let other_handle = other.insert(50_000);
+        for _ in 0..200 {
+            other.insert(rng.gen_range(0..100_000));
+        }
+        let n = pq.len() + other.len();
+        pq.meld(other);
+        assert_eq!(pq.len(), n);
+        pq.decrease_key(&other_handle, 0);
+        assert_eq!(pq.peek(), Some(0));
+
+        let mut previous = 0;
+        let mut count = 0;
+        while let Some(key) = pq.del_min() {
+            assert!(key >= previous);
+            previous = key;
+            count += 1;
+        }
+        assert_eq!(count, n);
+        assert!(handles.iter().all(|handle| !handle.is_valid()));
+        assert!(pq.statistics().operations() > 0);
+    }
+
+    #[test]
+    fn test_statistics() {
+        let mut statistics = HeapStatistics::default();
+        statistics.operation();
+        statistics.operation();
+        statistics.compare();
+        statistics.exchange();
+        statistics += statistics;
+        assert_eq!(statistics.operations(), 4);
+        assert_eq!(statistics.amortized_cost(), 1.0);
+        assert!(statistics.to_string().ends_with("(amortized cost 1.00)"));
+    }
+}
@@ -0,0 +1,154 @@
+//! Pairing Heap
+use std::mem;
+
+use super::mergeable::{
+    cut, drop_trees, into_key, link, take_children, Handle, HeapStatistics, Link, Node,
+    PriorityQueue,
+};
+
+/// Pairing heap: a single heap-ordered tree in which a node can have any number of children.
+///
+/// `insert`, `meld` and `decrease_key` link two trees with a single compare. `del_min` removes
+/// the root and combines its children with the two-pass pairing method: adjacent pairs are
+/// linked from left to right, and the resulting trees are linked from right to left. `del_min`
+/// takes O(log N) amortized time.
+pub struct PairingHeap<K> {
+    root: Option<Link<K>>,
+    n: usize,
+    statistics: HeapStatistics,
+}
+
+impl<K> Default for PairingHeap<K> {
+    fn default() -> Self {
+        Self {
+            root: None,
+            n: 0,
+            statistics: HeapStatistics::default(),
+        }
+    }
+}
+
+impl<K: Ord> PairingHeap<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Link the tree with the root of the heap
+    fn add_tree(&mut self, tree: Link<K>) {
+        self.root = Some(match self.root.take() {
+            None => tree,
+            Some(root) => link(root, tree, &mut self.statistics),
+        });
+    }
+}
+
+impl<K: Ord + Clone> PriorityQueue<K> for PairingHeap<K> {
+    fn insert(&mut self, key: K) -> Handle<K> {
+        self.statistics.operation();
+        let node = Node::new(key);
+        let handle = Handle::new(&node);
+        self.add_tree(node);
+        self.n += 1;
+        handle
+    }
+
+    fn peek(&self) -> Option<K> {
+        self.root.as_ref().map(|root| root.borrow().key.clone())
+    }
+
+    fn del_min(&mut self) -> Option<K> {
+        self.statistics.operation();
+        let root = self.root.take()?;
+        let mut pairs = vec![];
+        let mut children = take_children(&root).into_iter();
+        while let Some(a) = children.next() {
+            pairs.push(match children.next() {
+                Some(b) => link(a, b, &mut self.statistics),
+                None => a,
+            });
+        }
+        while let Some(tree) = pairs.pop() {
+            self.add_tree(tree);
+        }
+        self.n -= 1;
+        Some(into_key(root))
+    }
+
+    // The subtree of the node is cut from its parent and linked with the root
+    fn decrease_key(&mut self, handle: &Handle<K>, key: K) {
+        self.statistics.operation();
+        let node = handle.node();
+        assert!(
+            key < node.borrow().key,
+            "decrease_key() would not decrease the key"
+        );
+        node.borrow_mut().key = key;
+        // the root is still the smallest key, only other nodes have to move
+        if cut(&node, &mut self.statistics).is_some() {
+            self.add_tree(node);
+        }
+    }
+
+    fn meld(&mut self, mut other: Self) {
+        self.statistics.operation();
+        if let Some(tree) = other.root.take() {
+            self.add_tree(tree);
+        }
+        self.n += other.n;
+        self.statistics += other.statistics;
+    }
+
+    fn len(&self) -> usize {
+        self.n
+    }
+
+    fn statistics(&self) -> HeapStatistics {
+        self.statistics
+    }
+}
+
+impl<K> Drop for PairingHeap<K> {
+    fn drop(&mut self) {
+        drop_trees(mem::take(&mut self.root));
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use crate::priorityqueues::mergeable::{test::check_queue, PriorityQueue};
+
+    use super::PairingHeap;
+
+    #[test]
+    fn test_pairing_heap() {
+        check_queue::<PairingHeap<u32>>();
+    }
+
+    #[test]
+    fn test_deep_tree() {
+        // keys inserted in decreasing order form a single path
+        let mut pq = PairingHeap::new();
+        for key in (0..200_000).rev() {
+            pq.insert(key);
+        }
+        assert_eq!(pq.statistics().links(), 199_999);
+        assert_eq!(pq.del_min(), Some(0));
+        assert_eq!(pq.del_min(), Some(1));
+    }
+
+    #[test]
+    fn test_amortized_cost() {
+        let mut pq = PairingHeap::new();
+        let n = 1 << 14;
+        for key in 0..n {
+            pq.insert((key * 7919) % n);
+        }
+        for key in 0..n {
+            assert_eq!(pq.del_min(), Some(key));
+        }
+        // O(log N) amortized per del_min
+        assert!(pq.statistics().amortized_cost() < 2.0 * 14.0);
+    }
+}
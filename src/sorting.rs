@@ -0,0 +1,139 @@
+//! Sorting
+//!
+//! Sorting algorithms for slices of items, which are put in order of their keys. Each sort counts
+//! the compares and exchanges it does, so that the costs of the different algorithms can be
+//! compared on the same input.
+use std::{
+    fmt::{self, Display},
+    ops::AddAssign,
+};
+
+use crate::symboltables::item::Item;
+
+pub mod benchmark;
+pub mod elementary;
+pub mod heapsort;
+pub mod mergesort;
+pub mod quicksort;
+pub mod radix;
+
+/// Counts of the basic operations done by a sort
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SortStatistics {
+    compares: usize,
+    exchanges: usize,
+}
+
+impl SortStatistics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of key compares
+    pub fn compares(&self) -> usize {
+        self.compares
+    }
+
+    /// Number of exchanges of two items
+    pub fn exchanges(&self) -> usize {
+        self.exchanges
+    }
+
+    /// Is the key of item `a` less than the key of item `b`?
+    pub fn less<I: Item>(&mut self, a: &I, b: &I) -> bool {
+        self.compares += 1;
+        a.key() < b.key()
+    }
+
+    /// Exchange the items at `i` and `j`
+    pub fn exch<T>(&mut self, a: &mut [T], i: usize, j: usize) {
+        self.exchanges += 1;
+        a.swap(i, j);
+    }
+}
+
+impl AddAssign for SortStatistics {
+    fn add_assign(&mut self, other: Self) {
+        self.compares += other.compares;
+        self.exchanges += other.exchanges;
+    }
+}
+
+impl Display for SortStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "compares: {}, exchanges: {}",
+            self.compares, self.exchanges
+        )
+    }
+}
+
+/// Are the items in order of their keys?
+pub fn is_sorted<I: Item>(a: &[I]) -> bool {
+    a.windows(2).all(|w| w[0].key() <= w[1].key())
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+pub(crate) mod test {
+    use rand::Rng;
+
+    use crate::symboltables::item::{DoubleItem, GenericItem, Item};
+
+    use super::{is_sorted, SortStatistics};
+
+    /// `n` items with random keys
+    pub fn random_items<R: Rng>(rng: &mut R, n: usize) -> Vec<DoubleItem> {
+        (0..n).map(|_| DoubleItem::with_key(rng.gen())).collect()
+    }
+
+    /// `n` items with random keys less than `m`, so that there are many duplicate keys
+    pub fn items_with_duplicates<R: Rng>(rng: &mut R, n: usize, m: usize) -> Vec<DoubleItem> {
+        (0..n)
+            .map(|_| DoubleItem::with_key(rng.gen_range(0..m)))
+            .collect()
+    }
+
+    /// `n` items with random keys less than `m`, whose values are their original positions
+    pub fn items_with_positions<R: Rng>(
+        rng: &mut R,
+        n: usize,
+        m: usize,
+    ) -> Vec<GenericItem<usize, usize>> {
+        (0..n)
+            .map(|i| GenericItem::with_value(rng.gen_range(0..m), i))
+            .collect()
+    }
+
+    /// Are items with equal keys still in order of their original positions?
+    pub fn is_stable(a: &[GenericItem<usize, usize>]) -> bool {
+        a.windows(2)
+            .all(|w| w[0].key() != w[1].key() || w[0].value() < w[1].value())
+    }
+
+    #[test]
+    fn test_is_sorted() {
+        let a: Vec<DoubleItem> = [1, 2, 2, 5].into_iter().map(DoubleItem::with_key).collect();
+        assert!(is_sorted(&a));
+        assert!(is_sorted::<DoubleItem>(&[]));
+        let b: Vec<DoubleItem> = [1, 3, 2].into_iter().map(DoubleItem::with_key).collect();
+        assert!(!is_sorted(&b));
+    }
+
+    #[test]
+    fn test_statistics() {
+        let mut a = vec![DoubleItem::with_key(2), DoubleItem::with_key(1)];
+        let mut statistics = SortStatistics::new();
+        if statistics.less(&a[1], &a[0]) {
+            statistics.exch(&mut a, 0, 1);
+        }
+        assert!(is_sorted(&a));
+
+        statistics += statistics;
+        assert_eq!(statistics.compares(), 2);
+        assert_eq!(statistics.exchanges(), 2);
+        assert_eq!(statistics.to_string(), "compares: 2, exchanges: 2");
+    }
+}
@@ -0,0 +1,120 @@
+//! Sorting Benchmark
+//!
+//! Sort the same list of words with the string sorts and with the comparison sorts, to compare
+//! their running times. The comparison sorts work on items, so each word is wrapped in a
+//! `GenericItem` first; this is done before the clock is started.
+use std::time::{Duration, Instant};
+
+use crate::symboltables::item::GenericItem;
+
+use super::{
+    elementary::{shell_sort, GapSequence},
+    heapsort::heap_sort,
+    is_sorted,
+    mergesort::merge_sort,
+    quicksort::{quick_sort, quick_sort_3way},
+    radix::{msd_sort, quick_sort_3way_strings},
+    SortStatistics,
+};
+
+type WordItem = GenericItem<String, ()>;
+type StringSort = fn(&mut [String]);
+type ItemSort = fn(&mut [WordItem]) -> SortStatistics;
+
+/// The time taken by one sorting algorithm
+#[derive(Debug)]
+pub struct SortBenchmark {
+    pub algorithm: &'static str,
+    pub elapsed: Duration,
+    pub compares: Option<usize>, // key compares, for comparison sorts only
+}
+
+impl SortBenchmark {
+    /// Sort a copy of the words with each algorithm
+    pub fn run(words: &[String]) -> Vec<SortBenchmark> {
+        let string_sorts: [(&'static str, StringSort); 3] = [
+            ("msd", msd_sort),
+            ("quick3string", quick_sort_3way_strings),
+            ("std", <[String]>::sort_unstable),
+        ];
+        let item_sorts: [(&'static str, ItemSort); 5] = [
+            ("shell", |a| shell_sort(a, &GapSequence::Ciura)),
+            ("merge", merge_sort),
+            ("quick", quick_sort),
+            ("quick3way", quick_sort_3way),
+            ("heap", heap_sort),
+        ];
+
+        let mut result = vec![];
+        for (algorithm, sort) in string_sorts {
+            let mut a = words.to_vec();
+            let start = Instant::now();
+            sort(&mut a);
+            let elapsed = start.elapsed();
+            debug_assert!(a.windows(2).all(|w| w[0] <= w[1]));
+            result.push(SortBenchmark {
+                algorithm,
+                elapsed,
+                compares: None,
+            });
+        }
+        for (algorithm, sort) in item_sorts {
+            let mut a: Vec<WordItem> = words
+                .iter()
+                .map(|word| GenericItem::with_value(word.clone(), ()))
+                .collect();
+            let start = Instant::now();
+            let statistics = sort(&mut a);
+            let elapsed = start.elapsed();
+            debug_assert!(is_sorted(&a));
+            result.push(SortBenchmark {
+                algorithm,
+                elapsed,
+                compares: Some(statistics.compares()),
+            });
+        }
+        result
+    }
+
+    /// Format the results, one algorithm per row
+    pub fn table(results: &[SortBenchmark]) -> String {
+        let mut s = format!(
+            "{:<14} {:>12} {:>12}\n",
+            "algorithm", "time (ms)", "compares"
+        );
+        for result in results {
+            let compares = result
+                .compares
+                .map_or("-".to_string(), |compares| compares.to_string());
+            s += &format!(
+                "{:<14} {:>12.1} {:>12}\n",
+                result.algorithm,
+                result.elapsed.as_secs_f64() * 1000.0,
+                compares
+            );
+        }
+        s
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use crate::symboltables::experiments::words;
+
+    use super::SortBenchmark;
+
+    #[test]
+    fn test_benchmark() {
+        let words = words("resources/tinyTale.txt", 1, usize::MAX).unwrap();
+        let results = SortBenchmark::run(&words);
+        assert_eq!(results.len(), 8);
+        assert!(results[0].compares.is_none());
+        assert!(results[7].compares.unwrap() > words.len());
+
+        let table = SortBenchmark::table(&results);
+        assert_eq!(table.lines().count(), 9);
+        assert!(table.lines().nth(1).unwrap().starts_with("msd"));
+    }
+}
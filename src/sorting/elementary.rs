@@ -0,0 +1,275 @@
+//! Elementary Sorts
+//!
+//! Selection sort, insertion sort, shell sort and bubble sort. They all sort in place and take
+//! quadratic time in the worst case, except shell sort with a good gap sequence.
+use crate::symboltables::item::Item;
+
+use super::SortStatistics;
+
+/// Selection sort: find the smallest remaining item and exchange it with the first unsorted one.
+///
+/// Uses ~ N^2/2 compares and N exchanges, whatever the order of the input.
+pub fn selection_sort<I: Item>(a: &mut [I]) -> SortStatistics {
+    let mut statistics = SortStatistics::new();
+    let n = a.len();
+    for i in 0..n {
+        let mut min = i;
+        for j in i + 1..n {
+            if statistics.less(&a[j], &a[min]) {
+                min = j;
+            }
+        }
+        statistics.exch(a, i, min);
+    }
+    statistics
+}
+
+/// Insertion sort: insert each item among the already sorted items to its left.
+///
+/// Uses ~ N^2/4 compares and exchanges on average, and N - 1 compares and no exchanges when the
+/// input is already sorted.
+pub fn insertion_sort<I: Item>(a: &mut [I]) -> SortStatistics {
+    let mut statistics = SortStatistics::new();
+    for i in 1..a.len() {
+        let mut j = i;
+        while j > 0 && statistics.less(&a[j], &a[j - 1]) {
+            statistics.exch(a, j, j - 1);
+            j -= 1;
+        }
+    }
+    statistics
+}
+
+/// Insertion sort with a sentinel.
+///
+/// The smallest item is first moved to the front, after which it stops every inner loop, so that
+/// the `j > 0` test can be left out.
+pub fn insertion_sort_with_sentinel<I: Item>(a: &mut [I]) -> SortStatistics {
+    let mut statistics = SortStatistics::new();
+    let n = a.len();
+    for i in (1..n).rev() {
+        if statistics.less(&a[i], &a[i - 1]) {
+            statistics.exch(a, i, i - 1);
+        }
+    }
+    for i in 2..n {
+        let mut j = i;
+        while statistics.less(&a[j], &a[j - 1]) {
+            statistics.exch(a, j, j - 1);
+            j -= 1;
+        }
+    }
+    statistics
+}
+
+/// The increments used by shell sort
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GapSequence {
+    /// 1, 4, 13, 40, 121, ... (3h + 1)
+    Knuth,
+    /// 1, 5, 19, 41, 109, ... (9 4^k - 9 2^k + 1 and 4^k - 3 2^k + 1)
+    Sedgewick,
+    /// 1, 4, 10, 23, 57, 132, 301, 701, 1750, extended by a factor of 2.25
+    Ciura,
+    /// Any increasing sequence of gaps; 1 is added if it is missing
+    Custom(Vec<usize>),
+}
+
+const CIURA: [usize; 9] = [1, 4, 10, 23, 57, 132, 301, 701, 1750];
+
+impl GapSequence {
+    /// The gaps to use for an array of `n` items, largest first and ending in 1
+    pub fn gaps(&self, n: usize) -> Vec<usize> {
+        let mut gaps = match self {
+            GapSequence::Knuth => {
+                let mut gaps = vec![1];
+                while 3 * gaps[gaps.len() - 1] + 1 < n {
+                    gaps.push(3 * gaps[gaps.len() - 1] + 1);
+                }
+                gaps
+            }
+            GapSequence::Sedgewick => {
+                let mut gaps = vec![1];
+                for k in 1.. {
+                    let even = 9 * (1 << (2 * k)) - 9 * (1 << k) + 1;
+                    let odd = (1 << (2 * k + 2)) - 3 * (1 << (k + 1)) + 1;
+                    gaps.extend([odd, even].iter().filter(|&&h| h < n));
+                    if odd.min(even) >= n {
+                        break;
+                    }
+                }
+                gaps.sort_unstable();
+                gaps.dedup();
+                gaps
+            }
+            GapSequence::Ciura => {
+                let mut gaps = CIURA.to_vec();
+                while gaps[gaps.len() - 1] < n {
+                    let h = (gaps[gaps.len() - 1] as f64 * 2.25) as usize;
+                    gaps.push(h);
+                }
+                gaps
+            }
+            GapSequence::Custom(gaps) => {
+                let mut gaps = gaps.clone();
+                gaps.push(1);
+                gaps.retain(|&h| h > 0);
+                gaps.sort_unstable();
+                gaps.dedup();
+                gaps
+            }
+        };
+        // gaps not smaller than n do no work, but keep the final 1
+        gaps.retain(|&h| h == 1 || h < n);
+        gaps.reverse();
+        gaps
+    }
+}
+
+/// Shell sort: h-sort the array for each gap h of the sequence, ending with an insertion sort.
+pub fn shell_sort<I: Item>(a: &mut [I], sequence: &GapSequence) -> SortStatistics {
+    let mut statistics = SortStatistics::new();
+    for h in sequence.gaps(a.len()) {
+        for i in h..a.len() {
+            let mut j = i;
+            while j >= h && statistics.less(&a[j], &a[j - h]) {
+                statistics.exch(a, j, j - h);
+                j -= h;
+            }
+        }
+    }
+    statistics
+}
+
+/// Bubble sort: exchange adjacent items that are out of order, until a pass makes no exchanges.
+pub fn bubble_sort<I: Item>(a: &mut [I]) -> SortStatistics {
+    let mut statistics = SortStatistics::new();
+    let mut n = a.len();
+    while n > 1 {
+        // everything after the last exchange is in its final position
+        let mut last = 0;
+        for i in 1..n {
+            if statistics.less(&a[i], &a[i - 1]) {
+                statistics.exch(a, i, i - 1);
+                last = i;
+            }
+        }
+        n = last;
+    }
+    statistics
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::{
+        sorting::{
+            is_sorted,
+            test::{items_with_duplicates, random_items},
+            SortStatistics,
+        },
+        symboltables::item::{DoubleItem, Item},
+    };
+
+    use super::{
+        bubble_sort, insertion_sort, insertion_sort_with_sentinel, selection_sort, shell_sort,
+        GapSequence,
+    };
+
+    fn check(sort: impl Fn(&mut [DoubleItem]) -> SortStatistics) {
+        let mut rng = StdRng::seed_from_u64(1);
+        for n in [0, 1, 2, 10, 500] {
+            let mut a = random_items(&mut rng, n);
+            let mut expected: Vec<_> = a.iter().map(|item| item.key()).collect();
+            expected.sort_unstable();
+            sort(&mut a);
+            assert!(is_sorted(&a));
+            assert_eq!(
+                a.iter().map(|item| item.key()).collect::<Vec<_>>(),
+                expected
+            );
+        }
+
+        let mut a = items_with_duplicates(&mut rng, 300, 5);
+        sort(&mut a);
+        assert!(is_sorted(&a));
+    }
+
+    #[test]
+    fn test_selection_sort() {
+        let mut rng = StdRng::seed_from_u64(1);
+        check(selection_sort);
+
+        let mut a = random_items(&mut rng, 100);
+        let statistics = selection_sort(&mut a);
+        assert_eq!(statistics.compares(), 100 * 99 / 2);
+        assert_eq!(statistics.exchanges(), 100);
+    }
+
+    #[test]
+    fn test_insertion_sort() {
+        let mut rng = StdRng::seed_from_u64(1);
+        check(insertion_sort);
+
+        let mut a = random_items(&mut rng, 100);
+        insertion_sort(&mut a);
+        let statistics = insertion_sort(&mut a);
+        assert_eq!(statistics.compares(), 99);
+        assert_eq!(statistics.exchanges(), 0);
+    }
+
+    #[test]
+    fn test_insertion_sort_with_sentinel() {
+        let mut rng = StdRng::seed_from_u64(1);
+        check(insertion_sort_with_sentinel);
+
+        // the same number of inversions are removed, one exchange each
+        let mut a = random_items(&mut rng, 200);
+        let with = insertion_sort_with_sentinel(&mut a.clone());
+        let without = insertion_sort(&mut a);
+        assert_eq!(with.exchanges(), without.exchanges());
+    }
+
+    #[test]
+    fn test_shell_sort() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for sequence in [
+            GapSequence::Knuth,
+            GapSequence::Sedgewick,
+            GapSequence::Ciura,
+            GapSequence::Custom(vec![7, 3]),
+        ] {
+            check(|a| shell_sort(a, &sequence));
+        }
+
+        let mut a = random_items(&mut rng, 2000);
+        let shell = shell_sort(&mut a.clone(), &GapSequence::Knuth);
+        let insertion = insertion_sort(&mut a);
+        assert!(shell.compares() < insertion.compares());
+    }
+
+    #[test]
+    fn test_gaps() {
+        assert_eq!(GapSequence::Knuth.gaps(100), vec![40, 13, 4, 1]);
+        assert_eq!(GapSequence::Sedgewick.gaps(200), vec![109, 41, 19, 5, 1]);
+        assert_eq!(GapSequence::Ciura.gaps(60), vec![57, 23, 10, 4, 1]);
+        assert_eq!(GapSequence::Ciura.gaps(3000).first(), Some(&1750));
+        assert_eq!(GapSequence::Custom(vec![5, 2]).gaps(10), vec![5, 2, 1]);
+        assert_eq!(GapSequence::Knuth.gaps(0), vec![1]);
+    }
+
+    #[test]
+    fn test_bubble_sort() {
+        let mut rng = StdRng::seed_from_u64(1);
+        check(bubble_sort);
+
+        let mut a = random_items(&mut rng, 100);
+        bubble_sort(&mut a);
+        let statistics = bubble_sort(&mut a);
+        assert_eq!(statistics.compares(), 99);
+        assert_eq!(statistics.exchanges(), 0);
+    }
+}
@@ -0,0 +1,65 @@
+//! Heapsort
+use crate::symboltables::item::Item;
+
+use super::SortStatistics;
+
+/// Heapsort: build a max-heap in the array, then repeatedly move the largest item to the end.
+///
+/// Sorts in place in at most 2 N lg N compares, whatever the order of the input.
+pub fn heap_sort<I: Item>(a: &mut [I]) -> SortStatistics {
+    let mut statistics = SortStatistics::new();
+    let mut n = a.len();
+    for k in (0..n / 2).rev() {
+        sink(a, k, n, &mut statistics);
+    }
+    while n > 1 {
+        n -= 1;
+        statistics.exch(a, 0, n);
+        sink(a, 0, n, &mut statistics);
+    }
+    statistics
+}
+
+// Move the item at `k` down the heap `a[..n]` until its children are not larger
+fn sink<I: Item>(a: &mut [I], mut k: usize, n: usize, statistics: &mut SortStatistics) {
+    while 2 * k + 1 < n {
+        let mut j = 2 * k + 1;
+        if j + 1 < n && statistics.less(&a[j], &a[j + 1]) {
+            j += 1;
+        }
+        if !statistics.less(&a[k], &a[j]) {
+            break;
+        }
+        statistics.exch(a, k, j);
+        k = j;
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::sorting::{
+        is_sorted,
+        test::{items_with_duplicates, random_items},
+    };
+
+    use super::heap_sort;
+
+    #[test]
+    fn test_heap_sort() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for n in [0, 1, 2, 3, 100, 1024] {
+            let mut a = random_items(&mut rng, n);
+            let statistics = heap_sort(&mut a);
+            assert!(is_sorted(&a));
+            assert!(statistics.compares() <= 2 * n * (usize::BITS - n.leading_zeros()) as usize);
+        }
+
+        let mut a = items_with_duplicates(&mut rng, 500, 3);
+        heap_sort(&mut a);
+        assert!(is_sorted(&a));
+    }
+}
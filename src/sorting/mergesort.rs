@@ -0,0 +1,232 @@
+//! Mergesort
+//!
+//! Top-down, bottom-up, natural and parallel mergesort. All of them are stable: items with equal
+//! keys keep their relative order. Merging copies the items to an auxiliary array, so the items
+//! have to be `Clone`.
+use std::thread;
+
+use crate::symboltables::item::Item;
+
+use super::{elementary::insertion_sort, SortStatistics};
+
+// Subarrays of at most this size are sorted with insertion sort
+const CUTOFF: usize = 7;
+
+// Subarrays smaller than this are not split over threads
+const PARALLEL_CUTOFF: usize = 1 << 12;
+
+/// Merge the sorted halves `a[..mid]` and `a[mid..]`, using `aux` (of the same length as `a`) as
+/// scratch space. On equal keys the item of the left half is taken first, which keeps the merge
+/// stable.
+fn merge<I: Item + Clone>(a: &mut [I], aux: &mut [I], mid: usize, statistics: &mut SortStatistics) {
+    aux.clone_from_slice(a);
+    let (mut i, mut j) = (0, mid);
+    for k in 0..a.len() {
+        if i >= mid {
+            a[k] = aux[j].clone();
+            j += 1;
+        } else if j >= a.len() {
+            a[k] = aux[i].clone();
+            i += 1;
+        } else if statistics.less(&aux[j], &aux[i]) {
+            a[k] = aux[j].clone();
+            j += 1;
+        } else {
+            a[k] = aux[i].clone();
+            i += 1;
+        }
+    }
+}
+
+/// Top-down mergesort: sort both halves recursively and merge them.
+///
+/// Small subarrays are sorted with insertion sort, and the merge is skipped when the halves are
+/// already in order. Uses at most N lg N compares.
+pub fn merge_sort<I: Item + Clone>(a: &mut [I]) -> SortStatistics {
+    let mut statistics = SortStatistics::new();
+    let mut aux = a.to_vec();
+    sort_r(a, &mut aux, &mut statistics);
+    statistics
+}
+
+fn sort_r<I: Item + Clone>(a: &mut [I], aux: &mut [I], statistics: &mut SortStatistics) {
+    let n = a.len();
+    if n <= CUTOFF {
+        *statistics += insertion_sort(a);
+        return;
+    }
+    let mid = n / 2;
+    sort_r(&mut a[..mid], &mut aux[..mid], statistics);
+    sort_r(&mut a[mid..], &mut aux[mid..], statistics);
+    if !statistics.less(&a[mid], &a[mid - 1]) {
+        return;
+    }
+    merge(a, aux, mid, statistics);
+}
+
+/// Bottom-up mergesort: merge subarrays of size 1, then 2, 4, 8, ... until the whole array is
+/// sorted.
+pub fn merge_sort_bottom_up<I: Item + Clone>(a: &mut [I]) -> SortStatistics {
+    let mut statistics = SortStatistics::new();
+    let n = a.len();
+    let mut aux = a.to_vec();
+    let mut size = 1;
+    while size < n {
+        for lo in (0..n - size).step_by(2 * size) {
+            let hi = (lo + 2 * size).min(n);
+            merge(&mut a[lo..hi], &mut aux[lo..hi], size, &mut statistics);
+        }
+        size *= 2;
+    }
+    statistics
+}
+
+/// Natural mergesort: find the runs that are already in order and merge adjacent runs until a
+/// single run is left.
+///
+/// Takes linear time on input that is already sorted, or that consists of a few sorted runs.
+pub fn natural_merge_sort<I: Item + Clone>(a: &mut [I]) -> SortStatistics {
+    let mut statistics = SortStatistics::new();
+    let n = a.len();
+    let mut aux = a.to_vec();
+    loop {
+        let mut lo = 0;
+        let mut runs = 0;
+        while lo < n {
+            let mid = run_end(a, lo, &mut statistics);
+            runs += 1;
+            if mid == n {
+                break;
+            }
+            let hi = run_end(a, mid, &mut statistics);
+            merge(&mut a[lo..hi], &mut aux[lo..hi], mid - lo, &mut statistics);
+            lo = hi;
+        }
+        if runs <= 1 {
+            return statistics;
+        }
+    }
+}
+
+// The end (exclusive) of the run of items in order that starts at `lo`
+fn run_end<I: Item>(a: &[I], lo: usize, statistics: &mut SortStatistics) -> usize {
+    let mut hi = lo + 1;
+    while hi < a.len() && !statistics.less(&a[hi], &a[hi - 1]) {
+        hi += 1;
+    }
+    hi
+}
+
+/// Parallel mergesort: the halves are sorted on separate threads, splitting until `threads`
+/// threads are in use or the subarrays get small, after which top-down mergesort is used.
+pub fn parallel_merge_sort<I>(a: &mut [I], threads: usize) -> SortStatistics
+where
+    I: Item + Clone + Send,
+{
+    let mut aux = a.to_vec();
+    parallel_sort_r(a, &mut aux, threads.max(1))
+}
+
+fn parallel_sort_r<I>(a: &mut [I], aux: &mut [I], threads: usize) -> SortStatistics
+where
+    I: Item + Clone + Send,
+{
+    let n = a.len();
+    if threads == 1 || n < PARALLEL_CUTOFF {
+        let mut statistics = SortStatistics::new();
+        sort_r(a, aux, &mut statistics);
+        return statistics;
+    }
+
+    let mid = n / 2;
+    let (left, right) = a.split_at_mut(mid);
+    let (aux_left, aux_right) = aux.split_at_mut(mid);
+    let mut statistics = thread::scope(|s| {
+        let handle = s.spawn(|| parallel_sort_r(left, aux_left, threads / 2));
+        let mut statistics = parallel_sort_r(right, aux_right, threads - threads / 2);
+        statistics += handle.join().expect("sorting thread panicked");
+        statistics
+    });
+    if statistics.less(&a[mid], &a[mid - 1]) {
+        merge(a, aux, mid, &mut statistics);
+    }
+    statistics
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::{
+        sorting::{
+            is_sorted,
+            test::{is_stable, items_with_positions, random_items},
+            SortStatistics,
+        },
+        symboltables::item::{DoubleItem, GenericItem, Item},
+    };
+
+    use super::{merge_sort, merge_sort_bottom_up, natural_merge_sort, parallel_merge_sort};
+
+    fn check(
+        sort: impl Fn(&mut [DoubleItem]) -> SortStatistics,
+        stable_sort: impl Fn(&mut [GenericItem<usize, usize>]) -> SortStatistics,
+    ) {
+        let mut rng = StdRng::seed_from_u64(1);
+        for n in [0, 1, 2, 7, 8, 100, 1000] {
+            let mut a = random_items(&mut rng, n);
+            let mut expected: Vec<_> = a.iter().map(|item| item.key()).collect();
+            expected.sort_unstable();
+            sort(&mut a);
+            assert_eq!(
+                a.iter().map(|item| item.key()).collect::<Vec<_>>(),
+                expected
+            );
+        }
+
+        let mut a = items_with_positions(&mut rng, 1000, 10);
+        stable_sort(&mut a);
+        assert!(is_sorted(&a));
+        assert!(is_stable(&a));
+    }
+
+    #[test]
+    fn test_merge_sort() {
+        let mut rng = StdRng::seed_from_u64(1);
+        check(merge_sort, merge_sort);
+
+        // at most N lg N compares
+        let mut a = random_items(&mut rng, 1024);
+        assert!(merge_sort(&mut a).compares() <= 1024 * 10);
+    }
+
+    #[test]
+    fn test_merge_sort_bottom_up() {
+        check(merge_sort_bottom_up, merge_sort_bottom_up);
+    }
+
+    #[test]
+    fn test_natural_merge_sort() {
+        let mut rng = StdRng::seed_from_u64(1);
+        check(natural_merge_sort, natural_merge_sort);
+
+        // a sorted array is a single run
+        let mut a = random_items(&mut rng, 100);
+        natural_merge_sort(&mut a);
+        let statistics = natural_merge_sort(&mut a);
+        assert_eq!(statistics.compares(), 99);
+    }
+
+    #[test]
+    fn test_parallel_merge_sort() {
+        let mut rng = StdRng::seed_from_u64(1);
+        check(|a| parallel_merge_sort(a, 4), |a| parallel_merge_sort(a, 4));
+
+        let mut a = items_with_positions(&mut rng, 20_000, 100);
+        parallel_merge_sort(&mut a, 3);
+        assert!(is_sorted(&a));
+        assert!(is_stable(&a));
+    }
+}
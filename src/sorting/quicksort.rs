@@ -0,0 +1,320 @@
+//! Quicksort
+//!
+//! In-place quicksorts: median-of-three quicksort, quicksort with 3-way partitioning for keys with
+//! many duplicates, dual-pivot quicksort and introsort. None of them are stable. `quickselect`
+//! uses the same partitioning to find the k-th smallest item in linear time on average.
+use std::cmp::Ordering;
+
+use crate::symboltables::item::Item;
+
+use super::{elementary::insertion_sort, heapsort::heap_sort, SortStatistics};
+
+// Subarrays of at most this size are sorted with insertion sort
+const CUTOFF: usize = 10;
+
+/// Quicksort with median-of-three partitioning, sorting small subarrays with insertion sort.
+///
+/// Taking the median of the first, middle and last items as the partitioning item avoids the
+/// quadratic worst case on input that is already (reverse) sorted.
+pub fn quick_sort<I: Item>(a: &mut [I]) -> SortStatistics {
+    let mut statistics = SortStatistics::new();
+    sort_r(a, &mut statistics);
+    statistics
+}
+
+fn sort_r<I: Item>(a: &mut [I], statistics: &mut SortStatistics) {
+    if a.len() <= CUTOFF {
+        *statistics += insertion_sort(a);
+        return;
+    }
+    median_of_three(a, statistics);
+    let j = partition(a, statistics);
+    let (left, right) = a.split_at_mut(j);
+    sort_r(left, statistics);
+    sort_r(&mut right[1..], statistics);
+}
+
+/// Partition the array on its first item: afterwards that item is at the returned index `j`, with
+/// no larger items before it and no smaller items after it.
+///
+/// The scans stop on items equal to the partitioning item, which keeps the partitions balanced
+/// when there are many duplicate keys.
+fn partition<I: Item>(a: &mut [I], statistics: &mut SortStatistics) -> usize {
+    let n = a.len();
+    let (mut i, mut j) = (0, n);
+    loop {
+        i += 1;
+        while i < n - 1 && statistics.less(&a[i], &a[0]) {
+            i += 1;
+        }
+        j -= 1;
+        // a[0] itself stops the scan
+        while statistics.less(&a[0], &a[j]) {
+            j -= 1;
+        }
+        if i >= j {
+            break;
+        }
+        statistics.exch(a, i, j);
+    }
+    statistics.exch(a, 0, j);
+    j
+}
+
+// Move the median of the first, middle and last items to the front
+fn median_of_three<I: Item>(a: &mut [I], statistics: &mut SortStatistics) {
+    let (lo, mid, hi) = (0, a.len() / 2, a.len() - 1);
+    let median = if statistics.less(&a[lo], &a[mid]) {
+        if statistics.less(&a[mid], &a[hi]) {
+            mid
+        } else if statistics.less(&a[lo], &a[hi]) {
+            hi
+        } else {
+            lo
+        }
+    } else if statistics.less(&a[hi], &a[mid]) {
+        mid
+    } else if statistics.less(&a[hi], &a[lo]) {
+        hi
+    } else {
+        lo
+    };
+    statistics.exch(a, lo, median);
+}
+
+/// Quicksort with Dijkstra's 3-way partitioning into items less than, equal to and greater than
+/// the partitioning item.
+///
+/// Items equal to the partitioning item are not looked at again, so arrays with only a few
+/// distinct keys are sorted in linear time.
+pub fn quick_sort_3way<I: Item>(a: &mut [I]) -> SortStatistics {
+    let mut statistics = SortStatistics::new();
+    sort_3way_r(a, &mut statistics);
+    statistics
+}
+
+fn sort_3way_r<I: Item>(a: &mut [I], statistics: &mut SortStatistics) {
+    let n = a.len();
+    if n <= 1 {
+        return;
+    }
+    // a[lt..i] are equal to the partitioning item, which is always at a[lt]
+    let (mut lt, mut i, mut gt) = (0, 1, n - 1);
+    while i <= gt {
+        if statistics.less(&a[i], &a[lt]) {
+            statistics.exch(a, lt, i);
+            lt += 1;
+            i += 1;
+        } else if statistics.less(&a[lt], &a[i]) {
+            statistics.exch(a, i, gt);
+            gt -= 1;
+        } else {
+            i += 1;
+        }
+    }
+    sort_3way_r(&mut a[..lt], statistics);
+    sort_3way_r(&mut a[gt + 1..], statistics);
+}
+
+/// Dual-pivot quicksort: partition on two items p <= q into items less than p, between p and q,
+/// and greater than q.
+pub fn dual_pivot_quick_sort<I: Item>(a: &mut [I]) -> SortStatistics {
+    let mut statistics = SortStatistics::new();
+    dual_pivot_r(a, &mut statistics);
+    statistics
+}
+
+fn dual_pivot_r<I: Item>(a: &mut [I], statistics: &mut SortStatistics) {
+    let n = a.len();
+    if n <= CUTOFF {
+        *statistics += insertion_sort(a);
+        return;
+    }
+    if statistics.less(&a[n - 1], &a[0]) {
+        statistics.exch(a, 0, n - 1);
+    }
+
+    // a[1..lt] < p, a[lt..i] between p and q, a[gt + 1..n - 1] > q
+    let (mut lt, mut i, mut gt) = (1, 1, n - 2);
+    while i <= gt {
+        if statistics.less(&a[i], &a[0]) {
+            statistics.exch(a, lt, i);
+            lt += 1;
+            i += 1;
+        } else if statistics.less(&a[n - 1], &a[i]) {
+            statistics.exch(a, i, gt);
+            gt -= 1;
+        } else {
+            i += 1;
+        }
+    }
+    lt -= 1;
+    gt += 1;
+    statistics.exch(a, 0, lt);
+    statistics.exch(a, n - 1, gt);
+
+    dual_pivot_r(&mut a[..lt], statistics);
+    // when the pivots are equal, so are all the items between them
+    if statistics.less(&a[lt], &a[gt]) {
+        dual_pivot_r(&mut a[lt + 1..gt], statistics);
+    }
+    dual_pivot_r(&mut a[gt + 1..], statistics);
+}
+
+/// Introsort: median-of-three quicksort that switches to heapsort when the recursion gets deeper
+/// than 2 lg N, which bounds the worst case to N lg N.
+pub fn intro_sort<I: Item>(a: &mut [I]) -> SortStatistics {
+    let mut statistics = SortStatistics::new();
+    let depth = 2 * (usize::BITS - a.len().leading_zeros()) as usize;
+    intro_sort_r(a, depth, &mut statistics);
+    statistics
+}
+
+fn intro_sort_r<I: Item>(a: &mut [I], depth: usize, statistics: &mut SortStatistics) {
+    if a.len() <= CUTOFF {
+        *statistics += insertion_sort(a);
+        return;
+    }
+    if depth == 0 {
+        *statistics += heap_sort(a);
+        return;
+    }
+    median_of_three(a, statistics);
+    let j = partition(a, statistics);
+    let (left, right) = a.split_at_mut(j);
+    intro_sort_r(left, depth - 1, statistics);
+    intro_sort_r(&mut right[1..], depth - 1, statistics);
+}
+
+/// Rearrange the array so that the k-th smallest item (counting from 0) is at index `k`, with no
+/// larger items before it and no smaller items after it.
+///
+/// Takes linear time on average.
+///
+/// # Panics
+///
+/// Panics if `k` is not less than the length of the array.
+pub fn quickselect<I: Item>(a: &mut [I], k: usize) -> SortStatistics {
+    assert!(k < a.len(), "cannot select item {} of {}", k, a.len());
+    let mut statistics = SortStatistics::new();
+    let (mut lo, mut hi) = (0, a.len());
+    while hi - lo > 1 {
+        let subarray = &mut a[lo..hi];
+        if subarray.len() >= 3 {
+            median_of_three(subarray, &mut statistics);
+        }
+        let j = lo + partition(subarray, &mut statistics);
+        match j.cmp(&k) {
+            Ordering::Less => lo = j + 1,
+            Ordering::Greater => hi = j,
+            Ordering::Equal => break,
+        }
+    }
+    statistics
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::{
+        sorting::{
+            is_sorted,
+            test::{items_with_duplicates, random_items},
+            SortStatistics,
+        },
+        symboltables::item::{DoubleItem, Item},
+    };
+
+    use super::{
+        dual_pivot_quick_sort, intro_sort, intro_sort_r, quick_sort, quick_sort_3way, quickselect,
+    };
+
+    fn check(sort: impl Fn(&mut [DoubleItem]) -> SortStatistics) {
+        let mut rng = StdRng::seed_from_u64(1);
+        for n in [0, 1, 2, 3, 10, 11, 100, 2000] {
+            let mut a = random_items(&mut rng, n);
+            let mut expected: Vec<_> = a.iter().map(|item| item.key()).collect();
+            expected.sort_unstable();
+            sort(&mut a);
+            assert_eq!(
+                a.iter().map(|item| item.key()).collect::<Vec<_>>(),
+                expected
+            );
+
+            // already sorted and reverse sorted input
+            sort(&mut a);
+            assert!(is_sorted(&a));
+            a.reverse();
+            sort(&mut a);
+            assert!(is_sorted(&a));
+        }
+
+        for m in [1, 2, 10] {
+            let mut a = items_with_duplicates(&mut rng, 1000, m);
+            sort(&mut a);
+            assert!(is_sorted(&a));
+        }
+    }
+
+    #[test]
+    fn test_quick_sort() {
+        check(quick_sort);
+    }
+
+    #[test]
+    fn test_quick_sort_3way() {
+        let mut rng = StdRng::seed_from_u64(1);
+        check(quick_sort_3way);
+
+        // with a single key every other item is compared twice to the partitioning item
+        let mut a = items_with_duplicates(&mut rng, 1000, 1);
+        let statistics = quick_sort_3way(&mut a);
+        assert_eq!(statistics.compares(), 2 * 999);
+    }
+
+    #[test]
+    fn test_dual_pivot_quick_sort() {
+        check(dual_pivot_quick_sort);
+    }
+
+    #[test]
+    fn test_intro_sort() {
+        let mut rng = StdRng::seed_from_u64(1);
+        check(intro_sort);
+
+        // without any recursion left, introsort is heapsort
+        let mut a = random_items(&mut rng, 1000);
+        let mut statistics = SortStatistics::new();
+        intro_sort_r(&mut a, 0, &mut statistics);
+        assert!(is_sorted(&a));
+    }
+
+    #[test]
+    fn test_quickselect() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let a = random_items(&mut rng, 500);
+        let mut keys: Vec<_> = a.iter().map(|item| item.key()).collect();
+        keys.sort_unstable();
+        for k in [0, 1, 250, 498, 499] {
+            let mut b = a.clone();
+            quickselect(&mut b, k);
+            assert_eq!(b[k].key(), keys[k]);
+            assert!(b[..k].iter().all(|item| item.key() <= keys[k]));
+            assert!(b[k + 1..].iter().all(|item| item.key() >= keys[k]));
+        }
+
+        let mut a = items_with_duplicates(&mut rng, 500, 3);
+        quickselect(&mut a, 400);
+        assert!(a[400..].iter().all(|item| item.key() >= a[400].key()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_quickselect_out_of_range() {
+        let mut rng = StdRng::seed_from_u64(1);
+        quickselect(&mut random_items(&mut rng, 3), 3);
+    }
+}
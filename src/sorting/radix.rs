@@ -0,0 +1,268 @@
+//! Radix Sorts
+//!
+//! Sorts that look at the digits (or characters) of the keys instead of comparing whole keys:
+//! key-indexed counting, LSD radix sort, MSD radix sort and 3-way string quicksort. Strings are
+//! sorted on their UTF-8 bytes, which puts them in the same order as comparing them as `str`.
+use crate::symboltables::item::Item;
+
+// Radix of the byte sized digits and characters
+const R: usize = 256;
+
+// Subarrays of at most this size are sorted with insertion sort by MSD radix sort
+const CUTOFF: usize = 15;
+
+/// Key-indexed counting: sort items with keys less than `r` by counting how often each key occurs.
+///
+/// The sort is stable and takes time proportional to N + r.
+///
+/// # Panics
+///
+/// Panics if an item has a key that is not less than `r`.
+pub fn key_indexed_counting_sort<I>(a: &mut [I], r: usize)
+where
+    I: Item<Key = usize> + Clone,
+{
+    let mut aux = a.to_vec();
+    counting_pass(a, &mut aux, r, |item| item.key());
+}
+
+// Stable counting sort on the digit (less than `r`) of each item, using `aux` as scratch space
+fn counting_pass<T: Clone>(a: &mut [T], aux: &mut [T], r: usize, digit: impl Fn(&T) -> usize) {
+    // count[d + 1] is the number of items with digit d, then turned into the start of d
+    let mut count = vec![0; r + 1];
+    for x in a.iter() {
+        count[digit(x) + 1] += 1;
+    }
+    for d in 0..r {
+        count[d + 1] += count[d];
+    }
+    for x in a.iter() {
+        let d = digit(x);
+        aux[count[d]] = x.clone();
+        count[d] += 1;
+    }
+    a.clone_from_slice(aux);
+}
+
+/// LSD radix sort of items with integer keys: a stable counting sort on each byte of the keys,
+/// from the least significant to the most significant byte.
+///
+/// Only the bytes needed for the largest key are looked at, so small keys take fewer passes.
+pub fn lsd_sort<I>(a: &mut [I])
+where
+    I: Item<Key = usize> + Clone,
+{
+    let max = a.iter().map(|item| item.key()).max().unwrap_or(0);
+    let bits = (usize::BITS - max.leading_zeros()) as usize;
+    let mut aux = a.to_vec();
+    for shift in (0..bits).step_by(8) {
+        counting_pass(a, &mut aux, R, |item| (item.key() >> shift) & (R - 1));
+    }
+}
+
+/// LSD radix sort of strings on their first `w` bytes: a stable counting sort on each byte, from
+/// the last to the first one. Strings of equal length `w` are put in order.
+///
+/// # Panics
+///
+/// Panics if a string is shorter than `w` bytes.
+pub fn lsd_sort_strings<S: AsRef<str> + Clone>(a: &mut [S], w: usize) {
+    let mut aux = a.to_vec();
+    for d in (0..w).rev() {
+        counting_pass(a, &mut aux, R, |s| s.as_ref().as_bytes()[d] as usize);
+    }
+}
+
+/// MSD radix sort of variable length strings: distribute the strings on their first character
+/// and sort each group recursively on the next character.
+///
+/// Small groups are sorted with insertion sort, since the counts of every group cost R steps.
+pub fn msd_sort<S: AsRef<str> + Clone>(a: &mut [S]) {
+    let mut aux = a.to_vec();
+    msd_r(a, &mut aux, 0);
+}
+
+fn msd_r<S: AsRef<str> + Clone>(a: &mut [S], aux: &mut [S], d: usize) {
+    if a.len() <= CUTOFF {
+        insertion_sort_from(a, d);
+        return;
+    }
+    // strings that end at d are counted as character 0, and go first
+    counting_pass(a, aux, R + 1, |s| char_at(s, d));
+
+    let mut lo = 0;
+    for c in 0..=R {
+        let hi = lo + a[lo..].iter().take_while(|s| char_at(*s, d) == c).count();
+        if c > 0 && hi - lo > 1 {
+            msd_r(&mut a[lo..hi], &mut aux[lo..hi], d + 1);
+        }
+        lo = hi;
+    }
+}
+
+// The character at `d` plus one, or 0 when the string has no character at `d`
+fn char_at<S: AsRef<str>>(s: &S, d: usize) -> usize {
+    s.as_ref().as_bytes().get(d).map_or(0, |&b| b as usize + 1)
+}
+
+// Insertion sort of strings that are known to be equal in their first `d` characters
+fn insertion_sort_from<S: AsRef<str>>(a: &mut [S], d: usize) {
+    for i in 1..a.len() {
+        let mut j = i;
+        while j > 0 && a[j].as_ref().as_bytes()[d..] < a[j - 1].as_ref().as_bytes()[d..] {
+            a.swap(j, j - 1);
+            j -= 1;
+        }
+    }
+}
+
+/// 3-way string quicksort: 3-way partition the strings on their character at `d`, then sort the
+/// smaller and larger groups on the same character and the equal group on the next one.
+///
+/// Sorts in place, and handles long common prefixes and many equal keys well.
+pub fn quick_sort_3way_strings<S: AsRef<str>>(a: &mut [S]) {
+    quick_sort_3way_r(a, 0);
+}
+
+fn quick_sort_3way_r<S: AsRef<str>>(a: &mut [S], d: usize) {
+    let n = a.len();
+    if n <= 1 {
+        return;
+    }
+    let v = char_at(&a[0], d);
+    let (mut lt, mut i, mut gt) = (0, 1, n - 1);
+    while i <= gt {
+        let c = char_at(&a[i], d);
+        if c < v {
+            a.swap(lt, i);
+            lt += 1;
+            i += 1;
+        } else if c > v {
+            a.swap(i, gt);
+            gt -= 1;
+        } else {
+            i += 1;
+        }
+    }
+    quick_sort_3way_r(&mut a[..lt], d);
+    // strings that end at d are equal
+    if v > 0 {
+        quick_sort_3way_r(&mut a[lt..=gt], d + 1);
+    }
+    quick_sort_3way_r(&mut a[gt + 1..], d);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::{
+        sorting::{
+            is_sorted,
+            test::{is_stable, items_with_positions, random_items},
+        },
+        symboltables::item::{DoubleItem, Item},
+    };
+
+    use super::{
+        key_indexed_counting_sort, lsd_sort, lsd_sort_strings, msd_sort, quick_sort_3way_strings,
+    };
+
+    fn random_strings<R: Rng>(rng: &mut R, n: usize, max_length: usize) -> Vec<String> {
+        (0..n)
+            .map(|_| {
+                let length = rng.gen_range(0..=max_length);
+                (0..length).map(|_| rng.gen_range('a'..='d')).collect()
+            })
+            .collect()
+    }
+
+    fn sorted(a: &[String]) -> Vec<String> {
+        let mut a = a.to_vec();
+        a.sort();
+        a
+    }
+
+    #[test]
+    fn test_key_indexed_counting_sort() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut a = items_with_positions(&mut rng, 1000, 20);
+        key_indexed_counting_sort(&mut a, 20);
+        assert!(is_sorted(&a));
+        assert!(is_stable(&a));
+    }
+
+    #[test]
+    fn test_lsd_sort() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut a = random_items(&mut rng, 1000);
+        let mut expected: Vec<_> = a.iter().map(|item| item.key()).collect();
+        expected.sort_unstable();
+        lsd_sort(&mut a);
+        assert_eq!(
+            a.iter().map(|item| item.key()).collect::<Vec<_>>(),
+            expected
+        );
+
+        let mut a: Vec<DoubleItem> = vec![];
+        lsd_sort(&mut a);
+
+        let mut a = items_with_positions(&mut rng, 1000, 300);
+        lsd_sort(&mut a);
+        assert!(is_sorted(&a));
+        assert!(is_stable(&a));
+    }
+
+    #[test]
+    fn test_lsd_sort_strings() {
+        // license plates, as in Algorithms 4th edition
+        let mut a = vec![
+            "4PGC938", "2IYE230", "3CIO720", "1ICK750", "1OHV845", "4JZY524", "1ICK750", "3CIO720",
+            "1OHV845", "1OHV845", "2RLA629", "2RLA629", "3ATW723",
+        ];
+        let mut expected = a.clone();
+        expected.sort();
+        lsd_sort_strings(&mut a, 7);
+        assert_eq!(a, expected);
+    }
+
+    #[test]
+    fn test_msd_sort() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut a: Vec<_> = "she sells seashells by the sea shore the shells she sells are \
+                             surely seashells"
+            .split(' ')
+            .collect();
+        let mut expected = a.clone();
+        expected.sort();
+        msd_sort(&mut a);
+        assert_eq!(a, expected);
+
+        for n in [0, 1, 100, 2000] {
+            let mut a = random_strings(&mut rng, n, 12);
+            let expected = sorted(&a);
+            msd_sort(&mut a);
+            assert_eq!(a, expected);
+        }
+    }
+
+    #[test]
+    fn test_quick_sort_3way_strings() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for n in [0, 1, 100, 2000] {
+            let mut a = random_strings(&mut rng, n, 12);
+            let expected = sorted(&a);
+            quick_sort_3way_strings(&mut a);
+            assert_eq!(a, expected);
+        }
+
+        // multi-byte characters are ordered as in `str`
+        let mut a = vec!["zebra", "émile", "eagle", "", "ébène", "e"];
+        let mut expected = a.clone();
+        expected.sort();
+        quick_sort_3way_strings(&mut a);
+        assert_eq!(a, expected);
+    }
+}
@@ -0,0 +1,7 @@
+//! Strings
+//!
+//! Algorithms for processing strings, which are taken as sequences of bytes so that they work
+//! the same on text and on binary data.
+pub mod regex;
+pub mod substring;
+pub mod suffixarray;
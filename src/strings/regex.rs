@@ -0,0 +1,392 @@
+//! Regular Expressions
+//!
+//! A regular expression is compiled into a nondeterministic finite-state automaton (NFA) with
+//! one state per token of the expression and an accept state. A state that matches a byte has a
+//! match transition to the next state, and the parentheses, `|` and the closures add epsilon
+//! transitions, which are kept in a digraph. The simulation keeps the set of states that can be
+//! reached after reading each byte of the text, found by a search of the digraph, so that it
+//! takes time proportional to NM in the worst case.
+//!
+//! The expressions are made of:
+//! - bytes, which match themselves, and `\` followed by any byte, which matches that byte;
+//! - `.`, which matches any byte;
+//! - classes such as `[abc]`, `[a-z0-9]` and `[^ ]`, which match a byte in, or not in, the set;
+//! - concatenation, alternation with `|`, and grouping with parentheses;
+//! - the closures `*` (zero or more), `+` (one or more) and `?` (zero or one).
+use std::fmt::{self, Display};
+
+use crate::graphs::{digraph::Digraph, digraphsearch::DirectedDfs};
+
+/// Set of bytes matched by a state of the automaton
+#[derive(Clone, Debug, PartialEq)]
+enum ByteClass {
+    Any,
+    Byte(u8),
+    Set {
+        negated: bool,
+        ranges: Vec<(u8, u8)>,
+    },
+}
+
+impl ByteClass {
+    fn matches(&self, c: u8) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Byte(b) => *b == c,
+            Self::Set { negated, ranges } => {
+                ranges.iter().any(|&(lo, hi)| lo <= c && c <= hi) != *negated
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    LeftParen,
+    RightParen,
+    Or,
+    Star,
+    Plus,
+    Question,
+    Match(ByteClass),
+}
+
+/// Error in the syntax of a regular expression
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RegexError {
+    position: usize,
+    message: String,
+}
+
+impl RegexError {
+    fn new(position: usize, message: &str) -> Self {
+        Self {
+            position,
+            message: message.to_string(),
+        }
+    }
+
+    /// Byte offset in the expression where the error was found
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl Display for RegexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for RegexError {}
+
+/// Split the expression into tokens, checking that the parentheses are balanced and that each
+/// closure follows a byte, a class or a group
+fn tokenize(regexp: &[u8]) -> Result<Vec<Token>, RegexError> {
+    let mut tokens = vec![];
+    let mut depth = 0;
+    let mut i = 0;
+    while i < regexp.len() {
+        let token = match regexp[i] {
+            b'(' => {
+                depth += 1;
+                Token::LeftParen
+            }
+            b')' => {
+                if depth == 0 {
+                    return Err(RegexError::new(i, "unmatched `)`"));
+                }
+                depth -= 1;
+                Token::RightParen
+            }
+            b'|' => Token::Or,
+            c @ (b'*' | b'+' | b'?') => {
+                if !matches!(tokens.last(), Some(Token::Match(_) | Token::RightParen)) {
+                    return Err(RegexError::new(i, "nothing to repeat"));
+                }
+                match c {
+                    b'*' => Token::Star,
+                    b'+' => Token::Plus,
+                    _ => Token::Question,
+                }
+            }
+            b'.' => Token::Match(ByteClass::Any),
+            b'\\' => {
+                i += 1;
+                let &c = regexp
+                    .get(i)
+                    .ok_or_else(|| RegexError::new(i - 1, "trailing `\\`"))?;
+                Token::Match(ByteClass::Byte(c))
+            }
+            b'[' => {
+                let (class, end) = parse_class(regexp, i)?;
+                i = end;
+                Token::Match(class)
+            }
+            c => Token::Match(ByteClass::Byte(c)),
+        };
+        tokens.push(token);
+        i += 1;
+    }
+    if depth > 0 {
+        return Err(RegexError::new(regexp.len(), "unmatched `(`"));
+    }
+    Ok(tokens)
+}
+
+/// Parse the class that starts with the `[` at `start`, and return it with the position of the
+/// closing `]`
+fn parse_class(regexp: &[u8], start: usize) -> Result<(ByteClass, usize), RegexError> {
+    let unclosed = || RegexError::new(start, "unclosed `[`");
+    let mut i = start + 1;
+    let negated = regexp.get(i) == Some(&b'^');
+    if negated {
+        i += 1;
+    }
+    // Read one byte of the class, which may be escaped
+    let byte = |i: &mut usize| -> Result<u8, RegexError> {
+        if regexp.get(*i) == Some(&b'\\') {
+            *i += 1;
+        }
+        let c = *regexp.get(*i).ok_or_else(unclosed)?;
+        *i += 1;
+        Ok(c)
+    };
+    let mut ranges = vec![];
+    // A `]` right after the `[` or `[^` is a byte of the class
+    let mut first = true;
+    loop {
+        match regexp.get(i) {
+            None => return Err(unclosed()),
+            Some(b']') if !first => break,
+            _ => {}
+        }
+        first = false;
+        let position = i;
+        let lo = byte(&mut i)?;
+        let hi = if regexp.get(i) == Some(&b'-') && regexp.get(i + 1).is_some_and(|&c| c != b']') {
+            i += 1;
+            byte(&mut i)?
+        } else {
+            lo
+        };
+        if lo > hi {
+            return Err(RegexError::new(position, "invalid range"));
+        }
+        ranges.push((lo, hi));
+    }
+    Ok((ByteClass::Set { negated, ranges }, i))
+}
+
+/// Nondeterministic finite-state automaton that recognizes the texts matched by a regular
+/// expression
+#[derive(Clone, Debug)]
+pub struct Nfa {
+    regexp: String,
+    states: Vec<Token>, // the tokens of the expression, wrapped in parentheses
+    epsilon: Digraph,
+}
+
+impl Nfa {
+    /// Compile a regular expression
+    pub fn new(regexp: &str) -> Result<Self, RegexError> {
+        let mut states = vec![Token::LeftParen];
+        states.extend(tokenize(regexp.as_bytes())?);
+        states.push(Token::RightParen);
+
+        let m = states.len();
+        let mut epsilon = Digraph::new(m + 1);
+        // Positions of the `(` and `|` of the enclosing groups
+        let mut ops = vec![];
+        for i in 0..m {
+            // First state of the expression ending at i, which a closure goes back to
+            let mut lp = i;
+            match states[i] {
+                Token::LeftParen | Token::Or => ops.push(i),
+                Token::RightParen => {
+                    let mut ors = vec![];
+                    loop {
+                        let op = ops.pop().expect("parentheses are balanced");
+                        if states[op] == Token::Or {
+                            ors.push(op);
+                        } else {
+                            lp = op;
+                            break;
+                        }
+                    }
+                    for or in ors {
+                        epsilon.add_edge(lp, or + 1);
+                        epsilon.add_edge(or, i);
+                    }
+                }
+                _ => {}
+            }
+            match states.get(i + 1) {
+                Some(Token::Star) => {
+                    epsilon.add_edge(lp, i + 1);
+                    epsilon.add_edge(i + 1, lp);
+                }
+                Some(Token::Plus) => epsilon.add_edge(i + 1, lp),
+                Some(Token::Question) => epsilon.add_edge(lp, i + 1),
+                _ => {}
+            }
+            if matches!(
+                states[i],
+                Token::LeftParen | Token::RightParen | Token::Star | Token::Plus | Token::Question
+            ) {
+                epsilon.add_edge(i, i + 1);
+            }
+        }
+        Ok(Self {
+            regexp: regexp.to_string(),
+            states,
+            epsilon,
+        })
+    }
+
+    /// The regular expression
+    pub fn regexp(&self) -> &str {
+        &self.regexp
+    }
+
+    /// Number of states, including the accept state
+    pub fn states(&self) -> usize {
+        self.states.len() + 1
+    }
+
+    /// Digraph of the epsilon transitions
+    pub fn epsilon(&self) -> &Digraph {
+        &self.epsilon
+    }
+
+    /// Does the regular expression match the whole text?
+    pub fn recognizes<T: AsRef<[u8]> + ?Sized>(&self, text: &T) -> bool {
+        let m = self.states.len();
+        let mut dfs = DirectedDfs::new(&self.epsilon, 0);
+        for &c in text.as_ref() {
+            let matched: Vec<usize> = (0..m)
+                .filter(|&v| dfs.marked(v))
+                .filter(|&v| matches!(&self.states[v], Token::Match(class) if class.matches(c)))
+                .map(|v| v + 1)
+                .collect();
+            if matched.is_empty() {
+                return false;
+            }
+            dfs = DirectedDfs::from_sources(&self.epsilon, &matched);
+        }
+        dfs.marked(m)
+    }
+}
+
+/// Search for the lines of text that contain a match of a regular expression
+#[derive(Clone, Debug)]
+pub struct Grep {
+    nfa: Nfa,
+}
+
+impl Grep {
+    pub fn new(regexp: &str) -> Result<Self, RegexError> {
+        // Check the expression alone, so that the errors give positions in it
+        Nfa::new(regexp)?;
+        let nfa = Nfa::new(&format!("(.*({}).*)", regexp))?;
+        Ok(Self { nfa })
+    }
+
+    /// Does some substring of the text match the regular expression?
+    pub fn is_match<T: AsRef<[u8]> + ?Sized>(&self, text: &T) -> bool {
+        self.nfa.recognizes(text)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::{Grep, Nfa};
+
+    fn recognizes(regexp: &str, text: &str) -> bool {
+        Nfa::new(regexp).unwrap().recognizes(text)
+    }
+
+    #[test]
+    fn test_recognizes() {
+        let nfa = Nfa::new("((A*B|AC)D)").unwrap();
+        assert_eq!(nfa.states(), 14);
+        assert!(nfa.recognizes("AABD"));
+        assert!(nfa.recognizes("ACD"));
+        assert!(nfa.recognizes("BD"));
+        assert!(!nfa.recognizes("AAAC"));
+        assert!(!nfa.recognizes("ABCD"));
+
+        assert!(recognizes("(A|B|C)*", "ABCCBA"));
+        assert!(recognizes("(A|B|C)*", ""));
+        assert!(!recognizes("(A|B|C)*", "ABD"));
+        assert!(recognizes("A(B|)C", "AC"));
+        assert!(recognizes("(AB)+", "ABAB"));
+        assert!(!recognizes("(AB)+", ""));
+        assert!(recognizes("colou?r", "color"));
+        assert!(recognizes("colou?r", "colour"));
+        assert!(!recognizes("colou?r", "colouur"));
+        assert!(recognizes("a.c", "abc"));
+        assert!(!recognizes("a.c", "ac"));
+        assert!(recognizes("a\\.c", "a.c"));
+        assert!(!recognizes("a\\.c", "abc"));
+        assert!(recognizes("", ""));
+        assert!(!recognizes("", "a"));
+    }
+
+    #[test]
+    fn test_classes() {
+        assert!(recognizes("[a-z]+[0-9]*", "abc123"));
+        assert!(!recognizes("[a-z]+[0-9]*", "abc1x"));
+        assert!(recognizes("[^ ]+ [^ ]+", "two words"));
+        assert!(!recognizes("[^ ]+", "two words"));
+        assert!(recognizes("[]a]*", "]a]"));
+        assert!(recognizes("[a-]*", "-a-"));
+        assert!(recognizes("[\\]]", "]"));
+        assert!(recognizes("(0|[1-9][0-9]*)(\\.[0-9]+)?", "3.14"));
+        assert!(!recognizes("(0|[1-9][0-9]*)(\\.[0-9]+)?", "03"));
+    }
+
+    #[test]
+    fn test_errors() {
+        for (regexp, position) in [
+            ("(ab", 3),
+            ("ab)", 2),
+            ("*a", 0),
+            ("a|*", 2),
+            ("(+)", 1),
+            ("ab\\", 2),
+            ("a[bc", 1),
+            ("[z-a]", 1),
+        ] {
+            let e = Nfa::new(regexp).unwrap_err();
+            assert_eq!(e.position(), position, "{}", regexp);
+        }
+        assert_eq!(
+            Nfa::new("a)").unwrap_err().to_string(),
+            "unmatched `)` at position 1"
+        );
+        assert_eq!(Grep::new("a)").unwrap_err().position(), 1);
+    }
+
+    #[test]
+    fn test_grep() {
+        let text = fs::read_to_string("resources/tinyTale.txt").unwrap();
+        let grep = Grep::new("(wor|spr)[a-z]+").unwrap();
+        let lines: Vec<&str> = text.lines().filter(|line| grep.is_match(line)).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "it was the best of times it was the worst of times",
+                "it was the spring of hope it was the winter of despair",
+            ]
+        );
+        let grep = Grep::new("age|epoch").unwrap();
+        assert_eq!(text.lines().filter(|line| grep.is_match(line)).count(), 2);
+        assert!(Grep::new("").unwrap().is_match(""));
+        assert!(!Grep::new("x").unwrap().is_match(&text));
+    }
+}
@@ -0,0 +1,502 @@
+//! Substring Search
+//!
+//! Find the occurrences of a pattern of length M in a text of length N. Brute force checks the
+//! pattern at every position, in time ~NM in the worst case. Knuth-Morris-Pratt never backs up
+//! in the text and takes time proportional to N + M, Boyer-Moore scans the pattern from right to
+//! left and skips ahead, which takes ~N/M compares on typical text, and Rabin-Karp compares
+//! hashes of the pattern and of the window of the text, updated in constant time.
+use std::io::{self, BufRead};
+
+use rand::{thread_rng, Rng};
+
+/// Number of different bytes
+const R: usize = 256;
+
+/// Search for a pattern in texts given as byte slices or strings.
+///
+/// Positions are byte offsets, and the occurrences found by `find_all` may overlap. The empty
+/// pattern occurs at every position of the text, including its end.
+pub trait SubstringSearch {
+    /// The pattern searched for
+    fn pattern(&self) -> &[u8];
+
+    /// Position of the first occurrence of the pattern that starts at or after `from`
+    fn find_from(&self, text: &[u8], from: usize) -> Option<usize>;
+
+    /// Position of the first occurrence of the pattern in the text
+    fn find<T: AsRef<[u8]> + ?Sized>(&self, text: &T) -> Option<usize> {
+        self.find_from(text.as_ref(), 0)
+    }
+
+    /// Positions of all the occurrences of the pattern in the text
+    fn find_all<T: AsRef<[u8]> + ?Sized>(&self, text: &T) -> Vec<usize> {
+        let text = text.as_ref();
+        let mut positions = vec![];
+        let mut from = 0;
+        while let Some(i) = self.find_from(text, from) {
+            positions.push(i);
+            from = i + 1;
+        }
+        positions
+    }
+
+    /// Position of the first occurrence of the pattern in the text read from `reader`, which is
+    /// read no further than the end of the occurrence
+    fn find_in_reader<R: BufRead>(&self, reader: R) -> io::Result<Option<usize>> {
+        let mut first = None;
+        search_reader(self, reader, |i| {
+            first = Some(i);
+            false
+        })?;
+        Ok(first)
+    }
+
+    /// Positions of all the occurrences of the pattern in the text read from `reader`
+    fn find_all_in_reader<R: BufRead>(&self, reader: R) -> io::Result<Vec<usize>> {
+        let mut positions = vec![];
+        search_reader(self, reader, |i| {
+            positions.push(i);
+            true
+        })?;
+        Ok(positions)
+    }
+}
+
+/// Search the text read from `reader` a buffer at a time, keeping the last M - 1 bytes of the
+/// previous buffers for the occurrences that straddle two of them. `found` is called with the
+/// position of each occurrence and returns whether to go on.
+fn search_reader<S, R, F>(search: &S, mut reader: R, mut found: F) -> io::Result<()>
+where
+    S: SubstringSearch + ?Sized,
+    R: BufRead,
+    F: FnMut(usize) -> bool,
+{
+    let m = search.pattern().len();
+    let mut window: Vec<u8> = vec![];
+    let mut offset = 0; // position in the text of the start of the window
+    if m == 0 && !found(0) {
+        return Ok(());
+    }
+    loop {
+        let buffer = reader.fill_buf()?;
+        if buffer.is_empty() {
+            return Ok(());
+        }
+        let len = buffer.len();
+        window.extend_from_slice(buffer);
+        let mut from = 0;
+        while let Some(i) = search.find_from(&window, from) {
+            // The empty pattern was already found at the start of the window
+            if (m > 0 || i > 0) && !found(offset + i) {
+                reader.consume(i + m - (window.len() - len));
+                return Ok(());
+            }
+            from = i + 1;
+        }
+        reader.consume(len);
+        let keep = m.saturating_sub(1).min(window.len());
+        offset += window.len() - keep;
+        window.drain(..window.len() - keep);
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check the pattern at every position of the text
+#[derive(Clone, Debug)]
+pub struct BruteForce {
+    pattern: Vec<u8>,
+}
+
+impl BruteForce {
+    pub fn new<P: AsRef<[u8]> + ?Sized>(pattern: &P) -> Self {
+        Self {
+            pattern: pattern.as_ref().to_vec(),
+        }
+    }
+}
+
+impl SubstringSearch for BruteForce {
+    fn pattern(&self) -> &[u8] {
+        &self.pattern
+    }
+
+    fn find_from(&self, text: &[u8], from: usize) -> Option<usize> {
+        let m = self.pattern.len();
+        let n = text.len();
+        (from..(n + 1).checked_sub(m)?).find(|&i| (0..m).all(|j| text[i + j] == self.pattern[j]))
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Knuth-Morris-Pratt with a deterministic finite-state automaton.
+///
+/// State j means that the last j bytes of the text match the first j bytes of the pattern, and
+/// the DFA gives the next state for each byte, so that the search does one step per byte of the
+/// text. The DFA takes space proportional to RM.
+#[derive(Clone, Debug)]
+pub struct KmpDfa {
+    pattern: Vec<u8>,
+    dfa: Vec<[usize; R]>, // dfa[j][c] is the state after reading c in state j
+}
+
+impl KmpDfa {
+    pub fn new<P: AsRef<[u8]> + ?Sized>(pattern: &P) -> Self {
+        let pattern = pattern.as_ref().to_vec();
+        let m = pattern.len();
+        let mut dfa = vec![[0; R]; m];
+        if m > 0 {
+            dfa[0][pattern[0] as usize] = 1;
+            // x is the state of the DFA after reading pattern[1..j]
+            let mut x = 0;
+            for j in 1..m {
+                dfa[j] = dfa[x];
+                dfa[j][pattern[j] as usize] = j + 1;
+                x = dfa[x][pattern[j] as usize];
+            }
+        }
+        Self { pattern, dfa }
+    }
+}
+
+impl SubstringSearch for KmpDfa {
+    fn pattern(&self) -> &[u8] {
+        &self.pattern
+    }
+
+    fn find_from(&self, text: &[u8], from: usize) -> Option<usize> {
+        let m = self.pattern.len();
+        if m == 0 {
+            return (from <= text.len()).then_some(from);
+        }
+        let mut j = 0;
+        for (i, &c) in text.iter().enumerate().skip(from) {
+            j = self.dfa[j][c as usize];
+            if j == m {
+                return Some(i + 1 - m);
+            }
+        }
+        None
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Knuth-Morris-Pratt with a failure function.
+///
+/// On a mismatch after matching j bytes of the pattern, the search falls back to the length of
+/// the longest proper prefix of the pattern that is also a suffix of those j bytes. This takes
+/// space proportional to M only, and at most 2N steps.
+#[derive(Clone, Debug)]
+pub struct KmpFailure {
+    pattern: Vec<u8>,
+    failure: Vec<usize>, // failure[j] is the length of the longest border of pattern[..=j]
+}
+
+impl KmpFailure {
+    pub fn new<P: AsRef<[u8]> + ?Sized>(pattern: &P) -> Self {
+        let pattern = pattern.as_ref().to_vec();
+        let mut failure = vec![0; pattern.len()];
+        let mut k = 0;
+        for j in 1..pattern.len() {
+            while k > 0 && pattern[j] != pattern[k] {
+                k = failure[k - 1];
+            }
+            if pattern[j] == pattern[k] {
+                k += 1;
+            }
+            failure[j] = k;
+        }
+        Self { pattern, failure }
+    }
+}
+
+impl SubstringSearch for KmpFailure {
+    fn pattern(&self) -> &[u8] {
+        &self.pattern
+    }
+
+    fn find_from(&self, text: &[u8], from: usize) -> Option<usize> {
+        let m = self.pattern.len();
+        if m == 0 {
+            return (from <= text.len()).then_some(from);
+        }
+        let mut k = 0;
+        for (i, &c) in text.iter().enumerate().skip(from) {
+            while k > 0 && c != self.pattern[k] {
+                k = self.failure[k - 1];
+            }
+            if c == self.pattern[k] {
+                k += 1;
+            }
+            if k == m {
+                return Some(i + 1 - m);
+            }
+        }
+        None
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Boyer-Moore with the bad-character rule.
+///
+/// The pattern is compared from right to left, and on a mismatch with byte c of the text the
+/// pattern is moved to align c with its rightmost occurrence in the pattern, or past c if it
+/// does not occur.
+#[derive(Clone, Debug)]
+pub struct BoyerMoore {
+    pattern: Vec<u8>,
+    right: [Option<usize>; R], // rightmost position of each byte in the pattern
+}
+
+impl BoyerMoore {
+    pub fn new<P: AsRef<[u8]> + ?Sized>(pattern: &P) -> Self {
+        let pattern = pattern.as_ref().to_vec();
+        let mut right = [None; R];
+        for (j, &c) in pattern.iter().enumerate() {
+            right[c as usize] = Some(j);
+        }
+        Self { pattern, right }
+    }
+}
+
+impl SubstringSearch for BoyerMoore {
+    fn pattern(&self) -> &[u8] {
+        &self.pattern
+    }
+
+    fn find_from(&self, text: &[u8], from: usize) -> Option<usize> {
+        let m = self.pattern.len();
+        let mut i = from;
+        while i + m <= text.len() {
+            let mismatch = (0..m).rev().find(|&j| self.pattern[j] != text[i + j]);
+            match mismatch {
+                None => return Some(i),
+                Some(j) => {
+                    i += match self.right[text[i + j] as usize] {
+                        Some(r) if r < j => j - r,
+                        Some(_) => 1,
+                        None => j + 1,
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Rabin-Karp fingerprint search.
+///
+/// The hashes are taken modulo a random prime Q of about 31 bits, so that two different strings
+/// have the same hash with a probability of about 1/Q. The Monte Carlo version trusts equal
+/// hashes, and may report a false match; the Las Vegas version checks the bytes, and is always
+/// right but may be slow.
+#[derive(Clone, Debug)]
+pub struct RabinKarp {
+    pattern: Vec<u8>,
+    q: u64,
+    rm: u64, // R^(M-1) modulo Q
+    hash: u64,
+    las_vegas: bool,
+}
+
+impl RabinKarp {
+    /// Monte Carlo search
+    pub fn new<P: AsRef<[u8]> + ?Sized>(pattern: &P) -> Self {
+        Self::with_rng(pattern, &mut thread_rng(), false)
+    }
+
+    /// Las Vegas search, which checks the bytes of each match of the hashes
+    pub fn las_vegas<P: AsRef<[u8]> + ?Sized>(pattern: &P) -> Self {
+        Self::with_rng(pattern, &mut thread_rng(), true)
+    }
+
+    /// Monte Carlo or Las Vegas search, with the prime drawn from the given random number
+    /// generator: a seeded generator makes the search deterministic
+    pub fn with_rng<P: AsRef<[u8]> + ?Sized, G: Rng>(
+        pattern: &P,
+        rng: &mut G,
+        las_vegas: bool,
+    ) -> Self {
+        Self::with_prime(pattern.as_ref(), random_prime(rng), las_vegas)
+    }
+
+    fn with_prime(pattern: &[u8], q: u64, las_vegas: bool) -> Self {
+        let r = R as u64;
+        let rm = (1..pattern.len()).fold(1, |rm, _| rm * r % q);
+        Self {
+            pattern: pattern.to_vec(),
+            q,
+            rm,
+            hash: hash(pattern, q),
+            las_vegas,
+        }
+    }
+
+    /// Is this the Las Vegas version?
+    pub fn is_las_vegas(&self) -> bool {
+        self.las_vegas
+    }
+
+    fn check(&self, text: &[u8], i: usize) -> bool {
+        !self.las_vegas || text[i..i + self.pattern.len()] == self.pattern[..]
+    }
+}
+
+impl SubstringSearch for RabinKarp {
+    fn pattern(&self) -> &[u8] {
+        &self.pattern
+    }
+
+    fn find_from(&self, text: &[u8], from: usize) -> Option<usize> {
+        let m = self.pattern.len();
+        let n = text.len();
+        if from + m > n {
+            return None;
+        }
+        let (r, q) = (R as u64, self.q);
+        let mut h = hash(&text[from..from + m], q);
+        if h == self.hash && self.check(text, from) {
+            return Some(from);
+        }
+        for i in from + m..n {
+            // Remove the leading byte and add the trailing one
+            h = (h + q - self.rm * text[i - m] as u64 % q) % q;
+            h = (h * r + text[i] as u64) % q;
+            if h == self.hash && self.check(text, i + 1 - m) {
+                return Some(i + 1 - m);
+            }
+        }
+        None
+    }
+}
+
+/// Horner's method for the hash of `key` modulo `q`
+fn hash(key: &[u8], q: u64) -> u64 {
+    key.iter().fold(0, |h, &c| (h * R as u64 + c as u64) % q)
+}
+
+/// Random prime between 2^30 and 2^31, found by trial division
+fn random_prime<G: Rng>(rng: &mut G) -> u64 {
+    loop {
+        let n: u64 = rng.gen_range(1 << 30..1 << 31) | 1;
+        if (3..)
+            .step_by(2)
+            .take_while(|d| d * d <= n)
+            .all(|d| !n.is_multiple_of(d))
+        {
+            return n;
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use std::io::{BufReader, Read};
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::{BoyerMoore, BruteForce, KmpDfa, KmpFailure, RabinKarp, SubstringSearch};
+
+    const TEXT: &str = "abacadabrabracabracadabrabrabracad";
+
+    /// Check a searcher against brute force on the given pattern and text
+    fn check<S: SubstringSearch>(search: &S, text: &[u8]) {
+        let expected = BruteForce::new(search.pattern()).find_all(text);
+        assert_eq!(search.find_all(text), expected);
+        assert_eq!(search.find(text), expected.first().copied());
+        for capacity in [1, 2, 5, 64] {
+            let reader = BufReader::with_capacity(capacity, text);
+            assert_eq!(search.find_all_in_reader(reader).unwrap(), expected);
+            let mut reader = BufReader::with_capacity(capacity, text);
+            let first = search.find_in_reader(&mut reader).unwrap();
+            assert_eq!(first, expected.first().copied());
+            // The reader stops at the end of the first occurrence
+            if let Some(i) = first {
+                let mut rest = vec![];
+                reader.read_to_end(&mut rest).unwrap();
+                assert_eq!(rest, &text[i + search.pattern().len()..]);
+            }
+        }
+    }
+
+    fn check_all(pattern: &[u8], text: &[u8]) {
+        let mut rng = StdRng::seed_from_u64(1);
+        check(&BruteForce::new(pattern), text);
+        check(&KmpDfa::new(pattern), text);
+        check(&KmpFailure::new(pattern), text);
+        check(&BoyerMoore::new(pattern), text);
+        check(&RabinKarp::with_rng(pattern, &mut rng, false), text);
+        check(&RabinKarp::with_rng(pattern, &mut rng, true), text);
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(KmpDfa::new("abracadabra").find(TEXT), Some(14));
+        assert_eq!(KmpFailure::new("abracadabra").find(TEXT), Some(14));
+        assert_eq!(BoyerMoore::new("abracadabra").find(TEXT), Some(14));
+        assert_eq!(RabinKarp::las_vegas("abracadabra").find(TEXT), Some(14));
+        assert_eq!(
+            BruteForce::new("abra").find_all(TEXT),
+            vec![6, 9, 14, 21, 24, 27]
+        );
+        assert_eq!(BoyerMoore::new(b"bracad").find_all(TEXT), vec![15, 28]);
+        assert_eq!(KmpDfa::new("aa").find_all("aaaa"), vec![0, 1, 2]);
+        assert_eq!(BoyerMoore::new("abd").find(TEXT), None);
+        assert_eq!(RabinKarp::new("longer than the text").find("text"), None);
+        assert_eq!(KmpFailure::new("").find_all("ab"), vec![0, 1, 2]);
+        for pattern in ["abra", "a", "ab", "cad", "zz", "", TEXT] {
+            check_all(pattern.as_bytes(), TEXT.as_bytes());
+        }
+        check_all(b"", b"");
+        check_all(b"a", b"");
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..200 {
+            let text: Vec<u8> = (0..rng.gen_range(0..100))
+                .map(|_| rng.gen_range(b'a'..b'd'))
+                .collect();
+            let pattern: Vec<u8> = (0..rng.gen_range(1..6))
+                .map(|_| rng.gen_range(b'a'..b'd'))
+                .collect();
+            check_all(&pattern, &text);
+        }
+    }
+
+    #[test]
+    fn test_binary() {
+        let text: Vec<u8> = (0..=255).chain(0..=255).collect();
+        let pattern = [254, 255, 0, 1];
+        assert_eq!(KmpDfa::new(&pattern).find_all(&text), vec![254]);
+        check_all(&pattern, &text);
+    }
+
+    #[test]
+    fn test_rabin_karp_collision() {
+        // Modulo 2 the hashes of "ab" and "cd" are the same, so Monte Carlo reports a false match
+        let monte_carlo = RabinKarp::with_prime(b"ab", 2, false);
+        let las_vegas = RabinKarp::with_prime(b"ab", 2, true);
+        assert!(!monte_carlo.is_las_vegas());
+        assert_eq!(monte_carlo.find("cdab"), Some(0));
+        assert_eq!(las_vegas.find("cdab"), Some(2));
+    }
+
+    #[test]
+    fn test_rabin_karp_with_rng() {
+        // the same seed gives the same prime
+        let a = RabinKarp::with_rng("abra", &mut StdRng::seed_from_u64(7), false);
+        let b = RabinKarp::with_rng("abra", &mut StdRng::seed_from_u64(7), true);
+        assert_eq!(a.q, b.q);
+        assert!(b.is_las_vegas());
+        assert_eq!(a.find_all(TEXT), b.find_all(TEXT));
+    }
+}
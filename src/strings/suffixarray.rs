@@ -0,0 +1,330 @@
+//! Suffix Arrays
+//!
+//! The suffix array of a text is the sorted list of its suffixes, kept as their starting
+//! positions. Suffixes that share a prefix are next to each other, so that finding the
+//! occurrences of a key is a binary search, and the longest repeated substring is the longest
+//! common prefix of two neighbouring suffixes.
+//!
+//! The array is built by prefix doubling (Manber-Myers): after the pass for length k the
+//! suffixes are sorted by their first k symbols, and the next pass sorts them by their first 2k
+//! symbols with two stable counting sorts on the pairs of ranks of their two halves. Each pass
+//! takes linear time, and there are at most lg N passes. The longest common prefixes of the
+//! neighbouring suffixes are then found in linear time by Kasai's algorithm.
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+use crate::symboltables::frequencycounter::Words;
+
+/// Sort the suffixes of `s`, whose symbols are less than `alphabet`
+fn suffix_array<T: Copy + Into<usize>>(s: &[T], alphabet: usize) -> Vec<usize> {
+    let n = s.len();
+    if n == 0 {
+        return vec![];
+    }
+    let mut rank: Vec<usize> = s.iter().map(|&c| c.into()).collect();
+    let mut sa: Vec<usize> = (0..n).collect();
+    counting_sort(&mut sa, (0..n).collect(), &rank, alphabet);
+    let mut next = vec![0; n];
+    let mut k = 1;
+    loop {
+        // Sort by the rank of the second half first: the suffixes shorter than k have an empty
+        // second half, and then come the others in the order of their second half
+        let by_second = (n.saturating_sub(k)..n)
+            .chain(sa.iter().filter(|&&i| i >= k).map(|&i| i - k))
+            .collect();
+        counting_sort(&mut sa, by_second, &rank, alphabet.max(n));
+
+        let key = |i: usize| (rank[i], rank.get(i + k));
+        next[sa[0]] = 0;
+        for j in 1..n {
+            next[sa[j]] = next[sa[j - 1]] + (key(sa[j - 1]) != key(sa[j])) as usize;
+        }
+        std::mem::swap(&mut rank, &mut next);
+        if rank[sa[n - 1]] == n - 1 {
+            break;
+        }
+        k *= 2;
+    }
+    sa
+}
+
+/// Stable sort of `order` by `rank`, into `sa`
+fn counting_sort(sa: &mut [usize], order: Vec<usize>, rank: &[usize], r: usize) {
+    let mut count = vec![0; r + 1];
+    for &i in &order {
+        count[rank[i] + 1] += 1;
+    }
+    for r in 0..r {
+        count[r + 1] += count[r];
+    }
+    for i in order {
+        sa[count[rank[i]]] = i;
+        count[rank[i]] += 1;
+    }
+}
+
+/// Longest common prefix of each suffix and the one before it in the suffix array, by Kasai's
+/// algorithm: the prefix shared by the suffix at i + 1 and its predecessor is at most one
+/// shorter than the one shared by the suffix at i and its predecessor.
+fn lcp_array<T: Eq>(s: &[T], sa: &[usize]) -> Vec<usize> {
+    let n = s.len();
+    let mut inverse = vec![0; n];
+    for (r, &i) in sa.iter().enumerate() {
+        inverse[i] = r;
+    }
+    let mut lcp = vec![0; n];
+    let mut h = 0;
+    for i in 0..n {
+        if inverse[i] == 0 {
+            h = 0;
+            continue;
+        }
+        let j = sa[inverse[i] - 1];
+        while i + h < n && j + h < n && s[i + h] == s[j + h] {
+            h += 1;
+        }
+        lcp[inverse[i]] = h;
+        h = h.saturating_sub(1);
+    }
+    lcp
+}
+
+/// Sorted suffixes of a text, with the longest common prefixes of neighbouring suffixes
+#[derive(Clone, Debug)]
+pub struct SuffixArray {
+    text: Vec<u8>,
+    index: Vec<usize>, // index[i] is the position of the i-th smallest suffix
+    lcp: Vec<usize>,   // lcp[i] is the length of the prefix shared by suffixes i - 1 and i
+}
+
+impl SuffixArray {
+    pub fn new<T: AsRef<[u8]> + ?Sized>(text: &T) -> Self {
+        let text = text.as_ref().to_vec();
+        let index = suffix_array(&text, 256);
+        let lcp = lcp_array(&text, &index);
+        Self { text, index, lcp }
+    }
+
+    /// Build the suffix array of the words read from `reader`, separated by single spaces
+    pub fn from_reader<R: BufRead>(reader: R) -> io::Result<Self> {
+        let words = Words::new(reader).collect::<io::Result<Vec<String>>>()?;
+        Ok(Self::new(&words.join(" ")))
+    }
+
+    /// Build the suffix array of the words of a file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    pub fn text(&self) -> &[u8] {
+        &self.text
+    }
+
+    /// Number of suffixes, which is the length of the text
+    pub fn len(&self) -> usize {
+        self.text.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Position in the text of the i-th smallest suffix
+    pub fn index(&self, i: usize) -> usize {
+        self.index[i]
+    }
+
+    /// The i-th smallest suffix
+    pub fn select(&self, i: usize) -> &[u8] {
+        &self.text[self.index[i]..]
+    }
+
+    /// Length of the longest common prefix of the i-th smallest suffix and the one before it,
+    /// or 0 for the smallest suffix
+    pub fn lcp(&self, i: usize) -> usize {
+        self.lcp[i]
+    }
+
+    /// Number of suffixes smaller than `key`
+    pub fn rank<K: AsRef<[u8]> + ?Sized>(&self, key: &K) -> usize {
+        let key = key.as_ref();
+        self.index.partition_point(|&i| &self.text[i..] < key)
+    }
+
+    /// Positions of all the occurrences of `key` in the text, in the order of their suffixes
+    pub fn occurrences<K: AsRef<[u8]> + ?Sized>(&self, key: &K) -> &[usize] {
+        let key = key.as_ref();
+        let first = self.rank(key);
+        let count = self.index[first..].partition_point(|&i| self.text[i..].starts_with(key));
+        &self.index[first..first + count]
+    }
+
+    /// Longest substring that occurs at least twice in the text, the first one in the order of
+    /// the suffixes if there are several. The occurrences may overlap.
+    pub fn longest_repeated_substring(&self) -> &[u8] {
+        let best = (1..self.len()).rev().max_by_key(|&i| self.lcp[i]);
+        match best {
+            Some(i) => &self.select(i)[..self.lcp[i]],
+            None => &[],
+        }
+    }
+
+    /// Keyword in context: each occurrence of `key` with up to `context` bytes of text on either
+    /// side, in the order of their suffixes
+    pub fn keyword_in_context<K: AsRef<[u8]> + ?Sized>(
+        &self,
+        key: &K,
+        context: usize,
+    ) -> Vec<&[u8]> {
+        let m = key.as_ref().len();
+        self.occurrences(key)
+            .iter()
+            .map(|&i| &self.text[i.saturating_sub(context)..(i + m + context).min(self.len())])
+            .collect()
+    }
+}
+
+/// Longest substring of `a` that also occurs in `b`, or one of them if there are several.
+///
+/// The suffixes of a, a separator that is not a byte, and b are sorted together, and the
+/// longest common prefix of two neighbouring suffixes that start in different texts is a common
+/// substring, which stops before the separator.
+pub fn longest_common_substring<'a, A, B>(a: &'a A, b: &B) -> &'a [u8]
+where
+    A: AsRef<[u8]> + ?Sized,
+    B: AsRef<[u8]> + ?Sized,
+{
+    let (a, b) = (a.as_ref(), b.as_ref());
+    const SEPARATOR: u16 = 256;
+    let s: Vec<u16> = a
+        .iter()
+        .map(|&c| c as u16)
+        .chain([SEPARATOR])
+        .chain(b.iter().map(|&c| c as u16))
+        .collect();
+    let sa = suffix_array(&s, SEPARATOR as usize + 1);
+    let lcp = lcp_array(&s, &sa);
+
+    let mut best = (0, 0); // length and position in a
+    for i in 1..s.len() {
+        let (p, q) = (sa[i - 1], sa[i]);
+        let in_a = if p < a.len() && q > a.len() {
+            p
+        } else if q < a.len() && p > a.len() {
+            q
+        } else {
+            continue;
+        };
+        if lcp[i] > best.0 {
+            best = (lcp[i], in_a);
+        }
+    }
+    &a[best.1..best.1 + best.0]
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::{longest_common_substring, SuffixArray};
+
+    #[test]
+    fn test_suffix_array() {
+        let sa = SuffixArray::new("ABRACADABRA!");
+        let index: Vec<usize> = (0..sa.len()).map(|i| sa.index(i)).collect();
+        assert_eq!(index, vec![11, 10, 7, 0, 3, 5, 8, 1, 4, 6, 9, 2]);
+        let lcp: Vec<usize> = (0..sa.len()).map(|i| sa.lcp(i)).collect();
+        assert_eq!(lcp, vec![0, 0, 1, 4, 1, 1, 0, 3, 0, 0, 0, 2]);
+        assert_eq!(sa.select(3), b"ABRACADABRA!");
+        assert_eq!(sa.rank("ABRA"), 2);
+        assert_eq!(sa.rank("ABRACADABRA!"), 3);
+        assert_eq!(sa.rank("Z"), 12);
+        assert_eq!(sa.occurrences("BRA"), &[8, 1]);
+        assert!(sa.occurrences("BRAB").is_empty());
+        assert_eq!(sa.longest_repeated_substring(), b"ABRA");
+
+        let empty = SuffixArray::new("");
+        assert!(empty.is_empty());
+        assert_eq!(empty.longest_repeated_substring(), b"");
+        assert_eq!(empty.rank("a"), 0);
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..100 {
+            let alphabet = rng.gen_range(1..5);
+            let text: Vec<u8> = (0..rng.gen_range(0..200))
+                .map(|_| rng.gen_range(b'a'..b'a' + alphabet))
+                .collect();
+            let sa = SuffixArray::new(&text);
+
+            let mut expected: Vec<usize> = (0..text.len()).collect();
+            expected.sort_by_key(|&i| &text[i..]);
+            for (r, &i) in expected.iter().enumerate() {
+                assert_eq!(sa.index(r), i);
+                if r > 0 {
+                    let previous = &text[expected[r - 1]..];
+                    let shared = previous.iter().zip(&text[i..]).take_while(|(a, b)| a == b);
+                    assert_eq!(sa.lcp(r), shared.count());
+                }
+            }
+
+            let lrs = sa.longest_repeated_substring();
+            assert!(lrs.is_empty() || sa.occurrences(lrs).len() >= 2);
+            let longer = (0..text.len().saturating_sub(lrs.len()))
+                .map(|i| &text[i..i + lrs.len() + 1])
+                .any(|s| sa.occurrences(s).len() >= 2);
+            assert!(!longer);
+        }
+    }
+
+    #[test]
+    fn test_longest_common_substring() {
+        assert_eq!(
+            longest_common_substring("ABRACADABRA", "XCADABRY"),
+            b"CADABR"
+        );
+        assert_eq!(longest_common_substring("abc", "xyz"), b"");
+        assert_eq!(longest_common_substring("", "abc"), b"");
+        assert_eq!(longest_common_substring("aXbX", "bYaY").len(), 1);
+
+        let mut rng = StdRng::seed_from_u64(2);
+        for _ in 0..100 {
+            let mut random = |len| -> Vec<u8> {
+                (0..rng.gen_range(0..len))
+                    .map(|_| rng.gen_range(b'a'..b'd'))
+                    .collect()
+            };
+            let (a, b) = (random(40), random(40));
+            let lcs = longest_common_substring(&a, &b);
+            let sb = SuffixArray::new(&b);
+            assert!(lcs.is_empty() || !sb.occurrences(lcs).is_empty());
+            let longer = (0..a.len().saturating_sub(lcs.len()))
+                .any(|i| !sb.occurrences(&a[i..i + lcs.len() + 1]).is_empty());
+            assert!(!longer);
+        }
+    }
+
+    #[test]
+    fn test_keyword_in_context() {
+        let sa = SuffixArray::from_file("resources/tinyTale.txt").unwrap();
+        assert!(sa
+            .text()
+            .starts_with(b"it was the best of times it was the worst"));
+        assert_eq!(sa.occurrences("it was").len(), 10);
+        let contexts = sa.keyword_in_context("wisdom", 8);
+        assert_eq!(contexts, vec![&b" age of wisdom it was "[..]]);
+        let contexts = sa.keyword_in_context("epoch of", 4);
+        assert_eq!(
+            contexts,
+            vec![&b"the epoch of bel"[..], &b"the epoch of inc"[..]]
+        );
+        assert!(sa.keyword_in_context("better", 4).is_empty());
+    }
+}
@@ -1,6 +1,10 @@
 //! Symbol Tables
 pub mod balancedtree;
 pub mod binarysearchtree;
+pub mod experiments;
 pub mod frequencycounter;
+pub mod hashtable;
 pub mod item;
+pub mod statistics;
 pub mod symboltable;
+pub mod trie;
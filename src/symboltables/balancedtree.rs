@@ -1,25 +1,39 @@
 #![allow(unused)]
 
-use std::{cell::RefCell, cmp::Ordering, fmt::Debug, rc::Rc};
+use std::{cell::RefCell, cmp::Ordering, fmt::Debug, mem, rc::Rc};
+
+use super::{
+    statistics::{Operation, OperationCounter, TreeShape},
+    symboltable::KeyValueSymbolTable,
+};
+
+// Keeps `balancedtree::SymbolTableStatistics` paths working for code that names the statistics
+// of the tree from this module
+pub use super::statistics::SymbolTableStatistics;
 
 type NodePtr<K, V> = Rc<RefCell<Node<K, V>>>;
 type Link<K, V> = Option<NodePtr<K, V>>;
 
+const RED: bool = true;
+const BLACK: bool = false;
+
 #[derive(Debug)]
 struct Node<K, V> {
     key: K,
     value: V,
-    n: usize, // nodes in subtree rooted here
+    n: usize,    // nodes in subtree rooted here
+    color: bool, // color of the link from the parent
     left: Link<K, V>,
     right: Link<K, V>,
 }
 
 impl<K, V> Node<K, V> {
-    pub fn new(key: K, value: V, n: usize) -> NodePtr<K, V> {
+    pub fn new(key: K, value: V, n: usize, color: bool) -> NodePtr<K, V> {
         let node = Self {
             key,
             value,
             n,
+            color,
             left: None,
             right: None,
         };
@@ -27,30 +41,18 @@ impl<K, V> Node<K, V> {
     }
 }
 
-#[derive(Debug, Default)]
-pub struct SymbolTableStatistics {
-    average_put_cost: f64,
-}
-
-impl SymbolTableStatistics {
-    pub fn new(compares_put: usize, total_puts: usize) -> Self {
-        // The average cost of a put operation is:
-        // 1 + the total number of comparisons done during puts divided by the total number of
-        // put operations.
-        //
-        // The theoretical value is ~ 1.39 lg N
-        // See: Algorithms, 4th edition, Robert Sedgewick, Kevin Wayne, Addition-Wesley, 2011
-        //
-        let average_put_cost = 1.0 + compares_put as f64 / total_puts as f64;
-        Self { average_put_cost }
-    }
-}
-
+/// A symbol table implemented as a left-leaning red-black BST.
+///
+/// The tree is a 2-3 tree in which a 3-node is a pair of nodes joined by a red link that leans
+/// left. Every path from the root to a null link has the same number of black links, so the
+/// height of the tree is at most 2 lg N and search, insert and delete take logarithmic time in
+/// the worst case. Put and delete restore the balance on the way up from the bottom of the tree
+/// with rotations and color flips.
 #[derive(Default, Debug)]
 pub struct BalancedTree<K, V> {
     root: Link<K, V>, // root of the tree
-    // Number of compares for the put operation
-    compares_put: usize,
+    // Number of operations and compares
+    counter: OperationCounter,
 }
 
 impl<K, V> BalancedTree<K, V>
@@ -61,7 +63,7 @@ where
     pub fn new() -> Self {
         Self {
             root: None,
-            compares_put: 0,
+            counter: OperationCounter::new(),
         }
     }
 
@@ -100,19 +102,106 @@ where
         self.get(key).is_some()
     }
 
-    /// Delete the key (and value) from the table
-    pub fn delete(&self, key: K) {
-        todo!()
+    /// Delete the key (and value) from the table, if it is there
+    ///
+    /// On the way down, the tree is transformed so that the current node is not a 2-node, by
+    /// borrowing a key from a sibling or merging with it. The key is then deleted from a 3-node
+    /// or a 4-node at the bottom of the tree (a node with two children is first replaced by its
+    /// successor), and the balance is restored on the way up.
+    pub fn delete(&mut self, key: K) {
+        self.counter.operation(Operation::Delete);
+        if self.find(&key, Operation::Delete).is_none() {
+            return;
+        }
+        let root = self.root.take().expect("the key is in the tree");
+        self.root = self.delete_r(BalancedTree::red_root(root), &key);
+        BalancedTree::set_black(&self.root);
+    }
+
+    fn delete_r(&self, mut h: NodePtr<K, V>, key: &K) -> Link<K, V> {
+        self.counter.compare(Operation::Delete);
+        if *key < h.borrow().key {
+            if !BalancedTree::is_red(&h.borrow().left)
+                && !BalancedTree::is_left_red(&h.borrow().left)
+            {
+                h = self.move_red_left(h);
+            }
+            let left = h.borrow_mut().left.take().expect("the key is in the tree");
+            let left = self.delete_r(left, key);
+            h.borrow_mut().left = left;
+        } else {
+            if BalancedTree::is_red(&h.borrow().left) {
+                h = self.rotate_right(h);
+            }
+            if *key == h.borrow().key && h.borrow().right.is_none() {
+                return None;
+            }
+            if !BalancedTree::is_red(&h.borrow().right)
+                && !BalancedTree::is_left_red(&h.borrow().right)
+            {
+                h = self.move_red_right(h);
+            }
+            let right = h.borrow_mut().right.take().expect("the key is in the tree");
+            let right = if *key == h.borrow().key {
+                let (successor, value) = BalancedTree::min_entry(&right);
+                let mut node = h.borrow_mut();
+                node.key = successor;
+                node.value = value;
+                self.delete_min_r(right)
+            } else {
+                self.delete_r(right, key)
+            };
+            h.borrow_mut().right = right;
+        }
+        Some(self.balance(h))
     }
 
     /// Delete the largest key (and value) from the table
-    pub fn delete_max(&self) {
-        todo!()
+    ///
+    /// Panics if the table is empty.
+    pub fn delete_max(&mut self) {
+        self.counter.operation(Operation::Delete);
+        let root = self.root.take().expect("Empty tree");
+        self.root = self.delete_max_r(BalancedTree::red_root(root));
+        BalancedTree::set_black(&self.root);
+    }
+
+    fn delete_max_r(&self, mut h: NodePtr<K, V>) -> Link<K, V> {
+        if BalancedTree::is_red(&h.borrow().left) {
+            h = self.rotate_right(h);
+        }
+        // the largest node is now a leaf, which is deleted
+        h.borrow().right.as_ref()?;
+        if !BalancedTree::is_red(&h.borrow().right) && !BalancedTree::is_left_red(&h.borrow().right)
+        {
+            h = self.move_red_right(h);
+        }
+        let right = h.borrow_mut().right.take().expect("a right child");
+        let right = self.delete_max_r(right);
+        h.borrow_mut().right = right;
+        Some(self.balance(h))
     }
 
     /// Delete the smallest key (and value) from the table
-    pub fn delete_min(&self) {
-        todo!()
+    ///
+    /// Panics if the table is empty.
+    pub fn delete_min(&mut self) {
+        self.counter.operation(Operation::Delete);
+        let root = self.root.take().expect("Empty tree");
+        self.root = self.delete_min_r(BalancedTree::red_root(root));
+        BalancedTree::set_black(&self.root);
+    }
+
+    fn delete_min_r(&self, mut h: NodePtr<K, V>) -> Link<K, V> {
+        // the smallest node is a leaf, which is deleted
+        h.borrow().left.as_ref()?;
+        if !BalancedTree::is_red(&h.borrow().left) && !BalancedTree::is_left_red(&h.borrow().left) {
+            h = self.move_red_left(h);
+        }
+        let left = h.borrow_mut().left.take().expect("a left child");
+        let left = self.delete_min_r(left);
+        h.borrow_mut().left = left;
+        Some(self.balance(h))
     }
 
     /// Return the largest key <= to the given key.
@@ -147,18 +236,25 @@ where
 
     /// Return the value that corresponds to the given key
     pub fn get(&self, key: K) -> Option<V> {
-        BalancedTree::get_r(&self.root, key)
-    }
-
-    fn get_r(link: &Link<K, V>, key: K) -> Option<V> {
-        match link {
-            Some(node) => match key.cmp(&node.borrow().key) {
-                Ordering::Less => BalancedTree::get_r(&node.borrow().left, key),
-                Ordering::Equal => Some(node.borrow().value.clone()),
-                Ordering::Greater => BalancedTree::get_r(&node.borrow().right, key),
-            },
-            None => None,
+        self.counter.operation(Operation::Get);
+        let node = self.find(&key, Operation::Get)?;
+        let value = node.borrow().value.clone();
+        Some(value)
+    }
+
+    // Return the node of the key, counting the compares as done by the given operation
+    fn find(&self, key: &K, operation: Operation) -> Link<K, V> {
+        let mut link = self.root.clone();
+        while let Some(node) = link {
+            self.counter.compare(operation);
+            let ordering = key.cmp(&node.borrow().key);
+            link = match ordering {
+                Ordering::Less => node.borrow().left.clone(),
+                Ordering::Equal => return Some(node),
+                Ordering::Greater => node.borrow().right.clone(),
+            };
         }
+        None
     }
 
     /// Is the table empty or not?
@@ -174,19 +270,33 @@ where
     }
 
     fn keys_r(link: &Link<K, V>, acc: &mut Vec<K>) {
-        match link {
-            Some(node) => {
-                BalancedTree::keys_r(&node.borrow().left, acc);
-                acc.push(node.borrow().key.clone());
-                BalancedTree::keys_r(&node.borrow().right, acc);
-            }
-            None => {}
+        if let Some(node) = link {
+            BalancedTree::keys_r(&node.borrow().left, acc);
+            acc.push(node.borrow().key.clone());
+            BalancedTree::keys_r(&node.borrow().right, acc);
         }
     }
 
     /// Return keys in [lo..hi] in sorted order
     pub fn keys_in_range(&self, lo: K, hi: K) -> Vec<K> {
-        todo!()
+        let mut result = vec![];
+        BalancedTree::keys_in_range_r(&self.root, &lo, &hi, &mut result);
+        result
+    }
+
+    fn keys_in_range_r(link: &Link<K, V>, lo: &K, hi: &K, acc: &mut Vec<K>) {
+        if let Some(node) = link {
+            let node = node.borrow();
+            if *lo < node.key {
+                BalancedTree::keys_in_range_r(&node.left, lo, hi, acc);
+            }
+            if *lo <= node.key && node.key <= *hi {
+                acc.push(node.key.clone());
+            }
+            if node.key < *hi {
+                BalancedTree::keys_in_range_r(&node.right, lo, hi, acc);
+            }
+        }
     }
 
     /// Return the largest key.
@@ -241,40 +351,60 @@ where
 
     /// Put the key, value pair into the table. Update the value if found, if not add the
     /// new key value pair.
+    ///
+    /// The new key is added at the bottom of the tree with a red link, and the balance is
+    /// restored on the way up.
     pub fn put(&mut self, key: K, value: V) {
-        BalancedTree::put_r(&mut self.root, key, value, &mut self.compares_put);
+        self.counter.operation(Operation::Put);
+        let root = self.root.take();
+        self.root = Some(self.put_r(root, key, value));
+        BalancedTree::set_black(&self.root);
     }
 
-    fn put_r(link: &mut Link<K, V>, key: K, value: V, compares_put: &mut usize) {
-        match link {
-            Some(node) => {
-                // store the ordering in a temporary to avoid overlapping borrows.
-                let ordering = key.cmp(&node.borrow().key);
-                *compares_put += 1;
-                match ordering {
-                    Ordering::Less => {
-                        BalancedTree::put_r(&mut node.borrow_mut().left, key, value, compares_put);
-                    }
-                    Ordering::Equal => {
-                        node.borrow_mut().value = value;
-                    }
-                    Ordering::Greater => {
-                        BalancedTree::put_r(&mut node.borrow_mut().right, key, value, compares_put);
-                    }
-                }
-                let left_size = BalancedTree::_size(&node.borrow().left);
-                let right_size = BalancedTree::_size(&node.borrow().right);
-                node.borrow_mut().n = left_size + right_size + 1;
+    fn put_r(&self, link: Link<K, V>, key: K, value: V) -> NodePtr<K, V> {
+        let Some(h) = link else {
+            return Node::new(key, value, 1, RED);
+        };
+        // store the ordering in a temporary to avoid overlapping borrows.
+        let ordering = key.cmp(&h.borrow().key);
+        self.counter.compare(Operation::Put);
+        match ordering {
+            Ordering::Less => {
+                let left = h.borrow_mut().left.take();
+                let left = self.put_r(left, key, value);
+                h.borrow_mut().left = Some(left);
             }
-            None => {
-                link.replace(Node::new(key, value, 1));
+            Ordering::Equal => {
+                h.borrow_mut().value = value;
+            }
+            Ordering::Greater => {
+                let right = h.borrow_mut().right.take();
+                let right = self.put_r(right, key, value);
+                h.borrow_mut().right = Some(right);
             }
         }
+        self.balance(h)
     }
 
     /// Number of keys less than the given key
     pub fn rank(&self, key: K) -> usize {
-        todo!()
+        BalancedTree::rank_r(&self.root, &key)
+    }
+
+    fn rank_r(link: &Link<K, V>, key: &K) -> usize {
+        match link {
+            Some(node) => {
+                let node = node.borrow();
+                match key.cmp(&node.key) {
+                    Ordering::Less => BalancedTree::rank_r(&node.left, key),
+                    Ordering::Equal => BalancedTree::_size(&node.left),
+                    Ordering::Greater => {
+                        1 + BalancedTree::_size(&node.left) + BalancedTree::rank_r(&node.right, key)
+                    }
+                }
+            }
+            None => 0,
+        }
     }
 
     /// Return the key of rank k (i.e. k_th smallest key)
@@ -302,8 +432,22 @@ where
         //
         // A, C, E, H, R, S, X
         //
+        // If the left subtree has t > k keys, the key of rank k is in the left subtree; if
+        // t == k it is the key at the root, and if t < k it is the key of rank k - t - 1 in the
+        // right subtree.
+        //
+        // Panics if k is not less than the number of keys.
+        BalancedTree::select_r(&self.root, k)
+    }
 
-        todo!()
+    fn select_r(link: &Link<K, V>, k: usize) -> K {
+        let node = link.as_ref().expect("rank out of range").borrow();
+        let t = BalancedTree::_size(&node.left);
+        match k.cmp(&t) {
+            Ordering::Less => BalancedTree::select_r(&node.left, k),
+            Ordering::Equal => node.key.clone(),
+            Ordering::Greater => BalancedTree::select_r(&node.right, k - t - 1),
+        }
     }
 
     /// Display the tree nodes in order
@@ -312,24 +456,25 @@ where
     }
 
     fn show_r(link: &Link<K, V>) {
-        match link {
-            Some(node) => {
-                BalancedTree::show_r(&node.borrow().left);
-                println!(
-                    "(k: {:?}, v: {:?}, n: {})",
-                    node.borrow().key,
-                    node.borrow().value,
-                    node.borrow().n
-                );
-                BalancedTree::show_r(&node.borrow().right);
-            }
-            None => {}
+        if let Some(node) = link {
+            BalancedTree::show_r(&node.borrow().left);
+            println!(
+                "(k: {:?}, v: {:?}, n: {})",
+                node.borrow().key,
+                node.borrow().value,
+                node.borrow().n
+            );
+            BalancedTree::show_r(&node.borrow().right);
         }
     }
 
     /// Return the number of keys in [lo..hi]
     pub fn size_in_range(&self, lo: K, hi: K) -> usize {
-        todo!()
+        if lo > hi {
+            return 0;
+        }
+        let below_hi = self.rank(hi.clone()) + self.contains(hi) as usize;
+        below_hi - self.rank(lo)
     }
 
     /// Return the number of key, value pairs in the table
@@ -338,8 +483,146 @@ where
     }
 
     /// Get the collected statistics
-    pub fn statistics(&self, total_puts: usize) -> SymbolTableStatistics {
-        SymbolTableStatistics::new(self.compares_put, total_puts)
+    pub fn statistics(&self) -> SymbolTableStatistics {
+        let mut shape = TreeShape::default();
+        BalancedTree::shape_r(&self.root, 0, &mut shape);
+        SymbolTableStatistics::new(&self.counter, shape)
+    }
+
+    fn shape_r(link: &Link<K, V>, depth: usize, shape: &mut TreeShape) {
+        if let Some(node) = link {
+            shape.add_node(depth);
+            BalancedTree::shape_r(&node.borrow().left, depth + 1, shape);
+            BalancedTree::shape_r(&node.borrow().right, depth + 1, shape);
+        }
+    }
+
+    // Restore the invariants at h: rotate a right-leaning red link to the left, rotate the upper
+    // of two red links in a row to the right, and split a temporary 4-node by flipping colors
+    fn balance(&self, mut h: NodePtr<K, V>) -> NodePtr<K, V> {
+        if BalancedTree::is_red(&h.borrow().right) && !BalancedTree::is_red(&h.borrow().left) {
+            h = self.rotate_left(h);
+        }
+        if BalancedTree::is_red(&h.borrow().left) && BalancedTree::is_left_red(&h.borrow().left) {
+            h = self.rotate_right(h);
+        }
+        if BalancedTree::is_red(&h.borrow().left) && BalancedTree::is_red(&h.borrow().right) {
+            BalancedTree::flip_colors(&h);
+        }
+        BalancedTree::update_size(&h);
+        h
+    }
+
+    // Make a right-leaning red link lean to the left
+    fn rotate_left(&self, h: NodePtr<K, V>) -> NodePtr<K, V> {
+        self.counter.rotation();
+        let x = h.borrow_mut().right.take().expect("a right child");
+        let middle = x.borrow_mut().left.take();
+        h.borrow_mut().right = middle;
+        BalancedTree::rotate(&h, &x);
+        x.borrow_mut().left = Some(h);
+        x
+    }
+
+    // Make a left-leaning red link lean to the right
+    fn rotate_right(&self, h: NodePtr<K, V>) -> NodePtr<K, V> {
+        self.counter.rotation();
+        let x = h.borrow_mut().left.take().expect("a left child");
+        let middle = x.borrow_mut().right.take();
+        h.borrow_mut().left = middle;
+        BalancedTree::rotate(&h, &x);
+        x.borrow_mut().right = Some(h);
+        x
+    }
+
+    // x takes the place of h, which becomes its child through a red link
+    fn rotate(h: &NodePtr<K, V>, x: &NodePtr<K, V>) {
+        let (color, n) = (h.borrow().color, h.borrow().n);
+        let mut node = x.borrow_mut();
+        node.color = color;
+        node.n = n;
+        h.borrow_mut().color = RED;
+        BalancedTree::update_size(h);
+    }
+
+    // Flip the colors of a node and its two children
+    fn flip_colors(h: &NodePtr<K, V>) {
+        let mut node = h.borrow_mut();
+        node.color = !node.color;
+        for child in [&node.left, &node.right].into_iter().flatten() {
+            let mut child = child.borrow_mut();
+            child.color = !child.color;
+        }
+    }
+
+    // Assuming that h is red and that h.left and h.left.left are black, make h.left or one of
+    // its children red
+    fn move_red_left(&self, mut h: NodePtr<K, V>) -> NodePtr<K, V> {
+        BalancedTree::flip_colors(&h);
+        if BalancedTree::is_left_red(&h.borrow().right) {
+            let right = h.borrow_mut().right.take().expect("a right child");
+            let right = self.rotate_right(right);
+            h.borrow_mut().right = Some(right);
+            h = self.rotate_left(h);
+            BalancedTree::flip_colors(&h);
+        }
+        h
+    }
+
+    // Assuming that h is red and that h.right and h.right.left are black, make h.right or one of
+    // its children red
+    fn move_red_right(&self, mut h: NodePtr<K, V>) -> NodePtr<K, V> {
+        BalancedTree::flip_colors(&h);
+        if BalancedTree::is_left_red(&h.borrow().left) {
+            h = self.rotate_right(h);
+            BalancedTree::flip_colors(&h);
+        }
+        h
+    }
+
+    // Before a deletion, make the root red if both its children are black
+    fn red_root(root: NodePtr<K, V>) -> NodePtr<K, V> {
+        if !BalancedTree::is_red(&root.borrow().left) && !BalancedTree::is_red(&root.borrow().right)
+        {
+            root.borrow_mut().color = RED;
+        }
+        root
+    }
+
+    fn set_black(link: &Link<K, V>) {
+        if let Some(node) = link {
+            node.borrow_mut().color = BLACK;
+        }
+    }
+
+    fn is_red(link: &Link<K, V>) -> bool {
+        link.as_ref().is_some_and(|node| node.borrow().color == RED)
+    }
+
+    // Is the left child of the node red?
+    fn is_left_red(link: &Link<K, V>) -> bool {
+        link.as_ref()
+            .is_some_and(|node| BalancedTree::is_red(&node.borrow().left))
+    }
+
+    // The smallest key of the subtree, with its value
+    fn min_entry(node: &NodePtr<K, V>) -> (K, V) {
+        let mut node = node.clone();
+        loop {
+            let left = node.borrow().left.clone();
+            match left {
+                Some(left) => node = left,
+                None => break,
+            }
+        }
+        let node = node.borrow();
+        (node.key.clone(), node.value.clone())
+    }
+
+    fn update_size(node: &NodePtr<K, V>) {
+        let n =
+            BalancedTree::_size(&node.borrow().left) + BalancedTree::_size(&node.borrow().right);
+        node.borrow_mut().n = n + 1;
     }
 
     fn _size(link: &Link<K, V>) -> usize {
@@ -350,13 +633,51 @@ where
     }
 }
 
+impl<K, V> KeyValueSymbolTable<K, V> for BalancedTree<K, V>
+where
+    K: Clone + Debug + Ord,
+    V: Clone + Debug,
+{
+    fn get(&self, key: K) -> Option<V> {
+        BalancedTree::get(self, key)
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        BalancedTree::put(self, key, value)
+    }
+
+    fn size(&self) -> usize {
+        BalancedTree::size(self)
+    }
+
+    fn keys(&self) -> Vec<K> {
+        BalancedTree::keys(self)
+    }
+
+    fn statistics(&self) -> SymbolTableStatistics {
+        BalancedTree::statistics(self)
+    }
+
+    fn counter(&self) -> &OperationCounter {
+        &self.counter
+    }
+
+    fn memory(&self) -> usize {
+        // each node is reference counted, which adds a strong and a weak count to the allocation
+        let node = mem::size_of::<RefCell<Node<K, V>>>() + 2 * mem::size_of::<usize>();
+        mem::size_of::<Self>() + self.size() * node
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 #[cfg(test)]
 mod test {
-    use std::{cell::RefCell, rc::Rc};
+    use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
 
-    use super::BalancedTree;
+    use super::{BalancedTree, Link, SymbolTableStatistics, RED};
 
     #[test]
     fn test_put() {
@@ -368,6 +689,24 @@ mod test {
         assert_eq!(tree.root.as_ref().unwrap().borrow().n, 10);
     }
 
+    #[test]
+    fn test_statistics() {
+        let tree = make_tree();
+        tree.get("H".into());
+        tree.get("Z".into());
+
+        // The statistics can still be named from this module
+        let statistics: SymbolTableStatistics = tree.statistics();
+        assert_eq!(statistics.puts(), 10);
+        assert_eq!(statistics.compares_put(), 26);
+        assert_eq!(statistics.gets(), 2);
+        // H for "H" and H, S, X for "Z"
+        assert_eq!(statistics.compares_get(), 4);
+        assert_eq!(statistics.node_count(), 10);
+        assert_eq!(statistics.height(), 4);
+        assert_eq!(statistics.internal_path_length(), 20);
+    }
+
     #[test]
     fn test_min_max() {
         let tree = make_tree();
@@ -382,15 +721,13 @@ mod test {
     fn test_floor() {
         let mut tree = BalancedTree::<String, u32>::new();
 
-        //         S
-        //        /  \
-        //       E    X
-        //      /  \
-        //     A    R
-        //    / \   / \
-        //       C H
-        //        / \
         //            M
+        //         /     \
+        //        E       S
+        //       / \     / \
+        //      C   H   R   X
+        //     //
+        //    A
         //
         tree.put("S".into(), 0);
         tree.put("E".into(), 0);
@@ -408,15 +745,13 @@ mod test {
     fn test_ceil() {
         let mut tree = BalancedTree::<String, u32>::new();
 
-        //         S
-        //        /  \
-        //       E    X
-        //      /  \
-        //     A    R
-        //    / \   / \
-        //       C H
-        //        / \
         //            M
+        //         /     \
+        //        E       S
+        //       / \     / \
+        //      C   H   R   X
+        //     //
+        //    A
         //
         tree.put("S".into(), 0);
         tree.put("E".into(), 0);
@@ -437,37 +772,165 @@ mod test {
         let tree = make_tree();
         tree.show();
 
-        // select (3)
+        // A C E H L M P R S X
+        assert_eq!(tree.select(3), "H");
+        assert_eq!(tree.select(0), "A");
+        assert_eq!(tree.select(9), "X");
+        for (i, key) in tree.keys().into_iter().enumerate() {
+            assert_eq!(tree.rank(key), i);
+        }
+        assert_eq!(tree.rank("B".into()), 1);
+        assert_eq!(tree.rank("Z".into()), 10);
+    }
+
+    #[test]
+    fn test_range() {
+        let tree = make_tree();
+        assert_eq!(
+            tree.keys_in_range("D".into(), "N".into()),
+            vec!["E", "H", "L", "M"]
+        );
+        assert_eq!(tree.keys_in_range("H".into(), "H".into()), vec!["H"]);
+        assert!(tree.keys_in_range("N".into(), "D".into()).is_empty());
+        assert_eq!(tree.size_in_range("D".into(), "N".into()), 4);
+        assert_eq!(tree.size_in_range("A".into(), "X".into()), 10);
+        assert_eq!(tree.size_in_range("N".into(), "D".into()), 0);
+    }
+
+    #[test]
+    fn test_delete() {
+        let mut tree = make_tree();
+        // E is in a 3-node with the root of the left subtree, which is rebalanced
+        tree.delete("E".into());
+        tree.delete("Z".into());
+        assert_eq!(tree.size(), 9);
+        assert_eq!(tree.get("E".into()), None);
+        assert_eq!(
+            tree.keys(),
+            vec!["A", "C", "H", "L", "M", "P", "R", "S", "X"]
+        );
+        let root = tree.root.clone().unwrap();
+        assert_eq!(root.borrow().key, "M");
+        assert_eq!(root.borrow().left.as_ref().unwrap().borrow().key, "H");
+        assert_eq!(root.borrow().n, 9);
+
+        tree.delete_min();
+        tree.delete_max();
+        assert_eq!(tree.keys(), vec!["C", "H", "L", "M", "P", "R", "S"]);
+        tree.delete("S".into());
+        assert_eq!(tree.min(), "C");
+        assert_eq!(tree.max(), "R");
+        for key in tree.keys() {
+            tree.delete(key);
+        }
+        assert!(tree.is_empty());
+        assert_eq!(tree.statistics().deletes(), 11);
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut tree = BalancedTree::new();
+        let mut expected = BTreeMap::new();
+        for i in 0..2000 {
+            let key = rng.gen_range(0..200);
+            match rng.gen_range(0..4) {
+                0 => {
+                    tree.delete(key);
+                    expected.remove(&key);
+                }
+                1 if !expected.is_empty() => {
+                    tree.delete_min();
+                    expected.pop_first();
+                }
+                2 if !expected.is_empty() => {
+                    tree.delete_max();
+                    expected.pop_last();
+                }
+                _ => {
+                    tree.put(key, i);
+                    expected.insert(key, i);
+                }
+            }
+            check(&tree.root);
+            assert_eq!(tree.size(), expected.len());
+        }
+        assert_eq!(tree.keys(), expected.keys().copied().collect::<Vec<_>>());
+        for (key, value) in expected {
+            assert_eq!(tree.get(key), Some(value));
+        }
+    }
+
+    #[test]
+    fn test_sorted() {
+        // keys in order build a tree of height lg N
+        let mut tree = BalancedTree::new();
+        for key in 0..1023 {
+            tree.put(key, ());
+        }
+        check(&tree.root);
+        assert!(tree.statistics().height() <= 10);
+        assert!(tree.statistics().rotations() > 0);
+    }
+
+    // Check the invariants of the tree and return its black height: the root is black, red links
+    // lean left, no node has two red links, every path from the root to a null link has the same
+    // number of black links, and the sizes are right
+    fn check<K: Ord, V>(root: &Link<K, V>) -> usize {
+        if let Some(root) = root {
+            assert_ne!(root.borrow().color, RED);
+        }
+        check_r(root)
+    }
+
+    fn check_r<K: Ord, V>(link: &Link<K, V>) -> usize {
+        let Some(node) = link else {
+            return 0;
+        };
+        let node = node.borrow();
+        let is_red = |link: &Link<K, V>| link.as_ref().is_some_and(|x| x.borrow().color == RED);
+        assert!(!is_red(&node.right), "right-leaning red link");
+        assert!(
+            !(node.color == RED && is_red(&node.left)),
+            "two red links in a row"
+        );
+        let size = |link: &Link<K, V>| link.as_ref().map_or(0, |x| x.borrow().n);
+        assert_eq!(node.n, 1 + size(&node.left) + size(&node.right));
+        for (child, less) in [(&node.left, true), (&node.right, false)] {
+            if let Some(child) = child {
+                assert_eq!(child.borrow().key < node.key, less);
+            }
+        }
+        let black_height = check_r(&node.left);
+        assert_eq!(black_height, check_r(&node.right), "unbalanced black links");
+        black_height + usize::from(node.color != RED)
     }
 
     fn make_tree() -> BalancedTree<String, u32> {
         let mut tree = BalancedTree::<String, u32>::new();
 
-        // The numbers in brackets indicate the number of nodes in the subtree
+        // The numbers in brackets indicate the number of nodes in the subtree, and the double
+        // lines are red links
         //
-        //                     +-------+
-        //                     | S(10) |
-        //                     +-------+
-        //                    /         \
-        //            +-------+          +-------+
-        //            |  E(8) |          |  X (1)|
-        //            +-------+          +-------+
-        //           /         \
-        //  +-------+          +-------+
-        //  | A (2) |          | R (5) |
-        //  +-------+          +-------+
-        // /         \        /      
-        //      +-------+    +-------+
-        //      | C (1) |    |  H (4)|
-        //      +-------+    +-------+
-        //                            \
-        //                             +-------+
-        //                             | M (3) |
-        //                             +-------+
-        //                            /         \
-        //                   +-------+           +-------+
-        //                   | L (1) |           | P (1) |
-        //                   +-------+           +-------+
+        //                            +-------+
+        //                            | H(10) |
+        //                            +-------+
+        //                           /         \
+        //                  +-------+           +-------+
+        //                  | C (3) |           | S (6) |
+        //                  +-------+           +-------+
+        //                 /         \         //        \
+        //        +-------+   +-------+  +-------+        +-------+
+        //        | A (1) |   | E (1) |  | M (4) |        | X (1) |
+        //        +-------+   +-------+  +-------+        +-------+
+        //                              /         \
+        //                     +-------+           +-------+
+        //                     | L (1) |           | R (2) |
+        //                     +-------+           +-------+
+        //                                        //
+        //                               +-------+
+        //                               | P (1) |
+        //                               +-------+
         //
         tree.put("S".into(), 0);
         tree.put("X".into(), 0);
@@ -1,7 +1,11 @@
 //! Binary Search Tree
-use std::{cell::RefCell, cmp::Ordering, fmt::Debug, rc::Rc};
+use std::{cell::RefCell, cmp::Ordering, fmt::Debug, mem, rc::Rc};
 
-use super::{item::Item, symboltable::SymbolTable};
+use super::{
+    item::{GenericItem, Item},
+    statistics::{Operation, OperationCounter, SymbolTableStatistics, TreeShape},
+    symboltable::{KeyValueSymbolTable, SymbolTable},
+};
 
 type NodePtr<I> = Rc<RefCell<Node<I>>>;
 
@@ -40,6 +44,8 @@ impl<I: Item> Node<I> {
 pub struct BinarySearchTree<I: Item> {
     head: Link<I>,
     count: usize,
+    // Number of operations, compares and rotations
+    counter: OperationCounter,
 }
 
 impl<I> SymbolTable<I, I::Key> for BinarySearchTree<I>
@@ -51,11 +57,13 @@ where
     }
 
     fn search(&self, key: I::Key) -> Option<I> {
-        BinarySearchTree::search_r(self.head.clone(), key)
+        self.counter.operation(Operation::Get);
+        BinarySearchTree::search_r(self.head.clone(), key, &self.counter)
     }
 
     fn insert(&mut self, item: I) {
-        BinarySearchTree::insert_r(&mut self.head, item);
+        self.counter.operation(Operation::Put);
+        BinarySearchTree::insert_r(&mut self.head, item, &self.counter);
         self.count += 1;
     }
 
@@ -71,6 +79,12 @@ where
         let mut acc = vec![];
         BinarySearchTree::show_r(self.head.clone(), &mut acc)
     }
+
+    fn statistics(&self) -> SymbolTableStatistics {
+        let mut shape = TreeShape::default();
+        BinarySearchTree::shape_r(&self.head, 0, &mut shape);
+        SymbolTableStatistics::new(&self.counter, shape)
+    }
 }
 
 impl<I> BinarySearchTree<I>
@@ -81,24 +95,38 @@ where
         Self {
             head: None,
             count: 0,
+            counter: OperationCounter::new(),
+        }
+    }
+
+    /// Insert the `item`, replacing the item with an equal key if there is one already in the
+    /// tree.
+    pub fn update(&mut self, item: I) {
+        self.counter.operation(Operation::Put);
+        if BinarySearchTree::update_r(&mut self.head, item, &self.counter) {
+            self.count += 1;
         }
     }
 
     /// Insert the `item` at the root of the tree. This will do the necessary rotations to
     /// ensure that the `item` ends up at the root of the tree.
     pub fn insert_at_root(&mut self, item: I) {
-        BinarySearchTree::insert_at_root_r(&mut self.head, item);
+        self.counter.operation(Operation::Put);
+        BinarySearchTree::insert_at_root_r(&mut self.head, item, &self.counter);
+        self.count += 1;
     }
 
     // recursively insert `item` so that it ends up at the root of the whole tree
-    fn insert_at_root_r(root: &mut Link<I>, item: I) {
+    fn insert_at_root_r(root: &mut Link<I>, item: I, counter: &OperationCounter) {
         match root {
             Some(node) => {
+                counter.compare(Operation::Put);
+                counter.rotation();
                 if item.key() < node.borrow().item.key() {
-                    BinarySearchTree::insert_at_root_r(&mut node.borrow_mut().left, item);
+                    BinarySearchTree::insert_at_root_r(&mut node.borrow_mut().left, item, counter);
                     BinarySearchTree::rotate_right(root);
                 } else {
-                    BinarySearchTree::insert_at_root_r(&mut node.borrow_mut().right, item);
+                    BinarySearchTree::insert_at_root_r(&mut node.borrow_mut().right, item, counter);
                     BinarySearchTree::rotate_left(root);
                 }
             }
@@ -109,17 +137,45 @@ where
     }
 
     // Recursive implementation of insert
-    fn insert_r(root: &mut Link<I>, item: I) {
+    fn insert_r(root: &mut Link<I>, item: I, counter: &OperationCounter) {
         match root {
             Some(node) => {
+                counter.compare(Operation::Put);
                 if item.key() < node.borrow().item.key() {
-                    BinarySearchTree::insert_r(&mut node.borrow_mut().left, item)
+                    BinarySearchTree::insert_r(&mut node.borrow_mut().left, item, counter)
                 } else {
-                    BinarySearchTree::insert_r(&mut node.borrow_mut().right, item)
+                    BinarySearchTree::insert_r(&mut node.borrow_mut().right, item, counter)
+                }
+            }
+            None => {
+                root.replace(Node::new(item));
+            }
+        }
+    }
+
+    // Recursive implementation of update. Returns whether a new node was added to the tree.
+    fn update_r(root: &mut Link<I>, item: I, counter: &OperationCounter) -> bool {
+        match root {
+            Some(node) => {
+                // store the ordering in a temporary to avoid overlapping borrows.
+                let ordering = item.key().cmp(&node.borrow().item.key());
+                counter.compare(Operation::Put);
+                match ordering {
+                    Ordering::Less => {
+                        BinarySearchTree::update_r(&mut node.borrow_mut().left, item, counter)
+                    }
+                    Ordering::Equal => {
+                        node.borrow_mut().item = item;
+                        false
+                    }
+                    Ordering::Greater => {
+                        BinarySearchTree::update_r(&mut node.borrow_mut().right, item, counter)
+                    }
                 }
             }
             None => {
                 root.replace(Node::new(item));
+                true
             }
         }
     }
@@ -198,17 +254,33 @@ where
     }
 
     // Recursive implementation of search
-    fn search_r(root: Link<I>, key: I::Key) -> Option<I> {
+    fn search_r(root: Link<I>, key: I::Key, counter: &OperationCounter) -> Option<I> {
         match root {
-            Some(node) => match key.cmp(&node.borrow().item.key()) {
-                Ordering::Less => BinarySearchTree::search_r(node.borrow().left.clone(), key),
-                Ordering::Equal => Some(node.borrow().item.clone()),
-                Ordering::Greater => BinarySearchTree::search_r(node.borrow().right.clone(), key),
-            },
+            Some(node) => {
+                counter.compare(Operation::Get);
+                match key.cmp(&node.borrow().item.key()) {
+                    Ordering::Less => {
+                        BinarySearchTree::search_r(node.borrow().left.clone(), key, counter)
+                    }
+                    Ordering::Equal => Some(node.borrow().item.clone()),
+                    Ordering::Greater => {
+                        BinarySearchTree::search_r(node.borrow().right.clone(), key, counter)
+                    }
+                }
+            }
             None => None,
         }
     }
 
+    // traverse the tree and account for the depth of each node
+    fn shape_r(root: &Link<I>, depth: usize, shape: &mut TreeShape) {
+        if let Some(node) = root {
+            shape.add_node(depth);
+            BinarySearchTree::shape_r(&node.borrow().left, depth + 1, shape);
+            BinarySearchTree::shape_r(&node.borrow().right, depth + 1, shape);
+        }
+    }
+
     // traverse the tree in-order and collect the nodes
     fn show_r(root: Link<I>, acc: &mut Vec<I>) -> Vec<I> {
         if let Some(node) = root {
@@ -220,6 +292,43 @@ where
     }
 }
 
+// The tree can be used as a key, value symbol table by storing the pairs as generic items.
+impl<K, V> KeyValueSymbolTable<K, V> for BinarySearchTree<GenericItem<K, V>>
+where
+    K: Clone + Debug + Default + Ord,
+    V: Clone + Debug + Default,
+{
+    fn get(&self, key: K) -> Option<V> {
+        self.search(key).map(|item| item.value().clone())
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        self.update(GenericItem::with_value(key, value));
+    }
+
+    fn size(&self) -> usize {
+        self.count
+    }
+
+    fn keys(&self) -> Vec<K> {
+        self.show().iter().map(|item| item.key()).collect()
+    }
+
+    fn statistics(&self) -> SymbolTableStatistics {
+        SymbolTable::statistics(self)
+    }
+
+    fn counter(&self) -> &OperationCounter {
+        &self.counter
+    }
+
+    fn memory(&self) -> usize {
+        // each node is reference counted, which adds a strong and a weak count to the allocation
+        let node = mem::size_of::<RefCell<Node<GenericItem<K, V>>>>() + 2 * mem::size_of::<usize>();
+        mem::size_of::<Self>() + self.count * node
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 #[cfg(test)]
@@ -229,7 +338,7 @@ mod test {
     use crate::symboltables::{
         binarysearchtree::Node,
         item::{DoubleItem, GenericItem, Item},
-        symboltable::SymbolTable,
+        symboltable::{KeyValueSymbolTable, SymbolTable},
     };
 
     use super::BinarySearchTree;
@@ -261,6 +370,19 @@ mod test {
         assert_eq!(bst.search(150), None);
     }
 
+    #[test]
+    fn test_update() {
+        let mut bst = BinarySearchTree::<GenericItem<String, u32>>::default();
+        bst.put("B".to_string(), 1);
+        bst.put("A".to_string(), 1);
+        bst.put("B".to_string(), 2);
+
+        assert_eq!(bst.count(), 2);
+        assert_eq!(bst.get("B".to_string()), Some(2));
+        assert_eq!(bst.get("C".to_string()), None);
+        assert_eq!(bst.keys(), vec!["A".to_string(), "B".to_string()]);
+    }
+
     #[test]
     fn test_rotate_right() {
         let mut bst = BinarySearchTree::<DoubleItem>::default();
@@ -368,6 +490,8 @@ mod test {
         bst.insert(i_11);
 
         bst.insert_at_root(i_8);
+        // insert_at_root counts the new item, as insert does
+        assert_eq!(bst.count(), 4);
 
         //        8
         //       / \
@@ -443,6 +567,13 @@ mod test {
 
         assert_eq!(bst.head.as_ref().unwrap().borrow().left, left_subtree);
         assert_eq!(bst.head.as_ref().unwrap().borrow().right, right_subtree);
+
+        // G is compared with A, S, E, R and H on the way down, and rotated once at each of them
+        let statistics = SymbolTable::statistics(&bst);
+        assert_eq!(statistics.puts(), 8);
+        assert_eq!(statistics.rotations(), 5);
+        assert_eq!(statistics.node_count(), 8);
+        assert_eq!(statistics.height(), 3);
     }
 
     #[allow(unused)]
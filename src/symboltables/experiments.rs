@@ -0,0 +1,568 @@
+//! Symbol Table Experiments
+//!
+//! Empirical analysis of the cost of symbol table operations, as done in Algorithms, 4th edition,
+//! Robert Sedgewick, Kevin Wayne, Addison-Wesley, 2011: the amortized cost plot of the compares
+//! done by each put, and the doubling ratio test.
+use std::{
+    fmt::{self, Display},
+    fs::File,
+    io::{self, BufReader},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use super::{
+    frequencycounter::Words,
+    item::{DoubleItem, Item},
+    statistics::Operation,
+    symboltable::KeyValueSymbolTable,
+};
+
+/// Return `n` random keys, generated in the same way as the keys of random `DoubleItem`s
+pub fn random_keys(n: usize) -> Vec<usize> {
+    (0..n)
+        .map(|_| {
+            let mut item = DoubleItem::new();
+            item.rand();
+            item.key()
+        })
+        .collect()
+}
+
+/// Read the first `n` words of the given file that are at least `min_length` long
+pub fn words<P: AsRef<Path>>(path: P, min_length: usize, n: usize) -> io::Result<Vec<String>> {
+    let f = File::open(path)?;
+    Words::new(BufReader::new(f))
+        .filter(|word| word.as_ref().map_or(true, |word| word.len() >= min_length))
+        .take(n)
+        .collect()
+}
+
+// Put each key into the table, with the number of times it has been seen as the value (as the
+// frequency counter does) and return the number of compares done by each put.
+fn put_keys<K, T>(table: &mut T, keys: &[K]) -> Vec<usize>
+where
+    K: Clone,
+    T: KeyValueSymbolTable<K, usize>,
+{
+    let mut costs = Vec::with_capacity(keys.len());
+    for key in keys {
+        let count = table.get(key.clone()).unwrap_or(0);
+        let before = table.counter().compares(Operation::Put);
+        table.put(key.clone(), count + 1);
+        costs.push(table.counter().compares(Operation::Put) - before);
+    }
+    costs
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// The number of compares done by each put of an experiment.
+///
+/// This is the data of the amortized cost plot: the cost of each operation together with the
+/// cumulative average cost of the operations done so far.
+#[derive(Debug)]
+pub struct CostTrace {
+    costs: Vec<usize>,
+}
+
+impl CostTrace {
+    /// Put each of the keys into the table and record the cost of each put
+    pub fn run<K, T>(table: &mut T, keys: &[K]) -> Self
+    where
+        K: Clone,
+        T: KeyValueSymbolTable<K, usize>,
+    {
+        Self {
+            costs: put_keys(table, keys),
+        }
+    }
+
+    /// The number of compares done by each put
+    pub fn costs(&self) -> &[usize] {
+        &self.costs
+    }
+
+    /// The average number of compares done by the first 1, 2, 3, ... puts
+    pub fn cumulative_averages(&self) -> Vec<f64> {
+        let mut total = 0;
+        self.costs
+            .iter()
+            .enumerate()
+            .map(|(i, cost)| {
+                total += cost;
+                total as f64 / (i + 1) as f64
+            })
+            .collect()
+    }
+
+    /// Format the trace as comma separated values, one line per operation
+    pub fn to_csv(&self) -> String {
+        let mut s = "operation,cost,cumulative_average\n".to_string();
+        for (i, (cost, average)) in self
+            .costs
+            .iter()
+            .zip(self.cumulative_averages())
+            .enumerate()
+        {
+            s += &format!("{},{},{:.4}\n", i + 1, cost, average);
+        }
+        s
+    }
+
+    /// Plot the trace with characters: `.` marks the cost of an operation and `*` the cumulative
+    /// average cost. The plot is empty if the width or the height is zero.
+    pub fn to_ascii(&self, width: usize, height: usize) -> String {
+        if width == 0 || height == 0 {
+            return String::new();
+        }
+        let max = self.max_cost();
+        let mut grid = vec![vec![' '; width]; height];
+        let row = |cost: f64| height - 1 - ((cost / max) * (height - 1) as f64).round() as usize;
+
+        for (i, (&cost, average)) in self
+            .costs
+            .iter()
+            .zip(self.cumulative_averages())
+            .enumerate()
+        {
+            let column = i * width / self.costs.len();
+            let cell = &mut grid[row(cost as f64)][column];
+            if *cell == ' ' {
+                *cell = '.';
+            }
+            grid[row(average)][column] = '*';
+        }
+
+        let mut s = String::new();
+        for (i, line) in grid.iter().enumerate() {
+            let label = if i == 0 {
+                format!("{}", max)
+            } else {
+                String::new()
+            };
+            s += &format!(
+                "{:>6} |{}\n",
+                label,
+                line.iter().collect::<String>().trim_end()
+            );
+        }
+        s += &format!("{:>6} +{}\n", 0, "-".repeat(width));
+        s += &format!("{:>8}{:>width$}\n", 0, self.costs.len(), width = width - 1);
+        s
+    }
+
+    /// Plot the trace as an SVG image: grey points mark the cost of each operation and the red
+    /// line is the cumulative average cost. The margin shrinks in small images, so that the plot
+    /// stays inside the image.
+    pub fn to_svg(&self, width: usize, height: usize) -> String {
+        let (w, h) = (width as f64, height as f64);
+        let margin = (w.min(h) / 4.0).min(40.0);
+        let max = self.max_cost();
+        let n = self.costs.len().max(1) as f64;
+        let x = |i: usize| margin + (i as f64 / n) * (w - 2.0 * margin);
+        let y = |cost: f64| h - margin - (cost / max) * (h - 2.0 * margin);
+
+        let mut s = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">\n",
+            width, height
+        );
+        s += &format!(
+            "<path d=\"M {m} {m} L {m} {b} L {r} {b}\" fill=\"none\" stroke=\"black\"/>\n",
+            m = margin,
+            b = h - margin,
+            r = w - margin
+        );
+        s += &format!(
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>\n",
+            margin - 5.0,
+            margin,
+            max
+        );
+        s += &format!(
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>\n",
+            w - margin,
+            h - margin + 20.0,
+            self.costs.len()
+        );
+        for (i, &cost) in self.costs.iter().enumerate() {
+            s += &format!(
+                "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"1\" fill=\"grey\"/>\n",
+                x(i),
+                y(cost as f64)
+            );
+        }
+        let points: Vec<String> = self
+            .cumulative_averages()
+            .iter()
+            .enumerate()
+            .map(|(i, &average)| format!("{:.1},{:.1}", x(i), y(average)))
+            .collect();
+        s += &format!(
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"red\"/>\n",
+            points.join(" ")
+        );
+        s += "</svg>\n";
+        s
+    }
+
+    fn max_cost(&self) -> f64 {
+        self.costs.iter().copied().max().unwrap_or(0).max(1) as f64
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// The result of one run of the doubling ratio test
+#[derive(Debug, Clone, Copy)]
+pub struct DoublingExperiment {
+    pub n: usize,              // number of keys put into the table
+    pub average_put_cost: f64, // average cost of a put, as reported by the table's statistics
+    pub elapsed: Duration,     // time taken to put all the keys
+}
+
+/// Doubling ratio test.
+///
+/// Runs an experiment on inputs whose size doubles each time, and reports the ratio of the cost of
+/// each run to the one before. If the cost is ~ a N^b, the ratio approaches 2^b, so the order of
+/// growth of the cost can be estimated from the results.
+#[derive(Debug)]
+pub struct DoublingRatio {
+    experiments: Vec<DoublingExperiment>,
+}
+
+impl DoublingRatio {
+    /// Run the test with inputs of size `start`, 2 `start`, 4 `start`, ... up to `max`. `keys`
+    /// returns the input of the given size and `new_table` creates the (empty) symbol table that
+    /// the keys are put into. The test stops early when `keys` returns fewer keys than asked for,
+    /// since the input size no longer doubles.
+    pub fn run<K, T, F, G>(start: usize, max: usize, mut keys: F, new_table: G) -> Self
+    where
+        K: Clone,
+        T: KeyValueSymbolTable<K, usize>,
+        F: FnMut(usize) -> Vec<K>,
+        G: Fn() -> T,
+    {
+        let mut experiments = vec![];
+        let mut n = start.max(1);
+        while n <= max {
+            let keys = keys(n);
+            if keys.len() < n {
+                break;
+            }
+            let mut table = new_table();
+            let timer = Instant::now();
+            put_keys(&mut table, &keys);
+            let elapsed = timer.elapsed();
+            experiments.push(DoublingExperiment {
+                n: keys.len(),
+                average_put_cost: table.statistics().average_put_cost(),
+                elapsed,
+            });
+            n *= 2;
+        }
+        Self { experiments }
+    }
+
+    /// The results of each run, smallest input first
+    pub fn experiments(&self) -> &[DoublingExperiment] {
+        &self.experiments
+    }
+
+    /// Ratio of the average put cost of each run to that of the run before
+    pub fn cost_ratios(&self) -> Vec<f64> {
+        self.experiments
+            .windows(2)
+            .map(|w| w[1].average_put_cost / w[0].average_put_cost)
+            .collect()
+    }
+
+    /// Ratio of the running time of each run to that of the run before
+    pub fn time_ratios(&self) -> Vec<f64> {
+        self.experiments
+            .windows(2)
+            .map(|w| w[1].elapsed.as_secs_f64() / w[0].elapsed.as_secs_f64())
+            .collect()
+    }
+
+    /// Estimate the order of growth of the average put cost, if there are enough runs with a
+    /// cost (see `OrderOfGrowth::fit`)
+    pub fn order_of_growth(&self) -> Option<OrderOfGrowth> {
+        let points: Vec<(f64, f64)> = self
+            .experiments
+            .iter()
+            .map(|e| (e.n as f64, e.average_put_cost))
+            .collect();
+        OrderOfGrowth::fit(&points)
+    }
+
+    /// Format the results as comma separated values, one line per run
+    pub fn to_csv(&self) -> String {
+        let mut s = "n,average_put_cost,cost_ratio,time_ms,time_ratio\n".to_string();
+        let cost_ratios = self.cost_ratios();
+        let time_ratios = self.time_ratios();
+        for (i, e) in self.experiments.iter().enumerate() {
+            let (cost_ratio, time_ratio) = match i {
+                0 => (String::new(), String::new()),
+                _ => (
+                    format!("{:.4}", cost_ratios[i - 1]),
+                    format!("{:.4}", time_ratios[i - 1]),
+                ),
+            };
+            s += &format!(
+                "{},{:.4},{},{:.4},{}\n",
+                e.n,
+                e.average_put_cost,
+                cost_ratio,
+                e.elapsed.as_secs_f64() * 1000.0,
+                time_ratio
+            );
+        }
+        s
+    }
+}
+
+impl Display for DoublingRatio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>10} {:>10} {:>8} {:>12} {:>8}",
+            "n", "put cost", "ratio", "time (ms)", "ratio"
+        )?;
+        let cost_ratios = self.cost_ratios();
+        let time_ratios = self.time_ratios();
+        for (i, e) in self.experiments.iter().enumerate() {
+            write!(f, "{:>10} {:>10.2} ", e.n, e.average_put_cost)?;
+            match i {
+                0 => write!(f, "{:>8} ", "")?,
+                _ => write!(f, "{:>8.2} ", cost_ratios[i - 1])?,
+            }
+            write!(f, "{:>12.3} ", e.elapsed.as_secs_f64() * 1000.0)?;
+            match i {
+                0 => writeln!(f, "{:>8}", "")?,
+                _ => writeln!(f, "{:>8.2}", time_ratios[i - 1])?,
+            }
+        }
+        match self.order_of_growth() {
+            Some(growth) => write!(f, "order of growth: {}", growth),
+            None => write!(f, "order of growth: insufficient data"),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Estimated order of growth of a cost, fitted from measurements at different input sizes.
+///
+/// The cost is classified by the exponent of the power law fit: a cost that is constant or
+/// logarithmic has an exponent close to zero, but over the input sizes of an experiment a
+/// logarithm still grows noticeably.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrderOfGrowth {
+    /// `b` in the power law fit cost ~ a N^b
+    pub exponent: f64,
+    /// `a` in the logarithmic fit cost ~ a lg N
+    pub log_coefficient: f64,
+    /// The mean cost, for a cost that is constant
+    pub constant: f64,
+}
+
+impl OrderOfGrowth {
+    // Below this exponent the cost is taken to be constant
+    const CONSTANT: f64 = 0.05;
+    // Below this exponent the cost is taken to grow logarithmically rather than as a power of N
+    const LOGARITHMIC: f64 = 0.4;
+
+    /// Fit the (n, cost) points by least squares, both as a power law (a straight line on a
+    /// log-log plot) and as a multiple of lg N.
+    ///
+    /// Points with a cost of 0 or less have no logarithm and are skipped. Returns `None` if fewer
+    /// than 2 points are left, which is not enough to fit a line.
+    pub fn fit(points: &[(f64, f64)]) -> Option<Self> {
+        let points: Vec<(f64, f64)> = points.iter().copied().filter(|p| p.1 > 0.0).collect();
+        if points.len() < 2 {
+            return None;
+        }
+        let logs: Vec<(f64, f64)> = points.iter().map(|&(n, c)| (n.ln(), c.ln())).collect();
+        let k = logs.len() as f64;
+        let mean_x = logs.iter().map(|p| p.0).sum::<f64>() / k;
+        let mean_y = logs.iter().map(|p| p.1).sum::<f64>() / k;
+        let sxy: f64 = logs.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+        let sxx: f64 = logs.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+        let exponent = if sxx > 0.0 { sxy / sxx } else { 0.0 };
+
+        let lg_cost: f64 = points.iter().map(|&(n, c)| n.log2() * c).sum();
+        let lg_lg: f64 = points.iter().map(|&(n, _)| n.log2().powi(2)).sum();
+        let log_coefficient = if lg_lg > 0.0 { lg_cost / lg_lg } else { 0.0 };
+        let constant = points.iter().map(|p| p.1).sum::<f64>() / k;
+
+        Some(Self {
+            exponent,
+            log_coefficient,
+            constant,
+        })
+    }
+
+    /// Whether the cost does not grow with the input size
+    pub fn is_constant(&self) -> bool {
+        self.exponent < OrderOfGrowth::CONSTANT
+    }
+
+    /// Whether the cost grows logarithmically
+    pub fn is_logarithmic(&self) -> bool {
+        !self.is_constant() && self.exponent < OrderOfGrowth::LOGARITHMIC
+    }
+}
+
+impl Display for OrderOfGrowth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_constant() {
+            write!(f, "~ {:.2}", self.constant)
+        } else if self.is_logarithmic() {
+            write!(f, "~ {:.2} lg N", self.log_coefficient)
+        } else {
+            write!(f, "~ N^{:.2}", self.exponent)
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::symboltables::{
+        balancedtree::BalancedTree, binarysearchtree::BinarySearchTree,
+        hashtable::SeparateChainingHashTable, item::GenericItem, trie::TernarySearchTrie,
+    };
+
+    use super::{words, CostTrace, DoublingRatio, OrderOfGrowth};
+
+    // Random keys from a seeded generator, so that a failure can be reproduced
+    fn seeded_keys(seed: u64) -> impl FnMut(usize) -> Vec<usize> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        move |n| (0..n).map(|_| rng.gen()).collect()
+    }
+
+    #[test]
+    fn test_cost_trace() {
+        let mut tree = BalancedTree::new();
+        let trace = CostTrace::run(&mut tree, &["S", "E", "A", "R", "C", "H", "E"]);
+
+        assert_eq!(trace.costs(), &[0, 1, 2, 2, 2, 3, 2]);
+        assert_eq!(trace.cumulative_averages()[2], 1.0);
+        assert_eq!(tree.get("E"), Some(2));
+
+        let csv = trace.to_csv();
+        assert_eq!(csv.lines().count(), 8);
+        assert_eq!(csv.lines().nth(3), Some("3,2,1.0000"));
+
+        let ascii = trace.to_ascii(7, 4);
+        assert_eq!(ascii.lines().count(), 6);
+        assert!(ascii.starts_with("     3 |"));
+        assert_eq!(trace.to_ascii(0, 4), "");
+        assert_eq!(trace.to_ascii(7, 0), "");
+
+        let svg = trace.to_svg(400, 300);
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("<circle").count(), 7);
+
+        // the points of a small image are inside it
+        let svg = trace.to_svg(40, 30);
+        for circle in svg.lines().filter(|line| line.starts_with("<circle")) {
+            assert!(!circle.contains('-'), "{}", circle);
+        }
+    }
+
+    #[test]
+    fn test_words() {
+        let ws = words("resources/tinyTale.txt", 4, 3).unwrap();
+        assert_eq!(ws, vec!["best", "times", "worst"]);
+
+        let mut trie = TernarySearchTrie::new();
+        let trace = CostTrace::run(&mut trie, &words("resources/tinyTale.txt", 1, 100).unwrap());
+        assert_eq!(trace.costs().len(), 60);
+    }
+
+    #[test]
+    fn test_doubling_ratio_random() {
+        let doubling = DoublingRatio::run(256, 4096, seeded_keys(1), BalancedTree::new);
+        assert_eq!(doubling.experiments().len(), 5);
+        assert_eq!(doubling.experiments()[4].n, 4096);
+        assert_eq!(doubling.cost_ratios().len(), 4);
+
+        // random keys build a tree with a logarithmic height
+        let growth = doubling.order_of_growth().unwrap();
+        assert!(growth.is_logarithmic(), "{}", growth);
+        assert!(doubling.to_string().ends_with("lg N"));
+        assert_eq!(doubling.to_csv().lines().count(), 6);
+
+        // the chains of a hash table have a constant average length
+        let doubling =
+            DoublingRatio::run(256, 4096, seeded_keys(2), SeparateChainingHashTable::new);
+        assert!(doubling.order_of_growth().unwrap().is_constant());
+    }
+
+    #[test]
+    fn test_doubling_ratio_sorted() {
+        // keys in order build a BST that is a linked list, so the cost of a put is linear
+        let doubling = DoublingRatio::run(
+            64,
+            1024,
+            |n| (0..n).collect(),
+            BinarySearchTree::<GenericItem<usize, usize>>::new,
+        );
+        let growth = doubling.order_of_growth().unwrap();
+        assert!(!growth.is_logarithmic());
+        assert!(growth.exponent > 0.9 && growth.exponent < 1.1, "{}", growth);
+        for ratio in doubling.cost_ratios() {
+            assert!(ratio > 1.9 && ratio < 2.1);
+        }
+
+        // while a red-black BST stays balanced
+        let doubling = DoublingRatio::run(64, 1024, |n| (0..n).collect(), BalancedTree::new);
+        let growth = doubling.order_of_growth().unwrap();
+        assert!(growth.is_logarithmic(), "{}", growth);
+    }
+
+    #[test]
+    fn test_fit() {
+        let points: Vec<(f64, f64)> = (4..12)
+            .map(|i| {
+                let n = (1 << i) as f64;
+                (n, 1.39 * n.log2())
+            })
+            .collect();
+        let growth = OrderOfGrowth::fit(&points).unwrap();
+        assert!((growth.log_coefficient - 1.39).abs() < 1e-9);
+        assert_eq!(growth.to_string(), "~ 1.39 lg N");
+
+        // points without a cost are skipped
+        assert_eq!(OrderOfGrowth::fit(&[]), None);
+        assert_eq!(
+            OrderOfGrowth::fit(&[(1.0, 0.0), (2.0, 0.0), (4.0, 3.0)]),
+            None
+        );
+        let growth = OrderOfGrowth::fit(&[(1.0, 0.0), (2.0, 3.0), (4.0, 3.0)]).unwrap();
+        assert_eq!(growth.to_string(), "~ 3.00");
+    }
+
+    #[test]
+    fn test_doubling_ratio_short_input() {
+        // the source runs out of keys after 2, so the input size stops doubling
+        let doubling = DoublingRatio::run(1, 8, |n| (0..n.min(2)).collect(), BalancedTree::new);
+        let sizes: Vec<usize> = doubling.experiments().iter().map(|e| e.n).collect();
+        assert_eq!(sizes, [1, 2]);
+        assert!(doubling.order_of_growth().is_some());
+
+        // a single run is not enough to estimate the order of growth
+        let doubling = DoublingRatio::run(1, 8, |n| (0..n.min(1)).collect(), BalancedTree::new);
+        assert_eq!(doubling.experiments().len(), 1);
+        assert!(doubling.order_of_growth().is_none());
+        assert!(doubling.to_string().ends_with("insufficient data"));
+
+        let doubling = DoublingRatio::run(1, 8, |_| Vec::<usize>::new(), BalancedTree::new);
+        assert!(doubling.experiments().is_empty());
+    }
+}
@@ -1,9 +1,24 @@
 use std::{
-    io::{BufRead, BufReader},
+    cmp::Reverse,
+    fmt::{self, Display},
+    fs::File,
+    io::{self, BufRead, BufReader, Write},
     path::Path,
+    str::FromStr,
+    time::{Duration, Instant},
 };
 
-use super::balancedtree::{BalancedTree, SymbolTableStatistics};
+use crate::priorityqueues::binaryheap::MinPQ;
+
+use super::{
+    balancedtree::BalancedTree,
+    binarysearchtree::BinarySearchTree,
+    hashtable::SeparateChainingHashTable,
+    item::GenericItem,
+    statistics::SymbolTableStatistics,
+    symboltable::KeyValueSymbolTable,
+    trie::TernarySearchTrie,
+};
 
 #[derive(Debug)]
 pub struct FrequencyCounter {
@@ -21,68 +36,398 @@ pub struct FrequencyCounter {
 // table.  Then find the word with the highest frequency.
 impl FrequencyCounter {
     pub fn new<P: AsRef<Path>>(path: P, min_length: usize) -> Self {
-        let mut words = 0;
-        let mut distinct = 0;
+        let mut counter = StreamingFrequencyCounter::new(min_length);
+        counter.add_file(path).unwrap();
+        counter.checkpoint()
+    }
+
+    /// Count the words read from the given reader
+    pub fn from_reader<R: BufRead>(reader: R, min_length: usize) -> io::Result<Self> {
+        let mut counter = StreamingFrequencyCounter::new(min_length);
+        counter.add_reader(reader)?;
+        Ok(counter.checkpoint())
+    }
+
+    /// Count the words in the given file once with each of the given symbol table backends,
+    /// so that their costs can be compared on the same input.
+    pub fn compare_backends<P: AsRef<Path>>(
+        path: P,
+        min_length: usize,
+        backends: &[Backend],
+    ) -> io::Result<Vec<BackendComparison>> {
+        let mut result = vec![];
+        for &backend in backends {
+            let f = File::open(path.as_ref())?;
+            let start = Instant::now();
+            let mut counter = StreamingFrequencyCounter::with_table(min_length, backend.table());
+            counter.add_reader(BufReader::new(f))?;
+            let frequency_counter = counter.checkpoint();
+            result.push(BackendComparison {
+                backend,
+                elapsed: start.elapsed(),
+                memory: counter.table.memory(),
+                frequency_counter,
+            });
+        }
+        Ok(result)
+    }
+
+    fn words(line: &str) -> Vec<String> {
+        line.split_ascii_whitespace()
+            .map(|s| s.to_string())
+            .collect()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Iterator over the words of a reader.
+///
+/// The input is read one line at a time, so only the current line is held in memory.
+pub struct Words<R> {
+    reader: R,
+    line: String,
+    pending: Vec<String>, // words of the current line, in reverse order
+}
+
+impl<R: BufRead> Words<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: String::new(),
+            pending: vec![],
+        }
+    }
+}
 
-        let mut tree = BalancedTree::<String, u32>::new();
+impl<R: BufRead> Iterator for Words<R> {
+    type Item = io::Result<String>;
 
-        let f = std::fs::File::open(path).unwrap();
-        let br = BufReader::new(f);
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() {
+            self.line.clear();
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => {
+                    self.pending = FrequencyCounter::words(&self.line);
+                    self.pending.reverse();
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        self.pending.pop().map(Ok)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
 
-        let mut all_words = vec![];
-        for line in br.lines() {
-            let line = line.unwrap();
-            let mut words_list = FrequencyCounter::words(&line);
-            all_words.append(&mut words_list);
+/// Frequency counter that consumes its input incrementally.
+///
+/// Words can be added one at a time, from an iterator or from any reader, so that arbitrarily
+/// large inputs can be counted without reading them into memory first. Counters built from
+/// different inputs can be merged, and the partial results can be inspected with `checkpoint` or
+/// saved and restored to resume counting later.
+///
+/// The counts are kept in a `BalancedTree` by default, but any symbol table backend can be used.
+#[derive(Debug)]
+pub struct StreamingFrequencyCounter<T = BalancedTree<String, u32>> {
+    table: T,
+    min_length: usize,
+    words: usize,
+    distinct: usize,
+}
+
+impl StreamingFrequencyCounter {
+    pub fn new(min_length: usize) -> Self {
+        StreamingFrequencyCounter::with_table(min_length, BalancedTree::new())
+    }
+
+    /// Read back a counter written by `save`
+    pub fn restore<R: BufRead>(reader: R) -> io::Result<Self> {
+        StreamingFrequencyCounter::restore_with_table(reader, BalancedTree::new())
+    }
+}
+
+impl<T: KeyValueSymbolTable<String, u32>> StreamingFrequencyCounter<T> {
+    /// Create a counter that keeps the counts in the given (empty) symbol table
+    pub fn with_table(min_length: usize, table: T) -> Self {
+        Self {
+            table,
+            min_length,
+            words: 0,
+            distinct: 0,
         }
+    }
 
-        // Build symbol table and count frequencies
-        for word in all_words {
-            if word.len() < min_length {
+    /// Count a single word, ignoring it if it is shorter than the minimum length
+    pub fn add(&mut self, word: String) {
+        if word.len() < self.min_length {
+            return;
+        }
+        self.words += 1;
+        self.add_count(word, 1);
+    }
+
+    /// Count all the words produced by the given iterator
+    pub fn add_words<I: IntoIterator<Item = String>>(&mut self, words: I) {
+        for word in words {
+            self.add(word);
+        }
+    }
+
+    /// Count all the words read from the given reader
+    pub fn add_reader<R: BufRead>(&mut self, reader: R) -> io::Result<()> {
+        for word in Words::new(reader) {
+            self.add(word?);
+        }
+        Ok(())
+    }
+
+    /// Count all the words in the given file
+    pub fn add_file<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        let f = File::open(path)?;
+        self.add_reader(BufReader::new(f))
+    }
+
+    /// Add the counts collected by another counter to this one. Words shorter than the minimum
+    /// length of this counter are ignored, as `add` would ignore them.
+    pub fn merge<U: KeyValueSymbolTable<String, u32>>(
+        &mut self,
+        other: &StreamingFrequencyCounter<U>,
+    ) {
+        for word in other.table.keys() {
+            if word.len() < self.min_length {
                 continue;
             }
-            words += 1;
-            if !tree.contains(word.clone()) {
-                tree.put(word, 1);
-                distinct += 1;
-            } else {
-                let current_count = tree.get(word.clone()).unwrap();
-                tree.put(word, current_count + 1);
+            let count = other.table.get(word.clone()).unwrap();
+            self.words += count as usize;
+            self.add_count(word, count);
+        }
+    }
+
+    /// Return the number of times the given word has been seen
+    pub fn frequency(&self, word: &str) -> u32 {
+        self.table.get(word.to_string()).unwrap_or(0)
+    }
+
+    /// Return the `k` most frequent words with their frequencies, most frequent first. Words
+    /// with the same frequency are returned in order.
+    ///
+    /// The `k` best words seen so far are kept in a min priority queue, so that the worst of them
+    /// can be replaced when a better word is found.
+    pub fn most_frequent(&self, k: usize) -> Vec<(String, u32)> {
+        if k == 0 {
+            return vec![];
+        }
+        // a higher count is better, and so is a smaller word on equal counts
+        let mut pq = MinPQ::with_capacity(k + 1);
+        for word in self.table.keys() {
+            let count = self.table.get(word.clone()).unwrap();
+            pq.insert((count, Reverse(word)));
+            if pq.len() > k {
+                pq.del_min();
             }
         }
 
-        // Find the key with the highest frequency
+        let mut top = Vec::with_capacity(pq.len());
+        while let Some((count, Reverse(word))) = pq.del_min() {
+            top.push((word, count));
+        }
+        top.reverse();
+        top
+    }
+
+    /// Return the symbol table holding the counts
+    pub fn table(&self) -> &T {
+        &self.table
+    }
+
+    /// Summarise the words counted so far
+    pub fn checkpoint(&self) -> FrequencyCounter {
+        // Find the key with the highest frequency. Not every backend returns its keys in order,
+        // so ties are explicitly resolved in favour of the smallest word.
         let mut max = "".to_string();
-        tree.put(max.clone(), 0);
-        for word in tree.keys() {
-            if tree.get(word.clone()).unwrap() > tree.get(max.clone()).unwrap() {
+        let mut frequency = 0;
+        for word in self.table.keys() {
+            let count = self.table.get(word.clone()).unwrap();
+            if count > frequency || (count == frequency && word < max) {
                 max = word;
+                frequency = count;
             }
         }
 
-        let frequency = tree.get(max.clone()).unwrap();
-
-        Self {
-            words,
-            distinct,
+        FrequencyCounter {
+            words: self.words,
+            distinct: self.distinct,
             max,
             frequency,
-            statistics: tree.statistics(words),
+            statistics: self.table.statistics(),
         }
     }
 
-    fn words(line: &str) -> Vec<String> {
-        line.split_ascii_whitespace()
-            .map(|s| s.to_string())
-            .collect()
+    /// Write the partial counts so that counting can be resumed with `restore`.
+    ///
+    /// The first line holds the minimum word length and the number of words counted, followed
+    /// by one `word count` line per distinct word.
+    pub fn save<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "{} {}", self.min_length, self.words)?;
+        let mut keys = self.table.keys();
+        keys.sort();
+        for word in keys {
+            let count = self.table.get(word.clone()).unwrap();
+            writeln!(writer, "{} {}", word, count)?;
+        }
+        Ok(())
+    }
+
+    /// Read back a counter written by `save`, keeping the counts in the given (empty) table
+    pub fn restore_with_table<R: BufRead>(reader: R, table: T) -> io::Result<Self> {
+        let mut lines = reader.lines();
+        let header = lines
+            .next()
+            .ok_or_else(|| invalid_data("missing header"))??;
+        let (min_length, words) = parse_pair(&header)?;
+
+        let mut counter = StreamingFrequencyCounter::with_table(min_length, table);
+        counter.words = words;
+        for line in lines {
+            let line = line?;
+            let (word, count) = line
+                .split_once(' ')
+                .ok_or_else(|| invalid_data("expected `word count`"))?;
+            let count = count.parse().map_err(invalid_data)?;
+            counter.add_count(word.to_string(), count);
+        }
+        Ok(counter)
+    }
+
+    fn add_count(&mut self, word: String, count: u32) {
+        match self.table.get(word.clone()) {
+            Some(current_count) => self.table.put(word, current_count + count),
+            None => {
+                self.table.put(word, count);
+                self.distinct += 1;
+            }
+        }
     }
 }
 
 // -------------------------------------------------------------------------------------------------
 
+/// The symbol table implementations that the frequency counter can run against
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backend {
+    BinarySearchTree,
+    BalancedTree,
+    HashTable,
+    Trie,
+}
+
+impl Backend {
+    pub const ALL: [Backend; 4] = [
+        Backend::BinarySearchTree,
+        Backend::BalancedTree,
+        Backend::HashTable,
+        Backend::Trie,
+    ];
+
+    /// Short name of the backend, as accepted by `from_str`. The balanced tree is also accepted
+    /// as `balanced`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Backend::BinarySearchTree => "bst",
+            Backend::BalancedTree => "rbt",
+            Backend::HashTable => "hash",
+            Backend::Trie => "trie",
+        }
+    }
+
+    /// Create an empty symbol table of this kind
+    pub fn table(&self) -> Box<dyn KeyValueSymbolTable<String, u32>> {
+        match self {
+            Backend::BinarySearchTree => {
+                Box::new(BinarySearchTree::<GenericItem<String, u32>>::new())
+            }
+            Backend::BalancedTree => Box::new(BalancedTree::<String, u32>::new()),
+            Backend::HashTable => Box::new(SeparateChainingHashTable::<String, u32>::new()),
+            Backend::Trie => Box::new(TernarySearchTrie::<u32>::new()),
+        }
+    }
+}
+
+impl Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Backend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "balanced" {
+            return Ok(Backend::BalancedTree);
+        }
+        Backend::ALL
+            .into_iter()
+            .find(|backend| backend.name() == s)
+            .ok_or_else(|| format!("unknown backend `{}`", s))
+    }
+}
+
+/// The result of running the frequency counter with one of the backends
+#[derive(Debug)]
+pub struct BackendComparison {
+    pub backend: Backend,
+    pub elapsed: Duration, // time taken to read and count the input
+    pub memory: usize,     // approximate bytes used by the symbol table
+    pub frequency_counter: FrequencyCounter,
+}
+
+impl BackendComparison {
+    /// Format the results side by side, one backend per row
+    pub fn table(results: &[BackendComparison]) -> String {
+        let mut s = format!(
+            "{:<10} {:>12} {:>12} {:>12}\n",
+            "backend", "put cost", "time (ms)", "memory (kB)"
+        );
+        for result in results {
+            s += &format!(
+                "{:<10} {:>12.2} {:>12.1} {:>12.1}\n",
+                result.backend.name(),
+                result.frequency_counter.statistics.average_put_cost(),
+                result.elapsed.as_secs_f64() * 1000.0,
+                result.memory as f64 / 1024.0
+            );
+        }
+        s
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+fn parse_pair(line: &str) -> io::Result<(usize, usize)> {
+    let mut fields = line.split_ascii_whitespace().map(|s| s.parse::<usize>());
+    match (fields.next(), fields.next()) {
+        (Some(a), Some(b)) => Ok((a.map_err(invalid_data)?, b.map_err(invalid_data)?)),
+        _ => Err(invalid_data("expected two numbers")),
+    }
+}
+
+fn invalid_data<E>(error: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+// -------------------------------------------------------------------------------------------------
+
 #[cfg(test)]
 mod test {
-    use super::FrequencyCounter;
+    use std::io::Cursor;
+
+    use super::{Backend, BackendComparison, FrequencyCounter, StreamingFrequencyCounter, Words};
 
     #[test]
     fn test_words() {
@@ -94,6 +439,18 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_words_iterator() {
+        let text = "it was the best\n\n  of times\nit was";
+        let ws: Vec<String> = Words::new(Cursor::new(text))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            ws,
+            vec!["it", "was", "the", "best", "of", "times", "it", "was"]
+        );
+    }
+
     #[test]
     fn test_count() {
         let frequency_counter = FrequencyCounter::new("resources/tinyTale.txt", 1);
@@ -101,11 +458,143 @@ mod test {
         assert_eq!(frequency_counter.distinct, 20);
         assert_eq!(frequency_counter.max, "it");
         assert_eq!(frequency_counter.frequency, 10);
-        
+
         // let frequency_counter = FrequencyCounter::new("/work/algs4-data/leipzig1M.txt", 10);
         // assert_eq!(frequency_counter.words, 1610829);
         // assert_eq!(frequency_counter.distinct, 165555);
         // assert_eq!(frequency_counter.max, "government");
         // assert_eq!(frequency_counter.frequency, 24763);
     }
+
+    #[test]
+    fn test_merge() {
+        let text = std::fs::read_to_string("resources/tinyTale.txt").unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        let (first, second) = lines.split_at(lines.len() / 2);
+
+        let mut a = StreamingFrequencyCounter::new(1);
+        a.add_reader(Cursor::new(first.join("\n"))).unwrap();
+        let mut b = StreamingFrequencyCounter::new(1);
+        b.add_words(second.join(" ").split_whitespace().map(String::from));
+        a.merge(&b);
+
+        let frequency_counter = a.checkpoint();
+        assert_eq!(frequency_counter.words, 60);
+        assert_eq!(frequency_counter.distinct, 20);
+        assert_eq!(frequency_counter.max, "it");
+        assert_eq!(frequency_counter.frequency, 10);
+    }
+
+    #[test]
+    fn test_most_frequent() {
+        let mut counter = StreamingFrequencyCounter::new(1);
+        counter.add_file("resources/tinyTale.txt").unwrap();
+
+        let top = counter.most_frequent(3);
+        assert_eq!(
+            top,
+            vec![("it".to_string(), 10), ("of".to_string(), 10), ("the".to_string(), 10)]
+        );
+        assert_eq!(counter.most_frequent(100).len(), 20);
+    }
+
+    #[test]
+    fn test_backends() {
+        let results =
+            FrequencyCounter::compare_backends("resources/tinyTale.txt", 1, &Backend::ALL).unwrap();
+        assert_eq!(results.len(), 4);
+        for result in &results {
+            let frequency_counter = &result.frequency_counter;
+            assert_eq!(frequency_counter.words, 60, "{}", result.backend);
+            assert_eq!(frequency_counter.distinct, 20, "{}", result.backend);
+            assert_eq!(frequency_counter.max, "it", "{}", result.backend);
+            assert_eq!(frequency_counter.frequency, 10, "{}", result.backend);
+            assert!(result.memory > 0);
+        }
+
+        let table = BackendComparison::table(&results);
+        assert_eq!(table.lines().count(), 5);
+        assert!(table.lines().nth(3).unwrap().starts_with("hash"));
+    }
+
+    #[test]
+    fn test_backend_from_str() {
+        assert_eq!("trie".parse::<Backend>(), Ok(Backend::Trie));
+        assert_eq!("bst".parse::<Backend>(), Ok(Backend::BinarySearchTree));
+        assert_eq!("rbt".parse::<Backend>(), Ok(Backend::BalancedTree));
+        assert_eq!("balanced".parse::<Backend>(), Ok(Backend::BalancedTree));
+        assert_eq!(Backend::BalancedTree.to_string(), "rbt");
+        assert!("avl".parse::<Backend>().is_err());
+    }
+
+    #[test]
+    fn test_merge_backends() {
+        let mut a = StreamingFrequencyCounter::with_table(1, Backend::HashTable.table());
+        a.add_words("a b a".split(' ').map(String::from));
+        let mut b = StreamingFrequencyCounter::with_table(1, Backend::Trie.table());
+        b.add_words("b c b".split(' ').map(String::from));
+        a.merge(&b);
+
+        assert_eq!(a.frequency("a"), 2);
+        assert_eq!(a.frequency("b"), 3);
+        assert_eq!(a.frequency("c"), 1);
+        assert_eq!(a.checkpoint().max, "b");
+    }
+
+    #[test]
+    fn test_empty_word_backends() {
+        for backend in Backend::ALL {
+            let mut counter = StreamingFrequencyCounter::with_table(0, backend.table());
+            counter.add(String::new());
+            counter.add(String::new());
+            assert_eq!(counter.frequency(""), 2, "{}", backend);
+        }
+    }
+
+    #[test]
+    fn test_merge_min_length() {
+        let mut a = StreamingFrequencyCounter::new(3);
+        a.add_words("the cat and the hat".split(' ').map(String::from));
+        let mut b = StreamingFrequencyCounter::new(1);
+        b.add_words("a cat in the hat".split(' ').map(String::from));
+        a.merge(&b);
+
+        // "a" and "in" are too short for `a`
+        assert_eq!(a.frequency("a"), 0);
+        assert_eq!(a.frequency("in"), 0);
+        assert_eq!(a.frequency("cat"), 2);
+        let frequency_counter = a.checkpoint();
+        assert_eq!(frequency_counter.words, 8);
+        assert_eq!(frequency_counter.distinct, 4);
+
+        // The other way around, all the words are long enough
+        b.merge(&a);
+        assert_eq!(b.frequency("the"), 4);
+        assert_eq!(b.checkpoint().words, 13);
+    }
+
+    #[test]
+    fn test_save_restore() {
+        let mut counter = StreamingFrequencyCounter::new(3);
+        counter
+            .add_reader(Cursor::new("the cat and the hat"))
+            .unwrap();
+
+        let mut checkpoint = vec![];
+        counter.save(&mut checkpoint).unwrap();
+        assert_eq!(
+            String::from_utf8(checkpoint.clone()).unwrap(),
+            "3 5\nand 1\ncat 1\nhat 1\nthe 2\n"
+        );
+
+        let mut restored = StreamingFrequencyCounter::restore(Cursor::new(checkpoint)).unwrap();
+        restored.add_words(vec!["a".to_string(), "cat".to_string()]);
+        assert_eq!(restored.frequency("cat"), 2);
+        assert_eq!(restored.frequency("a"), 0);
+
+        let frequency_counter = restored.checkpoint();
+        assert_eq!(frequency_counter.words, 6);
+        assert_eq!(frequency_counter.distinct, 4);
+        assert_eq!(frequency_counter.max, "cat");
+    }
 }
@@ -0,0 +1,231 @@
+//! Hash Table
+use std::{
+    collections::hash_map::DefaultHasher,
+    fmt::Debug,
+    hash::{Hash, Hasher},
+    mem,
+};
+
+use super::{
+    statistics::{Operation, OperationCounter, SymbolTableStatistics, TreeShape},
+    symboltable::KeyValueSymbolTable,
+};
+
+const INITIAL_CAPACITY: usize = 4;
+
+// Average length of the chains before the table is resized
+const MAX_CHAIN_LENGTH: usize = 10;
+
+/// Hash table with separate chaining.
+///
+/// Keys are hashed to one of `m` chains, and each chain is an un-ordered list of key, value pairs
+/// that is searched sequentially. The number of chains is doubled whenever the average chain
+/// length reaches `MAX_CHAIN_LENGTH`, so that search and insert take constant time on average.
+#[derive(Debug)]
+pub struct SeparateChainingHashTable<K, V> {
+    chains: Vec<Vec<(K, V)>>,
+    n: usize, // number of key, value pairs
+    // Number of operations, compares and resizes
+    counter: OperationCounter,
+}
+
+impl<K, V> Default for SeparateChainingHashTable<K, V>
+where
+    K: Hash + Eq,
+{
+    fn default() -> Self {
+        Self::with_capacity(INITIAL_CAPACITY)
+    }
+}
+
+impl<K, V> SeparateChainingHashTable<K, V>
+where
+    K: Hash + Eq,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a table with `m` chains
+    pub fn with_capacity(m: usize) -> Self {
+        let mut chains = Vec::with_capacity(m);
+        chains.resize_with(m.max(1), Vec::new);
+        Self {
+            chains,
+            n: 0,
+            counter: OperationCounter::new(),
+        }
+    }
+
+    /// Return the value that corresponds to the given key
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.counter.operation(Operation::Get);
+        self.chains[self.hash(key)]
+            .iter()
+            .find(|(k, _)| {
+                self.counter.compare(Operation::Get);
+                k == key
+            })
+            .map(|(_, v)| v)
+    }
+
+    /// Put the key, value pair into the table. Update the value if found, if not add the
+    /// new key value pair.
+    pub fn put(&mut self, key: K, value: V) {
+        self.counter.operation(Operation::Put);
+        if self.n >= MAX_CHAIN_LENGTH * self.chains.len() {
+            self.resize(2 * self.chains.len());
+        }
+
+        let i = self.hash(&key);
+        for (k, v) in self.chains[i].iter_mut() {
+            self.counter.compare(Operation::Put);
+            if *k == key {
+                *v = value;
+                return;
+            }
+        }
+        self.chains[i].push((key, value));
+        self.n += 1;
+    }
+
+    /// Delete the key (and value) from the table
+    pub fn delete(&mut self, key: &K) -> Option<V> {
+        self.counter.operation(Operation::Delete);
+        let i = self.hash(key);
+        let position = self.chains[i].iter().position(|(k, _)| {
+            self.counter.compare(Operation::Delete);
+            k == key
+        })?;
+        self.n -= 1;
+        Some(self.chains[i].swap_remove(position).1)
+    }
+
+    /// Return the number of key, value pairs in the table
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    /// Is the table empty or not?
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Iterate over the key, value pairs in the table, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.chains.iter().flatten().map(|(k, v)| (k, v))
+    }
+
+    /// Get the collected statistics
+    pub fn statistics(&self) -> SymbolTableStatistics {
+        SymbolTableStatistics::new(&self.counter, TreeShape::flat(self.n))
+    }
+
+    fn hash(&self, key: &K) -> usize {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        (hasher.finish() % self.chains.len() as u64) as usize
+    }
+
+    // rehash all the pairs into `m` chains
+    fn resize(&mut self, m: usize) {
+        self.counter.resize();
+        let mut chains = Vec::with_capacity(m);
+        chains.resize_with(m, Vec::new);
+        let old = mem::replace(&mut self.chains, chains);
+        for (k, v) in old.into_iter().flatten() {
+            let i = self.hash(&k);
+            self.chains[i].push((k, v));
+        }
+    }
+}
+
+impl<K, V> KeyValueSymbolTable<K, V> for SeparateChainingHashTable<K, V>
+where
+    K: Clone + Debug + Hash + Eq,
+    V: Clone + Debug,
+{
+    fn get(&self, key: K) -> Option<V> {
+        SeparateChainingHashTable::get(self, &key).cloned()
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        SeparateChainingHashTable::put(self, key, value)
+    }
+
+    fn size(&self) -> usize {
+        self.n
+    }
+
+    fn keys(&self) -> Vec<K> {
+        self.iter().map(|(k, _)| k.clone()).collect()
+    }
+
+    fn statistics(&self) -> SymbolTableStatistics {
+        SeparateChainingHashTable::statistics(self)
+    }
+
+    fn counter(&self) -> &OperationCounter {
+        &self.counter
+    }
+
+    fn memory(&self) -> usize {
+        let chains = self.chains.capacity() * mem::size_of::<Vec<(K, V)>>();
+        let pairs: usize = self.chains.iter().map(|chain| chain.capacity()).sum();
+        mem::size_of::<Self>() + chains + pairs * mem::size_of::<(K, V)>()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::SeparateChainingHashTable;
+
+    #[test]
+    fn test_put_get() {
+        let mut st = SeparateChainingHashTable::new();
+        for (i, word) in "S E A R C H E X A M P L E".split(' ').enumerate() {
+            st.put(word.to_string(), i);
+        }
+
+        assert_eq!(st.size(), 10);
+        assert_eq!(st.get(&"E".to_string()), Some(&12));
+        assert_eq!(st.get(&"S".to_string()), Some(&0));
+        assert_eq!(st.get(&"Z".to_string()), None);
+    }
+
+    #[test]
+    fn test_resize() {
+        let mut st = SeparateChainingHashTable::with_capacity(1);
+        for i in 0..1000 {
+            st.put(i, i * i);
+        }
+
+        assert_eq!(st.size(), 1000);
+        assert!(st.chains.len() >= 100);
+        for i in 0..1000 {
+            assert_eq!(st.get(&i), Some(&(i * i)));
+        }
+
+        // 1 -> 2 -> 4 -> ... -> 128 chains
+        let statistics = st.statistics();
+        assert_eq!(statistics.resizes(), 7);
+        assert_eq!(statistics.puts(), 1000);
+        assert_eq!(statistics.gets(), 1000);
+        assert_eq!(statistics.node_count(), 1000);
+    }
+
+    #[test]
+    fn test_delete() {
+        let mut st = SeparateChainingHashTable::new();
+        st.put("A", 1);
+        st.put("B", 2);
+
+        assert_eq!(st.delete(&"A"), Some(1));
+        assert_eq!(st.delete(&"A"), None);
+        assert_eq!(st.get(&"A"), None);
+        assert_eq!(st.get(&"B"), Some(&2));
+        assert_eq!(st.size(), 1);
+    }
+}
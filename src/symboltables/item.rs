@@ -99,6 +99,21 @@ impl<K: Default, V: Default> GenericItem<K, V> {
     }
 }
 
+impl<K: Default, V> GenericItem<K, V> {
+    pub fn with_value(key: K, value: V) -> Self {
+        Self {
+            key,
+            null_key: K::default(),
+            value,
+        }
+    }
+
+    /// Return the value carried by the item
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+}
+
 impl <K: Debug, V: Debug> Debug for GenericItem<K, V> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("GenericItem").field("key", &self.key).field("value", &self.value).finish()
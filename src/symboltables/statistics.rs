@@ -0,0 +1,321 @@
+//! Symbol Table Statistics
+use std::{
+    cell::Cell,
+    fmt::{self, Display},
+};
+
+/// The kinds of symbol table operations that are counted
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operation {
+    Get,
+    Put,
+    Delete,
+}
+
+/// Counts the operations performed on a symbol table and the key compares done by them.
+///
+/// The counts are kept in cells so that they can also be updated by operations that only borrow
+/// the table immutably, such as `get`.
+#[derive(Debug, Default)]
+pub struct OperationCounter {
+    operations: [Cell<usize>; 3],
+    compares: [Cell<usize>; 3],
+    rotations: Cell<usize>,
+    resizes: Cell<usize>,
+}
+
+impl OperationCounter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count one operation of the given kind
+    pub fn operation(&self, operation: Operation) {
+        increment(&self.operations[operation as usize]);
+    }
+
+    /// Count one key compare done by an operation of the given kind
+    pub fn compare(&self, operation: Operation) {
+        increment(&self.compares[operation as usize]);
+    }
+
+    /// Count one tree rotation
+    pub fn rotation(&self) {
+        increment(&self.rotations);
+    }
+
+    /// Count one resize of the underlying array
+    pub fn resize(&self) {
+        increment(&self.resizes);
+    }
+
+    /// Number of operations of the given kind
+    pub fn operations(&self, operation: Operation) -> usize {
+        self.operations[operation as usize].get()
+    }
+
+    /// Number of key compares done by operations of the given kind
+    pub fn compares(&self, operation: Operation) -> usize {
+        self.compares[operation as usize].get()
+    }
+
+    /// Number of tree rotations
+    pub fn rotations(&self) -> usize {
+        self.rotations.get()
+    }
+
+    /// Number of resizes of the underlying array
+    pub fn resizes(&self) -> usize {
+        self.resizes.get()
+    }
+}
+
+fn increment(cell: &Cell<usize>) {
+    cell.set(cell.get() + 1);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// The shape of a tree: the number of nodes, the height and the internal path length.
+///
+/// Tables that are not trees report their number of entries as the node count, with a height and
+/// internal path length of zero.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TreeShape {
+    /// Number of nodes
+    pub nodes: usize,
+    /// Depth of the deepest node, the root is at depth 0
+    pub height: usize,
+    /// Sum of the depths of all the nodes
+    pub internal_path_length: usize,
+}
+
+impl TreeShape {
+    /// The shape of a table with `n` entries that is not a tree
+    pub fn flat(n: usize) -> Self {
+        Self {
+            nodes: n,
+            ..Default::default()
+        }
+    }
+
+    /// Account for a node at the given depth
+    pub fn add_node(&mut self, depth: usize) {
+        self.nodes += 1;
+        self.height = self.height.max(depth);
+        self.internal_path_length += depth;
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Statistics collected by a symbol table: the number of operations and compares done, and the
+/// shape of the table.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SymbolTableStatistics {
+    gets: usize,
+    compares_get: usize,
+    puts: usize,
+    compares_put: usize,
+    deletes: usize,
+    compares_delete: usize,
+    rotations: usize,
+    resizes: usize,
+    shape: TreeShape,
+}
+
+impl SymbolTableStatistics {
+    pub fn new(counter: &OperationCounter, shape: TreeShape) -> Self {
+        Self {
+            gets: counter.operations(Operation::Get),
+            compares_get: counter.compares(Operation::Get),
+            puts: counter.operations(Operation::Put),
+            compares_put: counter.compares(Operation::Put),
+            deletes: counter.operations(Operation::Delete),
+            compares_delete: counter.compares(Operation::Delete),
+            rotations: counter.rotations(),
+            resizes: counter.resizes(),
+            shape,
+        }
+    }
+
+    /// Number of get (search) operations
+    pub fn gets(&self) -> usize {
+        self.gets
+    }
+
+    /// Number of compares done by get operations
+    pub fn compares_get(&self) -> usize {
+        self.compares_get
+    }
+
+    /// Number of put (insert) operations
+    pub fn puts(&self) -> usize {
+        self.puts
+    }
+
+    /// Number of compares done by put operations
+    pub fn compares_put(&self) -> usize {
+        self.compares_put
+    }
+
+    /// Number of delete operations
+    pub fn deletes(&self) -> usize {
+        self.deletes
+    }
+
+    /// Number of compares done by delete operations
+    pub fn compares_delete(&self) -> usize {
+        self.compares_delete
+    }
+
+    /// Number of tree rotations performed
+    pub fn rotations(&self) -> usize {
+        self.rotations
+    }
+
+    /// Number of times the underlying array was resized
+    pub fn resizes(&self) -> usize {
+        self.resizes
+    }
+
+    /// Number of nodes in the table
+    pub fn node_count(&self) -> usize {
+        self.shape.nodes
+    }
+
+    /// Height of the tree
+    pub fn height(&self) -> usize {
+        self.shape.height
+    }
+
+    /// Sum of the depths of all the nodes
+    pub fn internal_path_length(&self) -> usize {
+        self.shape.internal_path_length
+    }
+
+    /// Average depth of a node
+    pub fn average_depth(&self) -> f64 {
+        average(self.shape.internal_path_length, self.shape.nodes)
+    }
+
+    /// Average number of compares per get operation
+    pub fn average_get_cost(&self) -> f64 {
+        average(self.compares_get, self.gets)
+    }
+
+    /// Average cost of a put operation.
+    ///
+    /// This is 1 + the total number of comparisons done during puts divided by the total number of
+    /// put operations.
+    ///
+    /// The theoretical value is ~ 1.39 lg N
+    /// See: Algorithms, 4th edition, Robert Sedgewick, Kevin Wayne, Addition-Wesley, 2011
+    pub fn average_put_cost(&self) -> f64 {
+        if self.puts == 0 {
+            return 0.0;
+        }
+        1.0 + average(self.compares_put, self.puts)
+    }
+
+    /// Average number of compares per delete operation
+    pub fn average_delete_cost(&self) -> f64 {
+        average(self.compares_delete, self.deletes)
+    }
+}
+
+fn average(total: usize, n: usize) -> f64 {
+    if n == 0 {
+        0.0
+    } else {
+        total as f64 / n as f64
+    }
+}
+
+impl Display for SymbolTableStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "puts:                 {} (cost {:.2})",
+            self.puts,
+            self.average_put_cost()
+        )?;
+        writeln!(
+            f,
+            "gets:                 {} ({:.2} compares)",
+            self.gets,
+            self.average_get_cost()
+        )?;
+        writeln!(
+            f,
+            "deletes:              {} ({:.2} compares)",
+            self.deletes,
+            self.average_delete_cost()
+        )?;
+        writeln!(f, "nodes:                {}", self.shape.nodes)?;
+        writeln!(f, "height:               {}", self.shape.height)?;
+        writeln!(
+            f,
+            "internal path length: {} (average depth {:.2})",
+            self.shape.internal_path_length,
+            self.average_depth()
+        )?;
+        writeln!(f, "rotations:            {}", self.rotations)?;
+        write!(f, "resizes:              {}", self.resizes)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::{Operation, OperationCounter, SymbolTableStatistics, TreeShape};
+
+    #[test]
+    fn test_statistics() {
+        let counter = OperationCounter::new();
+        for _ in 0..4 {
+            counter.operation(Operation::Put);
+        }
+        for _ in 0..6 {
+            counter.compare(Operation::Put);
+        }
+        counter.operation(Operation::Get);
+        counter.compare(Operation::Get);
+        counter.compare(Operation::Get);
+        counter.rotation();
+
+        //       B
+        //      / \
+        //     A   C
+        //          \
+        //           D
+        let mut shape = TreeShape::default();
+        for depth in [0, 1, 1, 2] {
+            shape.add_node(depth);
+        }
+
+        let statistics = SymbolTableStatistics::new(&counter, shape);
+        assert_eq!(statistics.puts(), 4);
+        assert_eq!(statistics.average_put_cost(), 2.5);
+        assert_eq!(statistics.average_get_cost(), 2.0);
+        assert_eq!(statistics.average_delete_cost(), 0.0);
+        assert_eq!(statistics.node_count(), 4);
+        assert_eq!(statistics.height(), 2);
+        assert_eq!(statistics.internal_path_length(), 4);
+        assert_eq!(statistics.average_depth(), 1.0);
+        assert_eq!(statistics.rotations(), 1);
+        assert_eq!(statistics.resizes(), 0);
+
+        let s = statistics.to_string();
+        assert_eq!(s.lines().count(), 8);
+        assert!(s.starts_with("puts:                 4 (cost 2.50)"));
+    }
+
+    #[test]
+    fn test_empty() {
+        let statistics = SymbolTableStatistics::default();
+        assert_eq!(statistics.average_put_cost(), 0.0);
+        assert_eq!(statistics.average_depth(), 0.0);
+    }
+}
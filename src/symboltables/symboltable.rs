@@ -2,7 +2,12 @@
 
 use std::{fmt::Debug, rc::Rc};
 
-use super::item::Item;
+use crate::sorting::quicksort::{quick_sort, quickselect};
+
+use super::{
+    item::Item,
+    statistics::{Operation, OperationCounter, SymbolTableStatistics, TreeShape},
+};
 
 /// The interface of a symbol table
 pub trait SymbolTable<I: Item + PartialEq, K> {
@@ -23,6 +28,80 @@ pub trait SymbolTable<I: Item + PartialEq, K> {
 
     /// Display the items
     fn show(&self) -> Vec<I>;
+
+    /// Get the collected statistics
+    fn statistics(&self) -> SymbolTableStatistics;
+}
+
+/// The interface of a symbol table that associates values with keys.
+///
+/// This is the API used by clients such as the `FrequencyCounter`, so that the same client can be
+/// run against different symbol table implementations.
+pub trait KeyValueSymbolTable<K, V> {
+    /// Return the value paired with the given key
+    fn get(&self, key: K) -> Option<V>;
+
+    /// Put the key, value pair into the table, replacing the value if the key is already present
+    fn put(&mut self, key: K, value: V);
+
+    /// Returns whether there's a value paired with the given key in the table
+    fn contains(&self, key: K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Return the number of key, value pairs in the table
+    fn size(&self) -> usize;
+
+    /// Return all the keys in the table
+    fn keys(&self) -> Vec<K>;
+
+    /// Get the collected statistics
+    fn statistics(&self) -> SymbolTableStatistics;
+
+    /// Get the operation counts collected so far. Unlike `statistics`, this does not need to
+    /// traverse the table, so it can be called after every operation.
+    fn counter(&self) -> &OperationCounter;
+
+    /// Approximate number of bytes used by the structure of the table (nodes, links and arrays),
+    /// not counting any heap memory owned by the keys and values themselves.
+    fn memory(&self) -> usize;
+}
+
+impl<K, V, T> KeyValueSymbolTable<K, V> for Box<T>
+where
+    T: KeyValueSymbolTable<K, V> + ?Sized,
+{
+    fn get(&self, key: K) -> Option<V> {
+        (**self).get(key)
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        (**self).put(key, value)
+    }
+
+    fn contains(&self, key: K) -> bool {
+        (**self).contains(key)
+    }
+
+    fn size(&self) -> usize {
+        (**self).size()
+    }
+
+    fn keys(&self) -> Vec<K> {
+        (**self).keys()
+    }
+
+    fn statistics(&self) -> SymbolTableStatistics {
+        (**self).statistics()
+    }
+
+    fn counter(&self) -> &OperationCounter {
+        (**self).counter()
+    }
+
+    fn memory(&self) -> usize {
+        (**self).memory()
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -34,6 +113,7 @@ pub trait SymbolTable<I: Item + PartialEq, K> {
 pub struct KeyIndexedSymbolTable<I: Item<Key = usize>> {
     items: Vec<I>,
     m: usize,
+    counter: OperationCounter,
 }
 
 impl<I> KeyIndexedSymbolTable<I>
@@ -42,7 +122,8 @@ where
 {
     pub fn new(m: usize) -> Self {
         let items = vec![I::default(); m];
-        Self { items, m }
+        let counter = OperationCounter::new();
+        Self { items, m, counter }
     }
 }
 
@@ -60,18 +141,24 @@ where
         n
     }
 
+    // The key is used as an index, so no compares are needed
     fn search(&self, key: usize) -> Option<I> {
+        self.counter.operation(Operation::Get);
         Some(self.items[key].clone())
     }
 
     fn insert(&mut self, item: I) {
+        self.counter.operation(Operation::Put);
         self.items[item.key()] = item.clone();
     }
 
     fn remove(&mut self, item: I) {
+        self.counter.operation(Operation::Delete);
         self.items[item.key()] = I::default();
     }
 
+    // The keys are the indices of the array, so the items are already in order and the k-th
+    // item is found by a scan: quickselect would only add the cost of copying the array
     fn select(&self, k: usize) -> I {
         let mut k = k;
         for i in 0..self.m {
@@ -95,6 +182,10 @@ where
         }
         result
     }
+
+    fn statistics(&self) -> SymbolTableStatistics {
+        SymbolTableStatistics::new(&self.counter, TreeShape::flat(self.count()))
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -105,6 +196,7 @@ where
 pub struct ArraySymbolTable<I: Item> {
     items: Vec<I>,
     count: usize,
+    counter: OperationCounter,
 }
 
 impl<I> ArraySymbolTable<I>
@@ -114,13 +206,24 @@ where
     pub fn new(m: usize) -> Self {
         let items = vec![I::default(); m];
         let count = 0;
-        Self { items, count }
+        let counter = OperationCounter::new();
+        Self {
+            items,
+            count,
+            counter,
+        }
     }
 
     /// Find the index of the given item if it exists
     pub fn find_index(&self, item: I) -> Option<usize> {
         (0..self.count).find(|&i| self.items[i] == item)
     }
+
+    // compare the keys of two items during insert
+    fn less(&self, a: &I, b: &I) -> bool {
+        self.counter.compare(Operation::Put);
+        a.key() < b.key()
+    }
 }
 
 impl<I> SymbolTable<I, I::Key> for ArraySymbolTable<I>
@@ -132,13 +235,16 @@ where
     }
 
     fn search(&self, key: I::Key) -> Option<I> {
+        self.counter.operation(Operation::Get);
         let mut k = 0;
         for i in 0..self.count {
+            self.counter.compare(Operation::Get);
             if self.items[i].key() >= key {
                 break;
             }
             k += 1;
         }
+        self.counter.compare(Operation::Get);
         if key == self.items[k].key() {
             return Some(self.items[k].clone());
         }
@@ -148,9 +254,10 @@ where
     // Keep the array in order when inserting a new item by moving larger items to make room,
     // in the same manner as insertion sort.
     fn insert(&mut self, item: I) {
+        self.counter.operation(Operation::Put);
         let mut i = self.count;
 
-        while i > 0 && item.key() < self.items[i - 1].key() {
+        while i > 0 && self.less(&item, &self.items[i - 1]) {
             self.items[i] = self.items[i - 1].clone();
             i -= 1;
         }
@@ -159,18 +266,26 @@ where
     }
 
     fn remove(&mut self, item: I) {
+        self.counter.operation(Operation::Delete);
         // find the index of the item in the array
-        if let Some(i) = self.find_index(item) {
+        let index = (0..self.count).find(|&i| {
+            self.counter.compare(Operation::Delete);
+            self.items[i] == item
+        });
+        if let Some(i) = index {
             // shift the elements from higher indices so the current element is overwritten
             let mut j = i;
-            while j < self.count {
+            while j + 1 < self.count {
                 self.items[j] = self.items[j + 1].clone();
                 j += 1;
             }
-            self.items[j - 1] = I::default();
+            self.count -= 1;
+            self.items[self.count] = I::default();
         }
     }
 
+    // The array is kept in order by insert, so the k-th item is at index k and quickselect is
+    // not needed
     fn select(&self, k: usize) -> I {
         self.items[k].clone()
     }
@@ -187,6 +302,10 @@ where
         }
         result
     }
+
+    fn statistics(&self) -> SymbolTableStatistics {
+        SymbolTableStatistics::new(&self.counter, TreeShape::flat(self.count))
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -211,6 +330,7 @@ impl<I: Item> Node<I> {
 pub struct LinkedSymbolTable<I: Item> {
     head: Link<I>,
     count: usize,
+    counter: OperationCounter,
 }
 
 impl<I> LinkedSymbolTable<I>
@@ -221,17 +341,30 @@ where
         Self {
             head: None,
             count: 0,
+            counter: OperationCounter::new(),
+        }
+    }
+
+    // the items in the order of the list
+    fn items(&self) -> Vec<I> {
+        let mut items = Vec::with_capacity(self.count);
+        let mut link = &self.head;
+        while let Some(node) = link {
+            items.push(node.item.clone());
+            link = &node.next;
         }
+        items
     }
 
     // recursive implementation of search.
-    fn search_r(link: &Link<I>, key: I::Key) -> Option<I> {
+    fn search_r(link: &Link<I>, key: I::Key, counter: &OperationCounter) -> Option<I> {
         match link {
             Some(t) => {
+                counter.compare(Operation::Get);
                 if t.item.key() == key {
                     Some(t.item.clone())
                 } else {
-                    LinkedSymbolTable::search_r(&t.next, key)
+                    LinkedSymbolTable::search_r(&t.next, key, counter)
                 }
             }
             None => None,
@@ -248,25 +381,41 @@ where
     }
 
     fn search(&self, key: I::Key) -> Option<I> {
-        LinkedSymbolTable::search_r(&self.head, key)
+        self.counter.operation(Operation::Get);
+        LinkedSymbolTable::search_r(&self.head, key, &self.counter)
     }
 
+    // New items are added at the front of the list, so no compares are needed
     fn insert(&mut self, item: I) {
+        self.counter.operation(Operation::Put);
         self.head = Some(Rc::new(Node::new(item, self.head.clone())));
+        self.count += 1;
     }
 
     fn remove(&mut self, _item: I) {
         todo!()
     }
 
-    // Since the list is not in order this is not implemented
-    fn select(&self, _k: usize) -> I {
-        todo!()
+    // The list is not in order, so the items are copied to an array to select from. This is the
+    // only table that needs quickselect, since the array and key indexed tables are in order.
+    fn select(&self, k: usize) -> I {
+        let mut items = self.items();
+        if k >= items.len() {
+            return I::default();
+        }
+        quickselect(&mut items, k);
+        items.swap_remove(k)
     }
 
-    // The list is not in order. `show` should return items in order for a correct implementation.
+    // The list is not in order, so the items are sorted before they are returned
     fn show(&self) -> Vec<I> {
-        todo!()
+        let mut items = self.items();
+        quick_sort(&mut items);
+        items
+    }
+
+    fn statistics(&self) -> SymbolTableStatistics {
+        SymbolTableStatistics::new(&self.counter, TreeShape::flat(self.count))
     }
 }
 
@@ -274,7 +423,7 @@ where
 
 #[cfg(test)]
 mod test {
-    use crate::symboltables::item::DoubleItem;
+    use crate::symboltables::item::{DoubleItem, Item};
 
     use super::{ArraySymbolTable, KeyIndexedSymbolTable, LinkedSymbolTable, SymbolTable};
 
@@ -289,6 +438,11 @@ mod test {
         // select 5th smallest item
         let _k = st.select(5);
         assert_eq!(_k, DoubleItem::with_key(5));
+
+        let statistics = st.statistics();
+        assert_eq!(statistics.puts(), 10);
+        assert_eq!(statistics.compares_put(), 0);
+        assert_eq!(statistics.node_count(), 10);
     }
 
     #[test]
@@ -316,6 +470,37 @@ mod test {
 
         let expected = vec![i1, i2];
         assert_eq!(st.show(), expected);
+        assert_eq!(st.count(), 2);
+
+        let statistics = st.statistics();
+        assert_eq!(statistics.puts(), 3);
+        // 15 is compared with 20 and 10
+        assert_eq!(statistics.compares_put(), 3);
+        assert_eq!(statistics.gets(), 2);
+        assert_eq!(statistics.deletes(), 1);
+        assert_eq!(statistics.compares_delete(), 2);
+        assert_eq!(statistics.node_count(), 2);
+    }
+
+    #[test]
+    fn test_array_symbol_table_remove() {
+        // A full array: removing used to read past its end, and did not update the count
+        let mut st = ArraySymbolTable::new(3);
+        for key in [30, 10, 20] {
+            st.insert(DoubleItem::with_key(key));
+        }
+        assert_eq!(st.count(), 3);
+        st.remove(DoubleItem::with_key(10));
+        assert_eq!(st.count(), 2);
+        assert_eq!(st.search(10), None);
+
+        // Removing a missing item changes nothing, and the freed slot can be reused
+        st.remove(DoubleItem::with_key(10));
+        assert_eq!(st.count(), 2);
+        st.remove(DoubleItem::with_key(30));
+        st.insert(DoubleItem::with_key(5));
+        let keys: Vec<_> = st.show().iter().map(|item| item.key()).collect();
+        assert_eq!(keys, vec![5, 20]);
     }
 
     #[test]
@@ -327,10 +512,23 @@ mod test {
         st.insert(i1);
         st.insert(i2);
         st.insert(i3);
+        assert_eq!(st.count(), 3);
 
         assert_eq!(st.search(15), Some(DoubleItem::with_key(15)));
 
         // non-existent item
         assert_eq!(st.search(150), None);
+
+        let statistics = st.statistics();
+        assert_eq!(statistics.node_count(), 3);
+        // 15 is found at the front of the list, 150 is compared with every item
+        assert_eq!(statistics.compares_get(), 4);
+
+        // select and show put the items in order
+        assert_eq!(st.select(0), DoubleItem::with_key(10));
+        assert_eq!(st.select(2), DoubleItem::with_key(20));
+        assert_eq!(st.select(3), DoubleItem::default());
+        let keys: Vec<_> = st.show().iter().map(|item| item.key()).collect();
+        assert_eq!(keys, vec![10, 15, 20]);
     }
 }
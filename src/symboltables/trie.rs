@@ -0,0 +1,344 @@
+//! Ternary Search Trie
+use std::{cmp::Ordering, fmt::Debug, mem};
+
+use super::{
+    statistics::{Operation, OperationCounter, SymbolTableStatistics, TreeShape},
+    symboltable::KeyValueSymbolTable,
+};
+
+type Link<V> = Option<Box<Node<V>>>;
+
+/// Nodes of the trie. Each node holds a character and three links, to the tries of keys whose
+/// next character is smaller than, equal to or larger than the node's character.
+#[derive(Debug)]
+struct Node<V> {
+    c: char,
+    value: Option<V>, // value of the key that ends at this node
+    left: Link<V>,
+    mid: Link<V>,
+    right: Link<V>,
+}
+
+impl<V> Node<V> {
+    pub fn new(c: char) -> Box<Node<V>> {
+        Box::new(Self {
+            c,
+            value: None,
+            left: None,
+            mid: None,
+            right: None,
+        })
+    }
+}
+
+/// A symbol table with string keys, implemented as a ternary search trie.
+///
+/// Search and insert examine each character of the key once, plus about ln R compares per
+/// character to find the next node, where R is the size of the alphabet.
+#[derive(Debug)]
+pub struct TernarySearchTrie<V> {
+    root: Link<V>,
+    empty: Option<V>, // value of the empty key, which has no node
+    n: usize,         // number of keys
+    nodes: usize,     // number of nodes
+    // Number of operations and character compares
+    counter: OperationCounter,
+}
+
+impl<V> Default for TernarySearchTrie<V> {
+    fn default() -> Self {
+        Self {
+            root: None,
+            empty: None,
+            n: 0,
+            nodes: 0,
+            counter: OperationCounter::new(),
+        }
+    }
+}
+
+impl<V: Clone> TernarySearchTrie<V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the value that corresponds to the given key
+    pub fn get(&self, key: &str) -> Option<&V> {
+        self.counter.operation(Operation::Get);
+        let chars: Vec<char> = key.chars().collect();
+        if chars.is_empty() {
+            return self.empty.as_ref();
+        }
+        self.get_r(&self.root, &chars, 0)?.value.as_ref()
+    }
+
+    fn get_r<'a>(&self, link: &'a Link<V>, key: &[char], d: usize) -> Option<&'a Node<V>> {
+        let node = link.as_ref()?;
+        self.counter.compare(Operation::Get);
+        match key[d].cmp(&node.c) {
+            Ordering::Less => self.get_r(&node.left, key, d),
+            Ordering::Greater => self.get_r(&node.right, key, d),
+            Ordering::Equal if d < key.len() - 1 => self.get_r(&node.mid, key, d + 1),
+            Ordering::Equal => Some(node),
+        }
+    }
+
+    /// Put the key, value pair into the table. Update the value if found, if not add the
+    /// new key value pair. The value of the empty key is kept next to the root.
+    pub fn put(&mut self, key: &str, value: V) {
+        let chars: Vec<char> = key.chars().collect();
+        self.counter.operation(Operation::Put);
+        let added = if chars.is_empty() {
+            self.empty.replace(value).is_none()
+        } else {
+            let mut added = false;
+            let root = self.root.take();
+            self.root = Some(self.put_r(root, &chars, value, 0, &mut added));
+            added
+        };
+        if added {
+            self.n += 1;
+        }
+    }
+
+    fn put_r(
+        &mut self,
+        link: Link<V>,
+        key: &[char],
+        value: V,
+        d: usize,
+        added: &mut bool,
+    ) -> Box<Node<V>> {
+        let c = key[d];
+        let mut node = match link {
+            Some(node) => node,
+            None => {
+                self.nodes += 1;
+                Node::new(c)
+            }
+        };
+        self.counter.compare(Operation::Put);
+        match c.cmp(&node.c) {
+            Ordering::Less => node.left = Some(self.put_r(node.left.take(), key, value, d, added)),
+            Ordering::Greater => {
+                node.right = Some(self.put_r(node.right.take(), key, value, d, added))
+            }
+            Ordering::Equal if d < key.len() - 1 => {
+                node.mid = Some(self.put_r(node.mid.take(), key, value, d + 1, added))
+            }
+            Ordering::Equal => {
+                *added = node.value.is_none();
+                node.value = Some(value);
+            }
+        }
+        node
+    }
+
+    /// Return the number of key, value pairs in the table
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    /// Is the table empty or not?
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Return all keys in the table in sorted order
+    pub fn keys(&self) -> Vec<String> {
+        let mut result = vec![];
+        if self.empty.is_some() {
+            result.push(String::new());
+        }
+        TernarySearchTrie::collect_r(&self.root, &mut String::new(), &mut result);
+        result
+    }
+
+    /// Return all keys in the table that start with the given prefix, in sorted order
+    pub fn keys_with_prefix(&self, prefix: &str) -> Vec<String> {
+        let chars: Vec<char> = prefix.chars().collect();
+        if chars.is_empty() {
+            return self.keys();
+        }
+        let mut result = vec![];
+        if let Some(node) = self.get_r(&self.root, &chars, 0) {
+            if node.value.is_some() {
+                result.push(prefix.to_string());
+            }
+            TernarySearchTrie::collect_r(&node.mid, &mut prefix.to_string(), &mut result);
+        }
+        result
+    }
+
+    /// Return the longest key in the table that is a prefix of the query, if there is one
+    pub fn longest_prefix_of<'a>(&self, query: &'a str) -> Option<&'a str> {
+        self.counter.operation(Operation::Get);
+        let mut link = &self.root;
+        let mut chars = query.char_indices();
+        let mut next = chars.next();
+        let mut length = self.empty.as_ref().map(|_| 0);
+        while let (Some(node), Some((i, c))) = (link, next) {
+            self.counter.compare(Operation::Get);
+            match c.cmp(&node.c) {
+                Ordering::Less => link = &node.left,
+                Ordering::Greater => link = &node.right,
+                Ordering::Equal => {
+                    if node.value.is_some() {
+                        length = Some(i + c.len_utf8());
+                    }
+                    link = &node.mid;
+                    next = chars.next();
+                }
+            }
+        }
+        length.map(|length| &query[..length])
+    }
+
+    fn collect_r(link: &Link<V>, prefix: &mut String, acc: &mut Vec<String>) {
+        if let Some(node) = link {
+            TernarySearchTrie::collect_r(&node.left, prefix, acc);
+            prefix.push(node.c);
+            if node.value.is_some() {
+                acc.push(prefix.clone());
+            }
+            TernarySearchTrie::collect_r(&node.mid, prefix, acc);
+            prefix.pop();
+            TernarySearchTrie::collect_r(&node.right, prefix, acc);
+        }
+    }
+
+    /// Get the collected statistics. The shape is that of the trie's nodes, one per character.
+    pub fn statistics(&self) -> SymbolTableStatistics {
+        let mut shape = TreeShape::default();
+        TernarySearchTrie::shape_r(&self.root, 0, &mut shape);
+        SymbolTableStatistics::new(&self.counter, shape)
+    }
+
+    fn shape_r(link: &Link<V>, depth: usize, shape: &mut TreeShape) {
+        if let Some(node) = link {
+            shape.add_node(depth);
+            TernarySearchTrie::shape_r(&node.left, depth + 1, shape);
+            TernarySearchTrie::shape_r(&node.mid, depth + 1, shape);
+            TernarySearchTrie::shape_r(&node.right, depth + 1, shape);
+        }
+    }
+}
+
+impl<V> KeyValueSymbolTable<String, V> for TernarySearchTrie<V>
+where
+    V: Clone + Debug,
+{
+    fn get(&self, key: String) -> Option<V> {
+        TernarySearchTrie::get(self, &key).cloned()
+    }
+
+    fn put(&mut self, key: String, value: V) {
+        TernarySearchTrie::put(self, &key, value)
+    }
+
+    fn size(&self) -> usize {
+        self.n
+    }
+
+    fn keys(&self) -> Vec<String> {
+        TernarySearchTrie::keys(self)
+    }
+
+    fn statistics(&self) -> SymbolTableStatistics {
+        TernarySearchTrie::statistics(self)
+    }
+
+    fn counter(&self) -> &OperationCounter {
+        &self.counter
+    }
+
+    fn memory(&self) -> usize {
+        mem::size_of::<Self>() + self.nodes * mem::size_of::<Node<V>>()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::TernarySearchTrie;
+
+    #[test]
+    fn test_put_get() {
+        let trie = make_trie();
+
+        assert_eq!(trie.size(), 8);
+        assert_eq!(trie.get("shells"), Some(&3));
+        assert_eq!(trie.get("she"), Some(&0));
+        assert_eq!(trie.get("sea"), Some(&6));
+        assert_eq!(trie.get("shell"), None);
+        assert_eq!(trie.get(""), None);
+    }
+
+    #[test]
+    fn test_keys() {
+        let trie = make_trie();
+        assert_eq!(
+            trie.keys(),
+            vec!["by", "sea", "sells", "she", "shells", "shore", "surely", "the"]
+        );
+        assert_eq!(trie.keys_with_prefix("sh"), vec!["she", "shells", "shore"]);
+        assert_eq!(trie.keys_with_prefix("she"), vec!["she", "shells"]);
+        assert!(trie.keys_with_prefix("x").is_empty());
+        assert_eq!(trie.longest_prefix_of("shellsort"), Some("shells"));
+        assert_eq!(trie.longest_prefix_of("shell"), Some("she"));
+        assert_eq!(trie.longest_prefix_of("sh"), None);
+        assert_eq!(trie.longest_prefix_of(""), None);
+    }
+
+    #[test]
+    fn test_statistics() {
+        let mut trie = TernarySearchTrie::new();
+        trie.put("by", 0);
+        trie.put("be", 1);
+        assert_eq!(trie.get("be"), Some(&1));
+
+        //    b
+        //    |
+        //    y
+        //   /
+        //  e
+        let statistics = trie.statistics();
+        assert_eq!(statistics.puts(), 2);
+        assert_eq!(statistics.compares_put(), 5);
+        assert_eq!(statistics.compares_get(), 3);
+        assert_eq!(statistics.node_count(), 3);
+        assert_eq!(statistics.height(), 2);
+        assert_eq!(statistics.internal_path_length(), 3);
+    }
+
+    #[test]
+    fn test_empty_key() {
+        let mut trie = make_trie();
+        trie.put("", 9);
+        assert_eq!(trie.get(""), Some(&9));
+        assert_eq!(trie.size(), 9);
+        trie.put("", 10);
+        assert_eq!(trie.get(""), Some(&10));
+        assert_eq!(trie.size(), 9);
+        assert_eq!(trie.keys()[..2], ["", "by"]);
+        assert_eq!(trie.keys_with_prefix("").len(), 9);
+        assert_eq!(trie.keys_with_prefix("sh"), vec!["she", "shells", "shore"]);
+        assert_eq!(trie.longest_prefix_of("sh"), Some(""));
+        assert_eq!(trie.longest_prefix_of("shell"), Some("she"));
+        // The empty key has no node
+        assert_eq!(
+            trie.statistics().node_count(),
+            make_trie().statistics().node_count()
+        );
+    }
+
+    fn make_trie() -> TernarySearchTrie<usize> {
+        let mut trie = TernarySearchTrie::new();
+        let words = "she sells sea shells by the sea shore surely";
+        for (i, word) in words.split(' ').enumerate() {
+            trie.put(word, i);
+        }
+        trie
+    }
+}
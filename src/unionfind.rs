@@ -0,0 +1,454 @@
+//! Union-Find
+//!
+//! Dynamic connectivity: sites are numbered from 0 to n - 1, and `union` connects two sites. The
+//! sites are partitioned into components, each identified by one of its sites, and `find`
+//! returns the identifier of the component of a site.
+//!
+//! See: Algorithms, 4th edition, Robert Sedgewick, Kevin Wayne, Addison-Wesley, 2011, section 1.5.
+use std::fmt::Debug;
+
+use crate::symboltables::balancedtree::BalancedTree;
+
+/// The interface of a union-find data structure
+pub trait UnionFind {
+    /// Add a new site in a component of its own, and return its number
+    fn add(&mut self) -> usize;
+
+    /// Connect the sites `p` and `q`, merging their components
+    fn union(&mut self, p: usize, q: usize);
+
+    /// Return the identifier of the component of site `p`
+    fn find(&mut self, p: usize) -> usize;
+
+    /// Are the sites `p` and `q` in the same component?
+    fn connected(&mut self, p: usize, q: usize) -> bool {
+        self.find(p) == self.find(q)
+    }
+
+    /// Return the number of components
+    fn count(&self) -> usize;
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Quick-find: all the sites of a component have the same identifier, so `find` takes constant
+/// time, but `union` has to relabel a whole component and takes linear time.
+#[derive(Clone, Debug, Default)]
+pub struct QuickFindUF {
+    id: Vec<usize>,
+    count: usize,
+}
+
+impl QuickFindUF {
+    pub fn new(n: usize) -> Self {
+        Self {
+            id: (0..n).collect(),
+            count: n,
+        }
+    }
+}
+
+impl UnionFind for QuickFindUF {
+    fn add(&mut self) -> usize {
+        let p = self.id.len();
+        self.id.push(p);
+        self.count += 1;
+        p
+    }
+
+    fn union(&mut self, p: usize, q: usize) {
+        let (p_id, q_id) = (self.id[p], self.id[q]);
+        if p_id == q_id {
+            return;
+        }
+        for id in self.id.iter_mut() {
+            if *id == p_id {
+                *id = q_id;
+            }
+        }
+        self.count -= 1;
+    }
+
+    fn find(&mut self, p: usize) -> usize {
+        self.id[p]
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Quick-union: every component is a tree of links to parent sites, identified by its root.
+/// `union` links one root to the other, but the trees can get as high as the number of sites.
+#[derive(Clone, Debug, Default)]
+pub struct QuickUnionUF {
+    parent: Vec<usize>,
+    count: usize,
+}
+
+impl QuickUnionUF {
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            count: n,
+        }
+    }
+}
+
+impl UnionFind for QuickUnionUF {
+    fn add(&mut self) -> usize {
+        let p = self.parent.len();
+        self.parent.push(p);
+        self.count += 1;
+        p
+    }
+
+    fn union(&mut self, p: usize, q: usize) {
+        let (p_root, q_root) = (self.find(p), self.find(q));
+        if p_root == q_root {
+            return;
+        }
+        self.parent[p_root] = q_root;
+        self.count -= 1;
+    }
+
+    fn find(&mut self, p: usize) -> usize {
+        root(&self.parent, p)
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
+
+fn root(parent: &[usize], mut p: usize) -> usize {
+    while p != parent[p] {
+        p = parent[p];
+    }
+    p
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Weighted quick-union: the root of the smaller tree is linked to the root of the larger one,
+/// which keeps the height of the trees below lg N.
+#[derive(Clone, Debug, Default)]
+pub struct WeightedQuickUnionUF {
+    parent: Vec<usize>,
+    size: Vec<usize>, // number of sites in the tree rooted at each site
+    count: usize,
+}
+
+impl WeightedQuickUnionUF {
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            count: n,
+        }
+    }
+}
+
+impl UnionFind for WeightedQuickUnionUF {
+    fn add(&mut self) -> usize {
+        let p = self.parent.len();
+        self.parent.push(p);
+        self.size.push(1);
+        self.count += 1;
+        p
+    }
+
+    fn union(&mut self, p: usize, q: usize) {
+        let (p_root, q_root) = (self.find(p), self.find(q));
+        if p_root == q_root {
+            return;
+        }
+        link_by_size(&mut self.parent, &mut self.size, p_root, q_root);
+        self.count -= 1;
+    }
+
+    fn find(&mut self, p: usize) -> usize {
+        root(&self.parent, p)
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
+
+// Link the root of the smaller tree to the root of the larger one
+fn link_by_size(parent: &mut [usize], size: &mut [usize], p_root: usize, q_root: usize) {
+    if size[p_root] < size[q_root] {
+        parent[p_root] = q_root;
+        size[q_root] += size[p_root];
+    } else {
+        parent[q_root] = p_root;
+        size[p_root] += size[q_root];
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Weighted quick-union with path compression: `find` also links every site on the path to the
+/// root directly to the root, which makes the trees almost flat. Any sequence of M operations on
+/// N sites takes close to linear time (M α(N), with α the inverse Ackermann function).
+#[derive(Clone, Debug, Default)]
+pub struct WeightedQuickUnionPathCompressionUF {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    count: usize,
+}
+
+impl WeightedQuickUnionPathCompressionUF {
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            count: n,
+        }
+    }
+}
+
+impl UnionFind for WeightedQuickUnionPathCompressionUF {
+    fn add(&mut self) -> usize {
+        let p = self.parent.len();
+        self.parent.push(p);
+        self.size.push(1);
+        self.count += 1;
+        p
+    }
+
+    fn union(&mut self, p: usize, q: usize) {
+        let (p_root, q_root) = (self.find(p), self.find(q));
+        if p_root == q_root {
+            return;
+        }
+        link_by_size(&mut self.parent, &mut self.size, p_root, q_root);
+        self.count -= 1;
+    }
+
+    fn find(&mut self, p: usize) -> usize {
+        let root = root(&self.parent, p);
+        let mut p = p;
+        while p != root {
+            let next = self.parent[p];
+            self.parent[p] = root;
+            p = next;
+        }
+        root
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Union-find over arbitrary keys.
+///
+/// Every key is given a site number the first time it is seen, kept in a `BalancedTree`, and the
+/// connectivity of the sites is tracked by the union-find `U`.
+pub struct KeyedUnionFind<K, U = WeightedQuickUnionPathCompressionUF> {
+    sites: BalancedTree<K, usize>, // site number of each key
+    keys: Vec<K>,                  // key of each site
+    uf: U,
+}
+
+impl<K, U> Default for KeyedUnionFind<K, U>
+where
+    K: Clone + Debug + Ord,
+    U: UnionFind + Default,
+{
+    fn default() -> Self {
+        Self {
+            sites: BalancedTree::new(),
+            keys: vec![],
+            uf: U::default(),
+        }
+    }
+}
+
+impl<K, U> KeyedUnionFind<K, U>
+where
+    K: Clone + Debug + Ord,
+    U: UnionFind + Default,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Connect the keys `p` and `q`, adding them if they have not been seen before
+    pub fn union(&mut self, p: K, q: K) {
+        let p = self.site(p);
+        let q = self.site(q);
+        self.uf.union(p, q);
+    }
+
+    /// Return the key that identifies the component of `p`, or `None` if `p` has not been seen
+    pub fn find(&mut self, p: K) -> Option<K> {
+        let p = self.sites.get(p)?;
+        let root = self.uf.find(p);
+        Some(self.keys[root].clone())
+    }
+
+    /// Are the keys `p` and `q` in the same component? A key that has not been seen is only
+    /// connected to itself.
+    pub fn connected(&mut self, p: K, q: K) -> bool {
+        if p == q {
+            return true;
+        }
+        match (self.sites.get(p), self.sites.get(q)) {
+            (Some(p), Some(q)) => self.uf.connected(p, q),
+            _ => false,
+        }
+    }
+
+    /// Return the number of components of the keys seen so far
+    pub fn count(&self) -> usize {
+        self.uf.count()
+    }
+
+    /// Return the number of keys seen so far
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Have any keys been seen?
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    // The site number of the key, adding it if needed
+    fn site(&mut self, key: K) -> usize {
+        if let Some(site) = self.sites.get(key.clone()) {
+            return site;
+        }
+        let site = self.uf.add();
+        self.sites.put(key.clone(), site);
+        self.keys.push(key);
+        site
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::{
+        KeyedUnionFind, QuickFindUF, QuickUnionUF, UnionFind, WeightedQuickUnionPathCompressionUF,
+        WeightedQuickUnionUF,
+    };
+
+    // The pairs of tinyUF.txt from Algorithms, 4th edition
+    const TINY: [(usize, usize); 11] = [
+        (4, 3),
+        (3, 8),
+        (6, 5),
+        (9, 4),
+        (2, 1),
+        (8, 9),
+        (5, 0),
+        (7, 2),
+        (6, 1),
+        (1, 0),
+        (6, 7),
+    ];
+
+    fn check<U: UnionFind>(mut uf: U) {
+        for (p, q) in TINY {
+            uf.union(p, q);
+        }
+        assert_eq!(uf.count(), 2);
+        assert!(uf.connected(3, 9));
+        assert!(uf.connected(0, 7));
+        assert!(!uf.connected(0, 9));
+        assert_eq!(uf.find(2), uf.find(6));
+
+        let p = uf.add();
+        assert_eq!(p, 10);
+        assert_eq!(uf.count(), 3);
+        uf.union(p, 0);
+        assert!(uf.connected(10, 5));
+        assert_eq!(uf.count(), 2);
+    }
+
+    #[test]
+    fn test_union_find() {
+        check(QuickFindUF::new(10));
+        check(QuickUnionUF::new(10));
+        check(WeightedQuickUnionUF::new(10));
+        check(WeightedQuickUnionPathCompressionUF::new(10));
+    }
+
+    #[test]
+    fn test_same_components() {
+        let n = 200;
+        let mut quick_find = QuickFindUF::new(n);
+        let mut weighted = WeightedQuickUnionUF::new(n);
+        let mut compressed = WeightedQuickUnionPathCompressionUF::new(n);
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..150 {
+            let (p, q) = (rng.gen_range(0..n), rng.gen_range(0..n));
+            quick_find.union(p, q);
+            weighted.union(p, q);
+            compressed.union(p, q);
+        }
+        assert_eq!(quick_find.count(), weighted.count());
+        assert_eq!(quick_find.count(), compressed.count());
+        for _ in 0..500 {
+            let (p, q) = (rng.gen_range(0..n), rng.gen_range(0..n));
+            let connected = quick_find.connected(p, q);
+            assert_eq!(weighted.connected(p, q), connected);
+            assert_eq!(compressed.connected(p, q), connected);
+        }
+    }
+
+    #[test]
+    fn test_path_compression() {
+        // union(i, i + 1) with equal sizes hangs 0 below 1, 1 below 2, ... without weighting
+        let mut uf = QuickUnionUF::new(5);
+        for i in 0..4 {
+            uf.union(i, i + 1);
+        }
+        assert_eq!(uf.parent, vec![1, 2, 3, 4, 4]);
+
+        let mut uf = WeightedQuickUnionPathCompressionUF::new(8);
+        for (p, q) in [(0, 1), (2, 3), (0, 2), (4, 5), (6, 7), (4, 6), (0, 4)] {
+            uf.union(p, q);
+        }
+        // 7 is three links below the root; find links it, 6 and 4 directly to the root
+        let root = uf.find(7);
+        assert_eq!(root, 0);
+        assert!([4, 6, 7].iter().all(|&p| uf.parent[p] == root));
+    }
+
+    #[test]
+    fn test_keyed_union_find() {
+        let mut uf: KeyedUnionFind<&str> = KeyedUnionFind::new();
+        uf.union("JFK", "MCO");
+        uf.union("ORD", "DEN");
+        uf.union("ORD", "HOU");
+        uf.union("DEN", "PHX");
+        assert_eq!(uf.len(), 6);
+        assert_eq!(uf.count(), 2);
+        assert!(uf.connected("HOU", "PHX"));
+        assert!(!uf.connected("JFK", "PHX"));
+        assert!(!uf.connected("JFK", "LAX"));
+        assert!(uf.connected("LAX", "LAX"));
+        assert_eq!(uf.find("MCO"), uf.find("JFK"));
+        assert_eq!(uf.find("LAX"), None);
+
+        uf.union("MCO", "HOU");
+        assert_eq!(uf.count(), 1);
+        assert!(uf.connected("JFK", "PHX"));
+
+        let mut uf: KeyedUnionFind<String, QuickFindUF> = KeyedUnionFind::new();
+        uf.union("a".to_string(), "b".to_string());
+        assert_eq!(uf.count(), 1);
+    }
+}